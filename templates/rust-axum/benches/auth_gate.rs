@@ -0,0 +1,63 @@
+//! `cargo bench --bench auth_gate`
+//!
+//! Per-request cost of turning a bearer token into claims: rebuilding the
+//! key and validation rules on every call (how `AuthGate` used to work),
+//! with a prebuilt `TokenVerifier`, and with a warm `TokenCache`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use std::sync::Arc;
+
+// Its test module's `use super::*` goes unused when compiled into the bench
+#[path = "../src/api/users/gates/credentials.rs"]
+#[allow(dead_code, unused_imports)]
+mod credentials;
+#[path = "../src/api/users/gates/token_cache.rs"]
+#[allow(dead_code)]
+mod token_cache;
+
+use credentials::{JwtClaims, TokenVerifier};
+use token_cache::TokenCache;
+
+const SECRET: &[u8] = b"bench-secret-at-least-32-characters";
+
+fn token() -> String {
+    let claims = serde_json::json!({
+        "sub": "user-123",
+        "tenant_id": "acme",
+        "email": "john@example.com",
+        "scopes": ["users:read", "users:update"],
+        "roles": ["support"],
+        "exp": jsonwebtoken::get_current_timestamp() + 3600,
+    });
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(SECRET)).unwrap()
+}
+
+fn verify(c: &mut Criterion) {
+    let token = token();
+    let verifier = TokenVerifier::new([SECRET]);
+    let cache = TokenCache::new(10_000);
+    cache.insert(&token, Arc::new(verifier.decode(&token, 0).unwrap()));
+    // A full cache, so lookups pay for a realistic table
+    for i in 0..9_999 {
+        cache.insert(&format!("{}{}", token, i), Arc::new(verifier.decode(&token, 0).unwrap()));
+    }
+    cache.get(&token, 0);
+
+    let mut group = c.benchmark_group("auth_gate_verify");
+    group.bench_function("key_per_request", |b| {
+        b.iter(|| {
+            let key = DecodingKey::from_secret(SECRET);
+            decode::<JwtClaims>(black_box(&token), &key, &Validation::new(Algorithm::HS256)).unwrap()
+        })
+    });
+    group.bench_function("prebuilt_verifier", |b| b.iter(|| verifier.decode(black_box(&token), 0).unwrap()));
+    group.bench_function("cache_hit", |b| {
+        let now = jsonwebtoken::get_current_timestamp();
+        b.iter(|| cache.get(black_box(&token), now).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, verify);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+//! `cargo bench --bench logger`
+//!
+//! A typical warn-level call site logged the way `BrikLogger` used to do it
+//! (a `LogContext` built by value, `extra` as a JSON map, every field through
+//! `Debug`) against `brik_log!`, with the level enabled and disabled.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::io;
+use tracing::Level;
+
+// Nested so `$crate::shared::observability::logger` in `brik_log!` resolves;
+// only `logger.rs` is loaded from `src/shared`
+#[path = "../src/shared"]
+mod shared {
+    pub mod observability {
+        // Its test module's `use super::*` goes unused when compiled into the bench
+        #[allow(dead_code, unused_imports)]
+        pub mod logger;
+    }
+}
+
+use shared::observability::logger::LogContext;
+
+struct PortError {
+    port: String,
+    code: String,
+    message: String,
+}
+
+fn legacy_warn(error: &PortError, attempt: u32) {
+    let ctx = LogContext::new()
+        .with_port(error.port.clone())
+        .with_extra("attempt", attempt)
+        .with_extra("code", &error.code)
+        .with_extra("error", error.message.as_str());
+    tracing::warn!(
+        correlation_id = ?ctx.correlation_id,
+        user_id = ?ctx.user_id,
+        endpoint = ?ctx.endpoint,
+        gate = ?ctx.gate,
+        port = ?ctx.port,
+        duration_ms = ?ctx.duration_ms,
+        extra = ?ctx.extra,
+        message = "Retrying port call"
+    );
+}
+
+fn brik_log_warn(error: &PortError, attempt: u32) {
+    brik_log!(
+        WARN,
+        "Retrying port call",
+        port = error.port.as_str(),
+        attempt = attempt,
+        code = error.code.as_str(),
+        error = error.message.as_str()
+    );
+}
+
+fn logging(c: &mut Criterion) {
+    let error = PortError {
+        port: "UserRepository".to_string(),
+        code: "DATABASE_TIMEOUT".to_string(),
+        message: "query timed out after 2s".to_string(),
+    };
+
+    for (name, max_level) in [("enabled", Level::TRACE), ("disabled", Level::ERROR)] {
+        let subscriber = tracing_subscriber::fmt().json().with_max_level(max_level).with_writer(io::sink).finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let mut group = c.benchmark_group(format!("logger_{}", name));
+            group.bench_function("legacy_log_context", |b| b.iter(|| legacy_warn(black_box(&error), 2)));
+            group.bench_function("brik_log", |b| b.iter(|| brik_log_warn(black_box(&error), 2)));
+            group.finish();
+        });
+    }
+}
+
+criterion_group!(benches, logging);
+criterion_main!(benches);
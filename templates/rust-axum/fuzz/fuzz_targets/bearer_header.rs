@@ -0,0 +1,25 @@
+//! `cargo +nightly fuzz run bearer_header`
+//!
+//! Any Authorization value either yields a non-empty, whitespace-free token
+//! taken from its end, or nothing; parsing never panics.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../../src/api/users/gates/credentials.rs"]
+#[allow(dead_code)]
+mod credentials;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(authorization) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Some(token) = credentials::bearer_token(authorization) {
+        assert!(!token.is_empty());
+        assert!(!token.contains(|c: char| c.is_ascii_whitespace()));
+        assert!(authorization.trim_end().ends_with(token));
+        assert!(authorization[..6].eq_ignore_ascii_case("bearer"));
+    }
+});
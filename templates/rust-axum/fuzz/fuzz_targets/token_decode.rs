@@ -0,0 +1,28 @@
+//! `cargo +nightly fuzz run token_decode`
+//!
+//! Decoding arbitrary tokens, including well-formed JWTs with hostile headers
+//! and claims, returns an error instead of panicking. Only a token signed
+//! with one of the secrets may decode.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::sync::OnceLock;
+
+#[path = "../../src/api/users/gates/credentials.rs"]
+#[allow(dead_code)]
+mod credentials;
+
+const SECRETS: [&[u8]; 2] = [b"fuzz-primary-secret", b"fuzz-previous-secret"];
+
+static VERIFIER: OnceLock<credentials::TokenVerifier> = OnceLock::new();
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(token) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    // Libfuzzer cannot forge an HMAC, so nothing it produces may verify
+    let verifier = VERIFIER.get_or_init(|| credentials::TokenVerifier::new(SECRETS));
+    assert!(verifier.decode(token, 0).is_err());
+});
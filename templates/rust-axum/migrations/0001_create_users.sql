@@ -0,0 +1,12 @@
+-- BRIK v5 - Users table
+
+CREATE TABLE IF NOT EXISTS users (
+    id          UUID PRIMARY KEY,
+    email       TEXT NOT NULL UNIQUE,
+    name        TEXT NOT NULL,
+    age         INTEGER NOT NULL CHECK (age BETWEEN 13 AND 150),
+    profile     JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at  TIMESTAMPTZ NOT NULL,
+    updated_at  TIMESTAMPTZ NOT NULL,
+    version     BIGINT NOT NULL DEFAULT 1
+);
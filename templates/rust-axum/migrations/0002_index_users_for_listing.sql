@@ -0,0 +1,5 @@
+-- BRIK v5 - Keyset indexes for GET /users sort fields
+
+CREATE INDEX IF NOT EXISTS users_created_at_id_idx ON users (created_at, id);
+CREATE INDEX IF NOT EXISTS users_name_id_idx ON users (name, id);
+CREATE INDEX IF NOT EXISTS users_age_id_idx ON users (age, id);
@@ -0,0 +1,21 @@
+-- BRIK v5 - Transactional outbox, written in the same transaction as the aggregate
+
+CREATE TABLE IF NOT EXISTS outbox_events (
+    id               BIGSERIAL PRIMARY KEY,
+    aggregate_type   TEXT NOT NULL,
+    aggregate_id     UUID NOT NULL,
+    event_type       TEXT NOT NULL,
+    payload          JSONB NOT NULL,
+    occurred_at      TIMESTAMPTZ NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+    attempts         INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_error       TEXT,
+    published_at     TIMESTAMPTZ,
+    dead_at          TIMESTAMPTZ
+);
+
+-- The relay only ever scans rows that still need publishing
+CREATE INDEX IF NOT EXISTS outbox_events_pending_idx
+    ON outbox_events (id)
+    WHERE published_at IS NULL AND dead_at IS NULL;
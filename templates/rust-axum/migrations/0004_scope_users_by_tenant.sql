@@ -0,0 +1,16 @@
+-- BRIK v5 - Users belong to a tenant; emails are unique per tenant
+
+-- Existing rows join the tenant used for tokens without a tenant claim
+ALTER TABLE users ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE users ALTER COLUMN tenant_id DROP DEFAULT;
+
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_email_key;
+CREATE UNIQUE INDEX IF NOT EXISTS users_tenant_email_idx ON users (tenant_id, email);
+
+-- Every lookup filters by tenant first, so the keyset indexes lead with it
+DROP INDEX IF EXISTS users_created_at_id_idx;
+DROP INDEX IF EXISTS users_name_id_idx;
+DROP INDEX IF EXISTS users_age_id_idx;
+CREATE INDEX IF NOT EXISTS users_tenant_created_at_id_idx ON users (tenant_id, created_at, id);
+CREATE INDEX IF NOT EXISTS users_tenant_name_id_idx ON users (tenant_id, name, id);
+CREATE INDEX IF NOT EXISTS users_tenant_age_id_idx ON users (tenant_id, age, id);
@@ -0,0 +1,5 @@
+-- BRIK v5 - Lets the relay hold back aggregates behind a dead event
+
+CREATE INDEX IF NOT EXISTS outbox_events_dead_aggregate_idx
+    ON outbox_events (aggregate_type, aggregate_id, id)
+    WHERE published_at IS NULL AND dead_at IS NOT NULL;
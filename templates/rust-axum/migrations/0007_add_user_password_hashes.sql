@@ -0,0 +1,4 @@
+-- BRIK v5 - Password hashes, self-describing (bcrypt or argon2id PHC strings)
+
+-- Existing users have no password until one is set
+ALTER TABLE users ADD COLUMN IF NOT EXISTS password_hash TEXT;
@@ -0,0 +1,94 @@
+//! BRIK v5 Application State - Ports and gates shared by every handler
+
+use crate::api::health::health_checker::HealthChecker;
+use crate::api::users::adapters::user_event_stream::UserEventStream;
+use crate::api::users::domain::ports::user_repository::UserRepository;
+use crate::api::users::gates::auth_gate::{AuthGate, UserScopes};
+use crate::api::users::gates::token_cache::TokenCache;
+use crate::shared::config::settings::Settings;
+use crate::shared::ids::id_generator::{id_generator_from_settings, SharedIdGenerator};
+use crate::shared::security::password_hasher::{password_hasher_from_settings, PasswordHasher};
+use crate::shared::storage::blob_storage::BlobStorage;
+use crate::shared::tenancy::tenant::TenantRegistry;
+use crate::shared::time::clock::SharedClock;
+use metrics_exporter_prometheus::PrometheusHandle;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub user_repository: Arc<dyn UserRepository>,
+    pub blob_storage: Arc<dyn BlobStorage>,
+    pub user_events: Arc<UserEventStream>,
+    pub create_user_gate: Arc<AuthGate>,
+    pub read_user_gate: Arc<AuthGate>,
+    pub update_user_gate: Arc<AuthGate>,
+    /// Any valid token; per-event visibility is decided by scopes
+    pub user_events_gate: Arc<AuthGate>,
+    /// Per-tenant overrides; the gates resolve the tenant itself
+    pub tenants: Arc<TenantRegistry>,
+    pub health_checker: Arc<HealthChecker>,
+    /// `SystemClock` outside tests; entities and gates read time from it
+    pub clock: SharedClock,
+    /// Selected by `ids.version` on `clock` unless replaced with `with_id_generator`
+    pub ids: SharedIdGenerator,
+    /// Selected by `password.algorithm`; see `domain::services::user_passwords`
+    pub password_hasher: Arc<dyn PasswordHasher>,
+    /// `http.max_avatar_bytes`, enforced while the upload streams in
+    pub max_avatar_bytes: usize,
+    /// Renders `/metrics` once the Prometheus recorder is installed
+    pub metrics: Option<PrometheusHandle>,
+}
+
+impl AppState {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        blob_storage: Arc<dyn BlobStorage>,
+        user_events: Arc<UserEventStream>,
+        settings: &Settings,
+        tenants: Arc<TenantRegistry>,
+        health_checker: Arc<HealthChecker>,
+        clock: SharedClock,
+    ) -> Self {
+        let auth = &settings.auth;
+        // One cache for every gate: a token verified by one is verified for all
+        let token_cache = (auth.token_cache_capacity > 0).then(|| Arc::new(TokenCache::new(auth.token_cache_capacity)));
+        let gate = |scopes| {
+            let gate = AuthGate::from_settings(auth, scopes)
+                .with_tenants(tenants.clone())
+                .with_clock(clock.clone());
+            Arc::new(match &token_cache {
+                Some(token_cache) => gate.with_token_cache(token_cache.clone()),
+                None => gate,
+            })
+        };
+
+        Self {
+            user_repository,
+            blob_storage,
+            user_events,
+            create_user_gate: gate(vec![UserScopes::create()]),
+            read_user_gate: gate(vec![UserScopes::read()]),
+            update_user_gate: gate(vec![UserScopes::update()]),
+            user_events_gate: gate(vec![]),
+            tenants,
+            health_checker,
+            ids: id_generator_from_settings(&settings.ids, clock.clone()),
+            password_hasher: password_hasher_from_settings(&settings.password),
+            clock,
+            max_avatar_bytes: settings.http.max_avatar_bytes,
+            metrics: None,
+        }
+    }
+
+    /// Source of new entity ids, e.g. `SequentialIdGenerator` in tests
+    pub fn with_id_generator(mut self, ids: SharedIdGenerator) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Mounts `/metrics` in `build_router`
+    pub fn with_metrics(mut self, handle: PrometheusHandle) -> Self {
+        self.metrics = Some(handle);
+        self
+    }
+}
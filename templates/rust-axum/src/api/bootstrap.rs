@@ -0,0 +1,194 @@
+//! BRIK v5 Bootstrap - Builds the running application from `Settings`
+//!
+//! The composition root: connects Postgres and Redis, stacks the user
+//! repository decorators, installs the Prometheus recorder, starts the
+//! outbox relay and serves the router until Ctrl-C. The binary's `main`
+//! only awaits `run`, which also answers `export-error-catalog`.
+
+use crate::api::app_state::AppState;
+use crate::api::health::health_checker::HealthChecker;
+use crate::api::health::probes::{PostgresProbe, RedisProbe};
+use crate::api::router::build_router;
+use crate::api::users::adapters::cached_user_repository::CachedUserRepository;
+use crate::api::users::adapters::circuit_breaking_user_repository::CircuitBreakingUserRepository;
+use crate::api::users::adapters::event_publishing_user_repository::EventPublishingUserRepository;
+use crate::api::users::adapters::postgres_user_repository::PostgresUserRepository;
+use crate::api::users::adapters::retrying_user_repository::RetryingUserRepository;
+use crate::api::users::adapters::user_event_stream::UserEventStream;
+use crate::api::users::domain::ports::user_repository::UserRepository;
+use crate::brik_log;
+use crate::shared::cache::cache_store::RedisCacheStore;
+use crate::shared::cache::circuit_breaking_cache_store::CircuitBreakingCacheStore;
+use crate::shared::config::settings::{Settings, SettingsError};
+use crate::shared::errors::error_code::export_command;
+use crate::shared::observability::logger::init_logger;
+use crate::shared::observability::metrics::install_prometheus_recorder;
+use crate::shared::outbox::outbox_publisher::outbox_publisher_from_settings;
+use crate::shared::outbox::outbox_relay::OutboxRelay;
+use crate::shared::outbox::outbox_store::PostgresOutboxStore;
+use crate::shared::resilience::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use crate::shared::resilience::retry::{RetryConfig, RetryPolicy};
+use crate::shared::storage::blob_storage::LocalFileBlobStorage;
+use crate::shared::tenancy::tenant::TenantRegistry;
+use crate::shared::time::clock::{SharedClock, SystemClock};
+use axum::Router;
+use metrics_exporter_prometheus::{BuildError, PrometheusHandle};
+use redis::aio::ConnectionManager;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+
+#[derive(Debug, Error)]
+pub enum BootstrapError {
+    #[error(transparent)]
+    Settings(#[from] SettingsError),
+
+    #[error("{0}")]
+    Usage(String),
+
+    #[error("Failed to initialise logging: {0}")]
+    Logging(String),
+
+    #[error("Failed to connect to Postgres: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Failed to connect to Redis: {0}")]
+    Redis(#[from] redis::RedisError),
+
+    #[error("Failed to install the metrics recorder: {0}")]
+    Metrics(#[from] BuildError),
+
+    #[error("Server failed: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Connections shared by every adapter
+#[derive(Clone)]
+pub struct Infrastructure {
+    pub pool: PgPool,
+    pub redis: ConnectionManager,
+}
+
+impl Infrastructure {
+    pub async fn connect(settings: &Settings) -> Result<Self, BootstrapError> {
+        let pool = PgPoolOptions::new()
+            .max_connections(settings.database.pool_size)
+            .acquire_timeout(Duration::from_secs(settings.database.connect_timeout_secs))
+            .connect(&settings.database.url)
+            .await?;
+        let redis = ConnectionManager::new(redis::Client::open(settings.redis.url.as_str())?).await?;
+
+        Ok(Self { pool, redis })
+    }
+}
+
+/// Postgres behind retries, a circuit breaker and the read-through cache;
+/// events go to in-process subscribers once a write committed
+pub fn user_repository(
+    infrastructure: &Infrastructure,
+    user_events: Arc<UserEventStream>,
+    settings: &Settings,
+    clock: &SharedClock,
+) -> Arc<dyn UserRepository> {
+    let breaker = |port: &str| {
+        CircuitBreaker::new(
+            port,
+            CircuitBreakerConfig::from_settings(&settings.resilience.circuit_breaker),
+            clock.clone(),
+        )
+    };
+
+    let postgres = Arc::new(PostgresUserRepository::new(infrastructure.pool.clone()).with_clock(clock.clone()));
+    let retrying = Arc::new(RetryingUserRepository::new(
+        postgres,
+        RetryPolicy::new("UserRepository", RetryConfig::from_settings(&settings.resilience.retry)),
+    ));
+    let guarded = Arc::new(CircuitBreakingUserRepository::new(retrying, breaker("UserRepository")));
+    let cache_store = Arc::new(CircuitBreakingCacheStore::new(
+        Arc::new(RedisCacheStore::new(infrastructure.redis.clone())),
+        breaker("CacheStore"),
+    ));
+    let cached = Arc::new(CachedUserRepository::from_settings(guarded, cache_store, &settings.redis));
+
+    Arc::new(EventPublishingUserRepository::new(cached, user_events).with_clock(clock.clone()))
+}
+
+/// Relays the outbox written by `PostgresUserRepository`
+pub fn outbox_relay(infrastructure: &Infrastructure, settings: &Settings, clock: &SharedClock) -> OutboxRelay {
+    OutboxRelay::from_settings(
+        Arc::new(PostgresOutboxStore::new(infrastructure.pool.clone())),
+        outbox_publisher_from_settings(&settings.outbox, infrastructure.redis.clone()),
+        &settings.outbox,
+    )
+    .with_clock(clock.clone())
+}
+
+/// The full router over real adapters
+pub fn build_app(
+    infrastructure: &Infrastructure,
+    settings: &Settings,
+    metrics: PrometheusHandle,
+    clock: SharedClock,
+) -> Router {
+    let user_events = Arc::new(UserEventStream::default());
+    let health_checker = HealthChecker::new()
+        .with_clock(clock.clone())
+        .with_probe(PostgresProbe::new(infrastructure.pool.clone()))
+        .with_probe(RedisProbe::new(infrastructure.redis.clone()));
+
+    let state = AppState::new(
+        user_repository(infrastructure, user_events.clone(), settings, &clock),
+        Arc::new(LocalFileBlobStorage::new(&settings.storage.local_root, &settings.storage.public_base_url)),
+        user_events,
+        settings,
+        Arc::new(TenantRegistry::from_settings(&settings.tenancy)),
+        Arc::new(health_checker),
+        clock,
+    )
+    .with_metrics(metrics);
+
+    build_router(state, settings)
+}
+
+/// Serves until Ctrl-C, then drains in-flight requests and stops the relay
+pub async fn serve(settings: Settings) -> Result<(), BootstrapError> {
+    let metrics = install_prometheus_recorder()?;
+    let infrastructure = Infrastructure::connect(&settings).await?;
+    let clock = SystemClock::shared();
+    let router = build_app(&infrastructure, &settings, metrics, clock.clone());
+
+    let (stop, mut stopped) = watch::channel(());
+    let relay = tokio::spawn(outbox_relay(&infrastructure, &settings, &clock).run(async move {
+        let _ = stopped.changed().await;
+    }));
+
+    let listener = TcpListener::bind((settings.server.host.as_str(), settings.server.port)).await?;
+    let address = listener.local_addr()?;
+    brik_log!(INFO, "Listening", address = %address);
+
+    let served = axum::serve(listener, router)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await;
+
+    // Also stops the relay when serving failed
+    drop(stop);
+    let _ = relay.await;
+    Ok(served?)
+}
+
+/// Entry point: `export-error-catalog` prints the catalog and exits;
+/// otherwise logging, settings from the environment, then `serve`
+pub async fn run() -> Result<(), BootstrapError> {
+    if let Some(exported) = export_command(std::env::args().skip(1)) {
+        println!("{}", exported.map_err(BootstrapError::Usage)?);
+        return Ok(());
+    }
+
+    init_logger().map_err(|error| BootstrapError::Logging(error.to_string()))?;
+    serve(Settings::load()?).await
+}
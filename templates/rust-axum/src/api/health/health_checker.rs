@@ -0,0 +1,359 @@
+//! BRIK v5 Health Checker - Concurrent probe execution with cached reports
+
+use super::probes::{DependencyStatus, HealthProbe};
+use crate::brik_log;
+use crate::shared::time::clock::{SharedClock, SystemClock};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use utoipa::ToSchema;
+
+/// Overall service status, as defined by the `HealthCheck` schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Healthy,
+    Unhealthy,
+    Degraded,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DependencyHealth {
+    #[schema(inline)]
+    pub status: DependencyStatus,
+    pub response_time_ms: f64,
+    /// Present when the probe failed or timed out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HealthCheck {
+    #[schema(inline)]
+    pub status: HealthStatus,
+    pub timestamp: DateTime<Utc>,
+    #[schema(example = "1.0.0")]
+    pub version: String,
+    /// Uptime in seconds
+    #[schema(example = 3600)]
+    pub uptime_seconds: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(inline)]
+    pub dependencies: Option<BTreeMap<String, DependencyHealth>>,
+}
+
+struct CachedReport {
+    checked_at: Instant,
+    report: HealthCheck,
+}
+
+/// Runs every registered probe concurrently and caches the aggregated report
+pub struct HealthChecker {
+    probes: Vec<Arc<dyn HealthProbe>>,
+    cache_ttl: Duration,
+    started_at: Instant,
+    version: String,
+    cache: Mutex<Option<CachedReport>>,
+    /// Timestamps, uptime, probe timings and cache age
+    clock: SharedClock,
+}
+
+impl HealthChecker {
+    pub fn new() -> Self {
+        let clock = SystemClock::shared();
+        Self {
+            probes: Vec::new(),
+            cache_ttl: Duration::from_secs(5),
+            started_at: clock.instant(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            cache: Mutex::new(None),
+            clock,
+        }
+    }
+
+    /// Uptime restarts from the new clock's current reading
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.started_at = clock.instant();
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_probe<P>(mut self, probe: P) -> Self
+    where
+        P: HealthProbe + 'static,
+    {
+        self.probes.push(Arc::new(probe));
+        self
+    }
+
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    pub fn uptime_seconds(&self) -> u64 {
+        self.elapsed_since(self.started_at).as_secs()
+    }
+
+    fn elapsed_since(&self, earlier: Instant) -> Duration {
+        self.clock.instant().saturating_duration_since(earlier)
+    }
+
+    /// Liveness report: the process is running, dependencies are not probed
+    pub fn liveness(&self) -> HealthCheck {
+        HealthCheck {
+            status: HealthStatus::Healthy,
+            timestamp: self.clock.now(),
+            version: self.version.clone(),
+            uptime_seconds: self.uptime_seconds(),
+            dependencies: None,
+        }
+    }
+
+    /// Readiness report: probes every dependency, reusing a recent result
+    /// while it is younger than the cache TTL
+    pub async fn readiness(&self) -> HealthCheck {
+        // Holding the lock while probing also collapses concurrent checks
+        // into a single round of probes
+        let mut cache = self.cache.lock().await;
+
+        if let Some(cached) = cache.as_ref() {
+            if self.elapsed_since(cached.checked_at) < self.cache_ttl {
+                return cached.report.clone();
+            }
+        }
+
+        let report = self.run_probes().await;
+        *cache = Some(CachedReport {
+            checked_at: self.clock.instant(),
+            report: report.clone(),
+        });
+
+        report
+    }
+
+    async fn run_probes(&self) -> HealthCheck {
+        let checks = self.probes.iter().map(|probe| {
+            let probe = Arc::clone(probe);
+            async move {
+                let start = self.clock.instant();
+                let outcome = tokio::time::timeout(probe.timeout(), probe.check()).await;
+                let response_time_ms = self.elapsed_since(start).as_secs_f64() * 1000.0;
+
+                let (status, error) = match outcome {
+                    Ok(Ok(status)) => (status, None),
+                    Ok(Err(message)) => (DependencyStatus::Down, Some(message)),
+                    Err(_) => (
+                        DependencyStatus::Down,
+                        Some(format!("Probe timed out after {}ms", probe.timeout().as_millis())),
+                    ),
+                };
+
+                if let Some(message) = &error {
+                    brik_log!(
+                        WARN,
+                        "Health probe failed",
+                        port = probe.name(),
+                        duration_ms = response_time_ms as u64,
+                        reason = message.as_str()
+                    );
+                }
+
+                (
+                    probe.name(),
+                    probe.is_critical(),
+                    DependencyHealth {
+                        status,
+                        response_time_ms,
+                        error,
+                    },
+                )
+            }
+        });
+
+        let results = futures::future::join_all(checks).await;
+
+        let status = aggregate_status(
+            results
+                .iter()
+                .map(|(_, critical, health)| (*critical, health.status)),
+        );
+
+        let dependencies = results
+            .into_iter()
+            .map(|(name, _, health)| (name.to_string(), health))
+            .collect();
+
+        HealthCheck {
+            status,
+            timestamp: self.clock.now(),
+            version: self.version.clone(),
+            uptime_seconds: self.uptime_seconds(),
+            dependencies: Some(dependencies),
+        }
+    }
+}
+
+impl Default for HealthChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Critical dependency down => unhealthy; optional dependency down or any
+/// dependency degraded => degraded; otherwise healthy
+pub fn aggregate_status<I>(statuses: I) -> HealthStatus
+where
+    I: IntoIterator<Item = (bool, DependencyStatus)>,
+{
+    let mut overall = HealthStatus::Healthy;
+
+    for (critical, status) in statuses {
+        match (critical, status) {
+            (true, DependencyStatus::Down) => return HealthStatus::Unhealthy,
+            (false, DependencyStatus::Down) | (_, DependencyStatus::Degraded) => {
+                overall = HealthStatus::Degraded;
+            }
+            (_, DependencyStatus::Up) => {}
+        }
+    }
+
+    overall
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::time::clock::{Clock, ManualClock};
+    use crate::shared::types::result::BrikResult;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FakeProbe {
+        name: &'static str,
+        critical: bool,
+        result: BrikResult<DependencyStatus, String>,
+        delay: Duration,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl FakeProbe {
+        fn new(name: &'static str, critical: bool, result: BrikResult<DependencyStatus, String>) -> Self {
+            Self {
+                name,
+                critical,
+                result,
+                delay: Duration::ZERO,
+                calls: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl HealthProbe for FakeProbe {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn is_critical(&self) -> bool {
+            self.critical
+        }
+
+        fn timeout(&self) -> Duration {
+            Duration::from_millis(50)
+        }
+
+        async fn check(&self) -> BrikResult<DependencyStatus, String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.result.clone()
+        }
+    }
+
+    #[test]
+    fn test_aggregate_status() {
+        use DependencyStatus::*;
+
+        assert_eq!(aggregate_status(vec![]), HealthStatus::Healthy);
+        assert_eq!(aggregate_status(vec![(true, Up), (false, Up)]), HealthStatus::Healthy);
+        assert_eq!(aggregate_status(vec![(true, Up), (false, Down)]), HealthStatus::Degraded);
+        assert_eq!(aggregate_status(vec![(true, Degraded), (false, Up)]), HealthStatus::Degraded);
+        assert_eq!(aggregate_status(vec![(false, Down), (true, Down)]), HealthStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_optional_dependency_down_is_degraded() {
+        let checker = HealthChecker::new()
+            .with_probe(FakeProbe::new("database", true, Ok(DependencyStatus::Up)))
+            .with_probe(FakeProbe::new("redis", false, Err("connection refused".to_string())));
+
+        let report = checker.readiness().await;
+        let dependencies = report.dependencies.unwrap();
+
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert_eq!(dependencies["database"].status, DependencyStatus::Up);
+        assert_eq!(dependencies["redis"].status, DependencyStatus::Down);
+        assert_eq!(dependencies["redis"].error.as_deref(), Some("connection refused"));
+    }
+
+    #[tokio::test]
+    async fn test_probe_timeout_reports_down() {
+        let mut slow = FakeProbe::new("database", true, Ok(DependencyStatus::Up));
+        slow.delay = Duration::from_millis(200);
+        let checker = HealthChecker::new().with_probe(slow);
+
+        let report = checker.readiness().await;
+
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+        assert_eq!(report.dependencies.unwrap()["database"].status, DependencyStatus::Down);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_is_cached() {
+        let probe = FakeProbe::new("database", true, Ok(DependencyStatus::Up));
+        let calls = Arc::clone(&probe.calls);
+        let checker = HealthChecker::new()
+            .with_probe(probe)
+            .with_cache_ttl(Duration::from_secs(60));
+
+        checker.readiness().await;
+        checker.readiness().await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cached_report_expires_after_ttl() {
+        let clock = ManualClock::starting_now();
+        let probe = FakeProbe::new("database", true, Ok(DependencyStatus::Up));
+        let calls = Arc::clone(&probe.calls);
+        let checker = HealthChecker::new()
+            .with_clock(clock.clone())
+            .with_probe(probe)
+            .with_cache_ttl(Duration::from_secs(5));
+
+        let first = checker.readiness().await;
+        clock.advance(Duration::from_secs(4));
+        assert_eq!(checker.readiness().await.timestamp, first.timestamp);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        clock.advance(Duration::from_secs(1));
+        let refreshed = checker.readiness().await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(refreshed.timestamp, clock.now());
+        assert_eq!(refreshed.uptime_seconds, 5);
+    }
+
+    #[test]
+    fn test_liveness_skips_dependencies() {
+        let checker = HealthChecker::new()
+            .with_probe(FakeProbe::new("database", true, Err("down".to_string())));
+
+        let report = checker.liveness();
+
+        assert_eq!(report.status, HealthStatus::Healthy);
+        assert!(report.dependencies.is_none());
+    }
+}
@@ -0,0 +1,114 @@
+//! BRIK v5 Health Routes - /health, /health/live and /health/ready
+
+use super::health_checker::{HealthCheck, HealthChecker, HealthStatus};
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use std::sync::Arc;
+
+pub fn health_routes(checker: Arc<HealthChecker>) -> Router {
+    Router::new()
+        .route("/health", get(health_check))
+        .route("/health/live", get(liveness))
+        .route("/health/ready", get(readiness))
+        .with_state(checker)
+}
+
+/// Full report; degraded still answers 200 so optional outages don't page
+#[utoipa::path(
+    get,
+    path = "/health",
+    operation_id = "health_check",
+    tag = "Health",
+    security(()),
+    responses(
+        (status = 200, description = "Service is healthy or degraded (an optional dependency is down)", body = HealthCheck),
+        (status = 503, description = "Service is unhealthy (a critical dependency is down)", body = HealthCheck),
+    )
+)]
+pub async fn health_check(State(checker): State<Arc<HealthChecker>>) -> (StatusCode, Json<HealthCheck>) {
+    let report = checker.readiness().await;
+    (status_code(&report), Json(report))
+}
+
+/// Liveness never touches dependencies: restarting the process won't fix them
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    operation_id = "health_live",
+    tag = "Health",
+    security(()),
+    responses(
+        (status = 200, description = "Process is alive", body = HealthCheck),
+    )
+)]
+pub async fn liveness(State(checker): State<Arc<HealthChecker>>) -> Json<HealthCheck> {
+    Json(checker.liveness())
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    operation_id = "health_ready",
+    tag = "Health",
+    security(()),
+    responses(
+        (status = 200, description = "Ready to receive traffic (healthy or degraded)", body = HealthCheck),
+        (status = 503, description = "Not ready - a critical dependency is down", body = HealthCheck),
+    )
+)]
+pub async fn readiness(State(checker): State<Arc<HealthChecker>>) -> (StatusCode, Json<HealthCheck>) {
+    let report = checker.readiness().await;
+    (status_code(&report), Json(report))
+}
+
+fn status_code(report: &HealthCheck) -> StatusCode {
+    match report.status {
+        HealthStatus::Healthy | HealthStatus::Degraded => StatusCode::OK,
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::health::probes::{DependencyStatus, HealthProbe};
+    use crate::shared::types::result::BrikResult;
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+
+    struct DownProbe;
+
+    #[async_trait::async_trait]
+    impl HealthProbe for DownProbe {
+        fn name(&self) -> &'static str {
+            "database"
+        }
+
+        async fn check(&self) -> BrikResult<DependencyStatus, String> {
+            Err("connection refused".to_string())
+        }
+    }
+
+    async fn get_status(router: Router, uri: &str) -> StatusCode {
+        router
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_live_and_ready_diverge_when_critical_dependency_down() {
+        let router = health_routes(Arc::new(HealthChecker::new().with_probe(DownProbe)));
+
+        assert_eq!(get_status(router.clone(), "/health/live").await, StatusCode::OK);
+        assert_eq!(get_status(router.clone(), "/health/ready").await, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(get_status(router, "/health").await, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_ready_without_probes() {
+        let router = health_routes(Arc::new(HealthChecker::new()));
+
+        assert_eq!(get_status(router, "/health/ready").await, StatusCode::OK);
+    }
+}
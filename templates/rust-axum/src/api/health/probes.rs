@@ -0,0 +1,173 @@
+//! BRIK v5 Health Probes - Dependency checks for the health endpoints
+
+use crate::shared::types::result::BrikResult;
+use redis::aio::ConnectionManager;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::time::Duration;
+use utoipa::ToSchema;
+
+/// Status reported for a single dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyStatus {
+    Up,
+    Down,
+    Degraded,
+}
+
+/// Trait for dependency probes used by the health endpoints
+#[async_trait::async_trait]
+pub trait HealthProbe: Send + Sync {
+    /// Name used as the key in the `dependencies` section of the report
+    fn name(&self) -> &'static str;
+
+    /// Critical dependencies make the service unhealthy when down;
+    /// optional ones only degrade it
+    fn is_critical(&self) -> bool {
+        true
+    }
+
+    /// Maximum time the probe may take before it is reported as down
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(2)
+    }
+
+    async fn check(&self) -> BrikResult<DependencyStatus, String>;
+}
+
+/// PostgreSQL probe - runs `SELECT 1` through the pool
+pub struct PostgresProbe {
+    pool: PgPool,
+    critical: bool,
+    timeout: Duration,
+}
+
+impl PostgresProbe {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            critical: true,
+            timeout: Duration::from_secs(2),
+        }
+    }
+
+    pub fn with_critical(mut self, critical: bool) -> Self {
+        self.critical = critical;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthProbe for PostgresProbe {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+
+    fn is_critical(&self) -> bool {
+        self.critical
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    async fn check(&self) -> BrikResult<DependencyStatus, String> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        // Reachable but with no idle connection left: requests will queue
+        if self.pool.num_idle() == 0 && self.pool.size() >= self.pool.options().get_max_connections() {
+            return Ok(DependencyStatus::Degraded);
+        }
+
+        Ok(DependencyStatus::Up)
+    }
+}
+
+/// Redis probe - issues `PING` over the connection manager
+pub struct RedisProbe {
+    connection: ConnectionManager,
+    critical: bool,
+    timeout: Duration,
+}
+
+impl RedisProbe {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self {
+            connection,
+            critical: false,
+            timeout: Duration::from_secs(1),
+        }
+    }
+
+    pub fn with_critical(mut self, critical: bool) -> Self {
+        self.critical = critical;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthProbe for RedisProbe {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    fn is_critical(&self) -> bool {
+        self.critical
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    async fn check(&self) -> BrikResult<DependencyStatus, String> {
+        let mut connection = self.connection.clone();
+        let reply: String = redis::cmd("PING")
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if reply == "PONG" {
+            Ok(DependencyStatus::Up)
+        } else {
+            Ok(DependencyStatus::Degraded)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dependency_status_serialization() {
+        assert_eq!(serde_json::to_value(DependencyStatus::Up).unwrap(), "up");
+        assert_eq!(serde_json::to_value(DependencyStatus::Down).unwrap(), "down");
+        assert_eq!(serde_json::to_value(DependencyStatus::Degraded).unwrap(), "degraded");
+    }
+
+    #[tokio::test]
+    async fn test_postgres_probe_defaults() {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/brik")
+            .unwrap();
+        let probe = PostgresProbe::new(pool).with_timeout(Duration::from_millis(500));
+
+        assert_eq!(probe.name(), "database");
+        assert!(probe.is_critical());
+        assert_eq!(probe.timeout(), Duration::from_millis(500));
+    }
+}
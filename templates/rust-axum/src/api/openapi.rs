@@ -0,0 +1,213 @@
+//! BRIK v5 OpenAPI - Code-first spec generated from handler and DTO annotations
+//! `openapi.yaml` stays checked in as the published contract; the drift test
+//! below keeps both in sync.
+
+use crate::api::health::health_checker::HealthCheck;
+use crate::api::health::health_routes::{
+    __path_health_check, __path_liveness, __path_readiness,
+};
+use crate::api::users::dto::user_dto::{
+    AvatarUpload, CreateUserRequest, CreateUserResponse, GetUserResponse, User, UserPage,
+    UserProfile,
+};
+use crate::api::users::handlers::create_user::__path_create_user;
+use crate::api::users::handlers::get_user::__path_get_user_by_id;
+use crate::api::users::handlers::list_users::__path_list_users;
+use crate::api::users::handlers::upload_avatar::__path_upload_avatar;
+use crate::api::users::handlers::user_events::__path_user_events;
+use crate::shared::errors::api_error::ErrorResponse;
+use crate::shared::errors::error_responses::{
+    BadRequest, Conflict, Forbidden, InternalServerError, NotFound, PayloadTooLarge,
+    ServiceUnavailable, TooManyRequests, Unauthorized, UnsupportedMediaType,
+};
+use crate::shared::pagination::page::PageInfo;
+use axum::Router;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+pub const DOCS_PATH: &str = "/docs";
+pub const SPEC_PATH: &str = "/api-docs/openapi.json";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "{{PROJECT_NAME}} API",
+        description = "BRIK v5 Rust API with Axum, circuitalidad digital and hexagonal architecture",
+        version = "1.0.0",
+    ),
+    servers(
+        (url = "/api/v1", description = "Current server"),
+    ),
+    paths(
+        health_check,
+        liveness,
+        readiness,
+        create_user,
+        list_users,
+        get_user_by_id,
+        upload_avatar,
+        user_events,
+    ),
+    components(
+        schemas(
+            ErrorResponse,
+            User,
+            UserProfile,
+            CreateUserRequest,
+            CreateUserResponse,
+            GetUserResponse,
+            UserPage,
+            PageInfo,
+            AvatarUpload,
+            HealthCheck,
+        ),
+        responses(
+            BadRequest,
+            Unauthorized,
+            Forbidden,
+            NotFound,
+            Conflict,
+            PayloadTooLarge,
+            UnsupportedMediaType,
+            TooManyRequests,
+            InternalServerError,
+            ServiceUnavailable,
+        ),
+    ),
+    modifiers(&SecurityAddon),
+    security(("bearerAuth" = [])),
+    tags(
+        (name = "Health", description = "Health check endpoints"),
+        (name = "Users", description = "User management endpoints"),
+    )
+)]
+pub struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "bearerAuth",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .build(),
+                ),
+            );
+        }
+    }
+}
+
+/// Swagger UI at `/docs`, backed by the generated spec
+pub fn docs_routes() -> Router {
+    SwaggerUi::new(DOCS_PATH).url(SPEC_PATH, ApiDoc::openapi()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::collections::BTreeSet;
+
+    const HTTP_METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+    fn generated_spec() -> Value {
+        serde_json::to_value(ApiDoc::openapi()).unwrap()
+    }
+
+    fn checked_in_spec() -> Value {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.yaml");
+        let yaml = std::fs::read_to_string(path).unwrap();
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    fn keys(value: &Value) -> BTreeSet<String> {
+        value
+            .as_object()
+            .map(|object| object.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// `METHOD path` for every operation in the spec
+    fn operations(spec: &Value) -> BTreeSet<String> {
+        let mut operations = BTreeSet::new();
+        for (path, item) in spec["paths"].as_object().unwrap() {
+            for method in HTTP_METHODS {
+                if item.get(method).is_some() {
+                    operations.insert(format!("{} {}", method.to_uppercase(), path));
+                }
+            }
+        }
+        operations
+    }
+
+    /// `METHOD path -> status` for every documented response
+    fn status_codes(spec: &Value) -> BTreeSet<String> {
+        let mut codes = BTreeSet::new();
+        for (path, item) in spec["paths"].as_object().unwrap() {
+            for method in HTTP_METHODS {
+                if let Some(operation) = item.get(method) {
+                    for status in keys(&operation["responses"]) {
+                        codes.insert(format!("{} {} -> {}", method.to_uppercase(), path, status));
+                    }
+                }
+            }
+        }
+        codes
+    }
+
+    /// `Schema.property` plus `Schema!required` for every component schema
+    fn schema_shapes(spec: &Value) -> BTreeSet<String> {
+        let mut shapes = BTreeSet::new();
+        for (name, schema) in spec["components"]["schemas"].as_object().unwrap() {
+            shapes.insert(name.clone());
+            for property in keys(&schema["properties"]) {
+                shapes.insert(format!("{}.{}", name, property));
+            }
+            for required in schema["required"].as_array().into_iter().flatten() {
+                shapes.insert(format!("{}!{}", name, required.as_str().unwrap()));
+            }
+        }
+        shapes
+    }
+
+    fn assert_no_drift(what: &str, generated: BTreeSet<String>, checked_in: BTreeSet<String>) {
+        let only_in_code: Vec<_> = generated.difference(&checked_in).collect();
+        let only_in_yaml: Vec<_> = checked_in.difference(&generated).collect();
+
+        assert!(
+            only_in_code.is_empty() && only_in_yaml.is_empty(),
+            "{} drifted from openapi.yaml\n  only in code: {:?}\n  only in openapi.yaml: {:?}",
+            what,
+            only_in_code,
+            only_in_yaml
+        );
+    }
+
+    #[test]
+    fn test_paths_match_openapi_yaml() {
+        assert_no_drift("Paths", operations(&generated_spec()), operations(&checked_in_spec()));
+    }
+
+    #[test]
+    fn test_status_codes_match_openapi_yaml() {
+        assert_no_drift("Status codes", status_codes(&generated_spec()), status_codes(&checked_in_spec()));
+    }
+
+    #[test]
+    fn test_schemas_match_openapi_yaml() {
+        assert_no_drift("Schemas", schema_shapes(&generated_spec()), schema_shapes(&checked_in_spec()));
+    }
+
+    #[test]
+    fn test_bearer_security_scheme() {
+        let spec = generated_spec();
+
+        assert_eq!(spec["components"]["securitySchemes"]["bearerAuth"]["scheme"], "bearer");
+        assert_eq!(spec["paths"]["/health"]["get"]["security"], serde_json::json!([{}]));
+    }
+}
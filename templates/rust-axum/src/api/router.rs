@@ -0,0 +1,204 @@
+//! BRIK v5 Router - Composes every route group under /api/v1 plus the docs
+
+use crate::api::app_state::AppState;
+use crate::api::health::health_routes::health_routes;
+use crate::api::openapi::docs_routes;
+use crate::api::users::users_routes::users_routes;
+use crate::shared::config::settings::Settings;
+use crate::shared::http::hardening::harden;
+use crate::shared::i18n::locale::negotiate_locale;
+use crate::shared::observability::metrics::metrics_routes;
+use crate::shared::resilience::bulkhead::apply_bulkheads;
+use crate::shared::storage::blob_storage::local_blob_routes;
+use axum::{middleware, Router};
+
+pub const API_PREFIX: &str = "/api/v1";
+
+pub fn build_router(state: AppState, settings: &Settings) -> Router {
+    let metrics = state.metrics.clone();
+    let api = Router::new()
+        .merge(health_routes(state.health_checker.clone()))
+        .merge(users_routes(state));
+
+    let router = Router::new()
+        .nest(API_PREFIX, api)
+        .merge(docs_routes())
+        .merge(local_blob_routes(&settings.storage));
+    // Inside the timeouts, so time spent queued counts against the request
+    let router = apply_bulkheads(router, &settings.resilience).layer(middleware::from_fn(negotiate_locale));
+    // Scrapes stay outside the bulkheads: they must work while requests are shed
+    let router = match metrics {
+        Some(handle) => router.merge(metrics_routes(handle)),
+        None => router,
+    };
+
+    harden(router, &settings.http)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::health::health_checker::HealthChecker;
+    use crate::api::users::adapters::in_memory_user_repository::InMemoryUserRepository;
+    use crate::api::users::adapters::user_event_stream::UserEventStream;
+    use crate::shared::config::settings::Profile;
+    use crate::shared::security::secret::Secret;
+    use crate::shared::storage::blob_storage::InMemoryBlobStorage;
+    use crate::shared::tenancy::tenant::TenantRegistry;
+    use crate::shared::time::clock::SystemClock;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "secret";
+
+    fn settings() -> Settings {
+        let mut settings = Settings::defaults(Profile::Testing);
+        settings.auth.jwt_secret = Secret::new(SECRET.to_string());
+        settings.auth.token_cache_capacity = 0;
+        settings
+    }
+
+    fn router() -> Router {
+        let settings = settings();
+        build_router(state(&settings), &settings)
+    }
+
+    fn state(settings: &Settings) -> AppState {
+        AppState::new(
+            Arc::new(InMemoryUserRepository::new()),
+            Arc::new(InMemoryBlobStorage::new("http://localhost:3000/uploads")),
+            Arc::new(UserEventStream::default()),
+            settings,
+            Arc::new(TenantRegistry::default()),
+            Arc::new(HealthChecker::new()),
+            SystemClock::shared(),
+        )
+    }
+
+    async fn get_status(uri: &str) -> StatusCode {
+        router()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_routes_are_nested_under_api_prefix() {
+        assert_eq!(get_status("/api/v1/health/live").await, StatusCode::OK);
+        assert_eq!(get_status("/health/live").await, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_error_messages_follow_accept_language() {
+        let response = router()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/users")
+                    .header("accept-language", "es-ES,es;q=0.9,en;q=0.8")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()["content-language"], "es");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body["error"]["message"],
+            "Se requiere la cabecera Authorization con un token Bearer"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_responses_carry_security_headers() {
+        let response = router()
+            .oneshot(Request::builder().uri("/api/v1/health/live").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()["x-content-type-options"], "nosniff");
+        assert_eq!(response.headers()["x-frame-options"], "DENY");
+    }
+
+    #[tokio::test]
+    async fn test_metrics_are_mounted_once_a_recorder_is_installed() {
+        assert_eq!(get_status("/metrics").await, StatusCode::NOT_FOUND);
+
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let settings = settings();
+        let router = build_router(state(&settings).with_metrics(recorder.handle()), &settings);
+        let response = router
+            .oneshot(Request::builder().uri("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_stored_blobs_are_served_at_the_public_base_url() {
+        let root = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(root.path().join("avatars")).unwrap();
+        std::fs::write(root.path().join("avatars/a.png"), b"png").unwrap();
+        let mut settings = settings();
+        settings.storage.local_root = root.path().to_string_lossy().into_owned();
+        settings.storage.public_base_url = "http://localhost:3000/uploads".to_string();
+
+        let response = build_router(state(&settings), &settings)
+            .oneshot(Request::builder().uri("/uploads/avatars/a.png").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-content-type-options"], "nosniff");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"png");
+    }
+
+    #[tokio::test]
+    async fn test_created_users_get_ids_of_the_configured_version() {
+        let mut settings = settings();
+        settings.ids.version = "v4".to_string();
+        let claims = serde_json::json!({
+            "sub": "user-123",
+            "tenant_id": "default",
+            "scopes": ["users:create"],
+            "exp": chrono::Utc::now().timestamp() + 3600,
+        });
+        let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap();
+        let body = serde_json::json!({ "email": "john.doe@example.com", "name": "John Doe", "age": 30 });
+
+        let response = build_router(state(&settings), &settings)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/users")
+                    .header("authorization", format!("Bearer {}", token))
+                    .header("idempotency-key", "user-creation-abc123")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let id: uuid::Uuid = body["user"]["id"].as_str().unwrap().parse().unwrap();
+        assert_eq!(id.get_version_num(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_spec_is_served() {
+        assert_eq!(get_status("/api-docs/openapi.json").await, StatusCode::OK);
+    }
+}
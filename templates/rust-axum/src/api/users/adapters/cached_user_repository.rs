@@ -0,0 +1,377 @@
+//! BRIK v5 Cached User Repository - Read-through cache decorator for the user port
+//!
+//! `get_by_id` is served from the cache store when possible; misses are
+//! coalesced per user with single-flight and written back with a TTL. Updates
+//! and deletes invalidate the entry once the inner repository succeeded.
+//! Cache failures and timeouts never fail a request: they are counted, logged
+//! and the inner repository answers instead. A read racing an update can still
+//! write back the old version, so the TTL bounds how stale an entry may get.
+
+use crate::api::users::domain::entities::user::{User, UserProfile};
+use crate::api::users::domain::ports::user_repository::UserRepository;
+use crate::api::users::domain::queries::list_users_query::ListUsersQuery;
+use crate::shared::cache::cache_store::CacheStore;
+use crate::shared::cache::single_flight::SingleFlight;
+use crate::shared::config::settings::RedisSettings;
+use crate::shared::errors::port_error::PortError;
+use crate::brik_log;
+use crate::shared::observability::metrics::{CACHE_ERRORS_TOTAL, CACHE_HITS_TOTAL, CACHE_MISSES_TOTAL};
+use crate::shared::tenancy::tenant::TenantId;
+use crate::shared::types::result::BrikResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+const CACHE_NAME: &str = "users";
+const KEY_PREFIX: &str = "user:v2:";
+
+pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+pub const DEFAULT_CACHE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Cache encoding of a user, versioned through `KEY_PREFIX`
+#[derive(Serialize, Deserialize)]
+struct CachedUser {
+    id: Uuid,
+    tenant_id: TenantId,
+    email: String,
+    name: String,
+    age: i32,
+    profile: UserProfile,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i64,
+}
+
+impl From<&User> for CachedUser {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id,
+            tenant_id: user.tenant_id.clone(),
+            email: user.email.clone(),
+            name: user.name.clone(),
+            age: user.age,
+            profile: user.profile.clone(),
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            version: user.version,
+        }
+    }
+}
+
+impl From<CachedUser> for User {
+    fn from(cached: CachedUser) -> Self {
+        User::from_persistence(
+            cached.id,
+            cached.tenant_id,
+            cached.email,
+            cached.name,
+            cached.age,
+            cached.profile,
+            cached.created_at,
+            cached.updated_at,
+            cached.version,
+        )
+    }
+}
+
+/// The tenant is part of the key, so an id never resolves across tenants
+fn cache_key(tenant: &TenantId, id: Uuid) -> String {
+    format!("{}{}:{}", KEY_PREFIX, tenant, id)
+}
+
+/// Cache access with a deadline; failures are recorded and reported as `None`
+#[derive(Clone)]
+struct Cache {
+    store: Arc<dyn CacheStore>,
+    ttl: Duration,
+    timeout: Duration,
+}
+
+impl Cache {
+    async fn guarded<T, F>(&self, operation: &'static str, call: F) -> Option<T>
+    where
+        F: Future<Output = BrikResult<T, PortError>>,
+    {
+        let error = match tokio::time::timeout(self.timeout, call).await {
+            Ok(Ok(value)) => return Some(value),
+            Ok(Err(error)) => error.to_string(),
+            Err(_) => format!("cache {} timed out after {:?}", operation, self.timeout),
+        };
+
+        metrics::counter!(CACHE_ERRORS_TOTAL, "cache" => CACHE_NAME, "operation" => operation).increment(1);
+        brik_log!(
+            WARN,
+            "User cache unavailable, falling back to the repository",
+            port = "CacheStore",
+            operation = operation,
+            error = error.as_str()
+        );
+        None
+    }
+
+    async fn get(&self, tenant: &TenantId, id: Uuid) -> Option<User> {
+        let raw = self.guarded("get", self.store.get(&cache_key(tenant, id))).await.flatten()?;
+        // Undecodable entries (e.g. written by an older release) count as misses
+        serde_json::from_str::<CachedUser>(&raw).ok().map(User::from)
+    }
+
+    async fn put(&self, user: &User) {
+        if let Ok(raw) = serde_json::to_string(&CachedUser::from(user)) {
+            self.guarded("set", self.store.set(&cache_key(&user.tenant_id, user.id), &raw, self.ttl)).await;
+        }
+    }
+
+    async fn invalidate(&self, tenant: &TenantId, id: Uuid) {
+        self.guarded("delete", self.store.delete(&cache_key(tenant, id))).await;
+    }
+}
+
+pub struct CachedUserRepository {
+    inner: Arc<dyn UserRepository>,
+    cache: Cache,
+    loads: SingleFlight<BrikResult<Option<User>, PortError>>,
+}
+
+impl CachedUserRepository {
+    pub fn new(inner: Arc<dyn UserRepository>, store: Arc<dyn CacheStore>) -> Self {
+        Self {
+            inner,
+            cache: Cache {
+                store,
+                ttl: DEFAULT_TTL,
+                timeout: DEFAULT_CACHE_TIMEOUT,
+            },
+            loads: SingleFlight::new(),
+        }
+    }
+
+    /// TTL from `redis.user_cache_ttl_secs`
+    pub fn from_settings(inner: Arc<dyn UserRepository>, store: Arc<dyn CacheStore>, settings: &RedisSettings) -> Self {
+        Self::new(inner, store).with_ttl(Duration::from_secs(settings.user_cache_ttl_secs))
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.cache.ttl = ttl;
+        self
+    }
+
+    /// Upper bound for a single cache call before it is treated as a failure
+    pub fn with_cache_timeout(mut self, timeout: Duration) -> Self {
+        self.cache.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for CachedUserRepository {
+    async fn create(&self, user: &User) -> BrikResult<(), PortError> {
+        self.inner.create(user).await
+    }
+
+    async fn get_by_id(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<User>, PortError> {
+        if let Some(user) = self.cache.get(tenant, id).await {
+            metrics::counter!(CACHE_HITS_TOTAL, "cache" => CACHE_NAME).increment(1);
+            return Ok(Some(user));
+        }
+        metrics::counter!(CACHE_MISSES_TOTAL, "cache" => CACHE_NAME).increment(1);
+
+        let inner = self.inner.clone();
+        let cache = self.cache.clone();
+        let tenant = tenant.clone();
+        self.loads
+            .run(&cache_key(&tenant, id), async move {
+                let user = inner.get_by_id(&tenant, id).await?;
+                if let Some(user) = &user {
+                    cache.put(user).await;
+                }
+                Ok(user)
+            })
+            .await
+    }
+
+    async fn get_by_email(&self, tenant: &TenantId, email: &str) -> BrikResult<Option<User>, PortError> {
+        self.inner.get_by_email(tenant, email).await
+    }
+
+    async fn list(&self, tenant: &TenantId, query: &ListUsersQuery) -> BrikResult<Vec<User>, PortError> {
+        self.inner.list(tenant, query).await
+    }
+
+    async fn update(&self, user: &User) -> BrikResult<(), PortError> {
+        self.inner.update(user).await?;
+        self.cache.invalidate(&user.tenant_id, user.id).await;
+        Ok(())
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: Uuid) -> BrikResult<bool, PortError> {
+        let deleted = self.inner.delete(tenant, id).await?;
+        self.cache.invalidate(tenant, id).await;
+        Ok(deleted)
+    }
+
+    async fn get_password_hash(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<String>, PortError> {
+        self.inner.get_password_hash(tenant, id).await
+    }
+
+    async fn set_password_hash(&self, tenant: &TenantId, id: Uuid, hash: &str) -> BrikResult<bool, PortError> {
+        self.inner.set_password_hash(tenant, id, hash).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::ids::id_generator::UuidV7Generator;
+    use crate::shared::time::clock::SystemClock;
+    use crate::api::users::adapters::in_memory_user_repository::InMemoryUserRepository;
+    use crate::api::users::domain::entities::user::{UserCreationData, UserUpdateData};
+    use crate::shared::cache::cache_store::InMemoryCacheStore;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts reads reaching the wrapped repository
+    struct CountingRepository {
+        inner: InMemoryUserRepository,
+        reads: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl UserRepository for CountingRepository {
+        async fn create(&self, user: &User) -> BrikResult<(), PortError> {
+            self.inner.create(user).await
+        }
+
+        async fn get_by_id(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<User>, PortError> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.inner.get_by_id(tenant, id).await
+        }
+
+        async fn get_by_email(&self, tenant: &TenantId, email: &str) -> BrikResult<Option<User>, PortError> {
+            self.inner.get_by_email(tenant, email).await
+        }
+
+        async fn list(&self, tenant: &TenantId, query: &ListUsersQuery) -> BrikResult<Vec<User>, PortError> {
+            self.inner.list(tenant, query).await
+        }
+
+        async fn update(&self, user: &User) -> BrikResult<(), PortError> {
+            self.inner.update(user).await
+        }
+
+        async fn delete(&self, tenant: &TenantId, id: Uuid) -> BrikResult<bool, PortError> {
+            self.inner.delete(tenant, id).await
+        }
+
+        async fn get_password_hash(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<String>, PortError> {
+            self.inner.get_password_hash(tenant, id).await
+        }
+
+        async fn set_password_hash(&self, tenant: &TenantId, id: Uuid, hash: &str) -> BrikResult<bool, PortError> {
+            self.inner.set_password_hash(tenant, id, hash).await
+        }
+    }
+
+    /// Cache store that behaves like Redis being down
+    struct DownCacheStore;
+
+    #[async_trait::async_trait]
+    impl CacheStore for DownCacheStore {
+        async fn get(&self, _: &str) -> BrikResult<Option<String>, PortError> {
+            Err(PortError::new("CacheStore", "CACHE_UNAVAILABLE", "Connection refused"))
+        }
+
+        async fn set(&self, _: &str, _: &str, _: Duration) -> BrikResult<(), PortError> {
+            Err(PortError::new("CacheStore", "CACHE_UNAVAILABLE", "Connection refused"))
+        }
+
+        async fn delete(&self, _: &str) -> BrikResult<(), PortError> {
+            Err(PortError::new("CacheStore", "CACHE_UNAVAILABLE", "Connection refused"))
+        }
+    }
+
+    async fn setup(store: Arc<dyn CacheStore>) -> (Arc<CountingRepository>, CachedUserRepository, User) {
+        let inner = Arc::new(CountingRepository {
+            inner: InMemoryUserRepository::new(),
+            reads: AtomicUsize::new(0),
+        });
+        let user = User::create(TenantId::parse("acme").unwrap(), UserCreationData {
+            email: "john@example.com".to_string(),
+            name: "John Doe".to_string(),
+            age: 30,
+            profile: None,
+        }, &SystemClock, &UuidV7Generator::default())
+        .unwrap();
+        inner.create(&user).await.unwrap();
+
+        (inner.clone(), CachedUserRepository::new(inner, store), user)
+    }
+
+    #[tokio::test]
+    async fn test_second_read_is_served_from_cache() {
+        let (inner, repository, user) = setup(Arc::new(InMemoryCacheStore::new())).await;
+
+        assert_eq!(repository.get_by_id(&user.tenant_id, user.id).await.unwrap(), Some(user.clone()));
+        assert_eq!(repository.get_by_id(&user.tenant_id, user.id).await.unwrap(), Some(user));
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_ttl_comes_from_settings() {
+        let settings = RedisSettings {
+            url: "redis://localhost:6379".to_string(),
+            user_cache_ttl_secs: 42,
+        };
+        let repository = CachedUserRepository::from_settings(
+            Arc::new(InMemoryUserRepository::new()),
+            Arc::new(InMemoryCacheStore::new()),
+            &settings,
+        );
+
+        assert_eq!(repository.cache.ttl, Duration::from_secs(42));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_misses_load_once() {
+        let (inner, repository, user) = setup(Arc::new(InMemoryCacheStore::new())).await;
+
+        let reads = futures::future::join_all((0..8).map(|_| repository.get_by_id(&user.tenant_id, user.id))).await;
+
+        assert!(reads.into_iter().all(|read| read.unwrap().is_some()));
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cached_user_is_not_served_to_another_tenant() {
+        let (_, repository, user) = setup(Arc::new(InMemoryCacheStore::new())).await;
+        repository.get_by_id(&user.tenant_id, user.id).await.unwrap();
+
+        let other = TenantId::parse("globex").unwrap();
+        assert_eq!(repository.get_by_id(&other, user.id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_invalidate() {
+        let (inner, repository, user) = setup(Arc::new(InMemoryCacheStore::new())).await;
+        repository.get_by_id(&user.tenant_id, user.id).await.unwrap();
+
+        let updated = user.update(UserUpdateData { age: Some(31), ..UserUpdateData::default() }, &SystemClock).unwrap();
+        repository.update(&updated).await.unwrap();
+        assert_eq!(repository.get_by_id(&user.tenant_id, user.id).await.unwrap().unwrap().age, 31);
+
+        repository.delete(&user.tenant_id, user.id).await.unwrap();
+        assert_eq!(repository.get_by_id(&user.tenant_id, user.id).await.unwrap(), None);
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cache_outage_falls_back_to_repository() {
+        let (inner, repository, user) = setup(Arc::new(DownCacheStore)).await;
+
+        assert_eq!(repository.get_by_id(&user.tenant_id, user.id).await.unwrap(), Some(user.clone()));
+        assert!(repository.delete(&user.tenant_id, user.id).await.unwrap());
+        assert_eq!(inner.reads.load(Ordering::SeqCst), 1);
+    }
+}
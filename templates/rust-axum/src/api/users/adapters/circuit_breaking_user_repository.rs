@@ -0,0 +1,174 @@
+//! BRIK v5 Circuit Breaking User Repository - Fails fast while the store is down
+//!
+//! Only infrastructure errors count against the circuit: a unique violation
+//! or a version conflict is a normal answer from a healthy database.
+
+use crate::api::users::domain::entities::user::User;
+use crate::api::users::domain::ports::user_repository::UserRepository;
+use crate::api::users::domain::queries::list_users_query::ListUsersQuery;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::resilience::circuit_breaker::CircuitBreaker;
+use crate::shared::tenancy::tenant::TenantId;
+use crate::shared::types::result::BrikResult;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Answers about the data rather than about the store's health
+const BUSINESS_CODES: [ErrorCode; 3] = [ErrorCode::UniqueViolation, ErrorCode::VersionConflict, ErrorCode::NotFound];
+
+fn is_failure(error: &PortError) -> bool {
+    !BUSINESS_CODES.iter().any(|code| error.has_code(*code))
+}
+
+pub struct CircuitBreakingUserRepository {
+    inner: Arc<dyn UserRepository>,
+    breaker: CircuitBreaker,
+}
+
+impl CircuitBreakingUserRepository {
+    pub fn new(inner: Arc<dyn UserRepository>, breaker: CircuitBreaker) -> Self {
+        Self { inner, breaker }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for CircuitBreakingUserRepository {
+    async fn create(&self, user: &User) -> BrikResult<(), PortError> {
+        self.breaker.call_classified(self.inner.create(user), is_failure).await
+    }
+
+    async fn get_by_id(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<User>, PortError> {
+        self.breaker.call_classified(self.inner.get_by_id(tenant, id), is_failure).await
+    }
+
+    async fn get_by_email(&self, tenant: &TenantId, email: &str) -> BrikResult<Option<User>, PortError> {
+        self.breaker.call_classified(self.inner.get_by_email(tenant, email), is_failure).await
+    }
+
+    async fn list(&self, tenant: &TenantId, query: &ListUsersQuery) -> BrikResult<Vec<User>, PortError> {
+        self.breaker.call_classified(self.inner.list(tenant, query), is_failure).await
+    }
+
+    async fn update(&self, user: &User) -> BrikResult<(), PortError> {
+        self.breaker.call_classified(self.inner.update(user), is_failure).await
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: Uuid) -> BrikResult<bool, PortError> {
+        self.breaker.call_classified(self.inner.delete(tenant, id), is_failure).await
+    }
+
+    async fn get_password_hash(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<String>, PortError> {
+        self.breaker.call_classified(self.inner.get_password_hash(tenant, id), is_failure).await
+    }
+
+    async fn set_password_hash(&self, tenant: &TenantId, id: Uuid, hash: &str) -> BrikResult<bool, PortError> {
+        self.breaker.call_classified(self.inner.set_password_hash(tenant, id, hash), is_failure).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::ids::id_generator::UuidV7Generator;
+    use crate::shared::time::clock::SystemClock;
+    use crate::api::users::adapters::in_memory_user_repository::InMemoryUserRepository;
+    use crate::api::users::domain::entities::user::UserCreationData;
+    use crate::shared::errors::api_error::ApiError;
+    use crate::shared::resilience::circuit_breaker::{CircuitBreakerConfig, CircuitState};
+    use axum::http::StatusCode;
+
+    /// Store that is down for every call
+    struct UnavailableRepository;
+
+    fn tenant() -> TenantId {
+        TenantId::parse("acme").unwrap()
+    }
+
+    fn unavailable() -> PortError {
+        PortError::new("UserRepository", "DATABASE_UNAVAILABLE", "connection refused")
+    }
+
+    #[async_trait::async_trait]
+    impl UserRepository for UnavailableRepository {
+        async fn create(&self, _: &User) -> BrikResult<(), PortError> {
+            Err(unavailable())
+        }
+
+        async fn get_by_id(&self, _: &TenantId, _: Uuid) -> BrikResult<Option<User>, PortError> {
+            Err(unavailable())
+        }
+
+        async fn get_by_email(&self, _: &TenantId, _: &str) -> BrikResult<Option<User>, PortError> {
+            Err(unavailable())
+        }
+
+        async fn list(&self, _: &TenantId, _: &ListUsersQuery) -> BrikResult<Vec<User>, PortError> {
+            Err(unavailable())
+        }
+
+        async fn update(&self, _: &User) -> BrikResult<(), PortError> {
+            Err(unavailable())
+        }
+
+        async fn delete(&self, _: &TenantId, _: Uuid) -> BrikResult<bool, PortError> {
+            Err(unavailable())
+        }
+
+        async fn get_password_hash(&self, _: &TenantId, _: Uuid) -> BrikResult<Option<String>, PortError> {
+            Err(unavailable())
+        }
+
+        async fn set_password_hash(&self, _: &TenantId, _: Uuid, _: &str) -> BrikResult<bool, PortError> {
+            Err(unavailable())
+        }
+    }
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(
+            "UserRepository",
+            CircuitBreakerConfig {
+                window_size: 2,
+                minimum_calls: 2,
+                ..CircuitBreakerConfig::default()
+            },
+            SystemClock::shared(),
+        )
+    }
+
+    fn user() -> User {
+        User::create(tenant(), UserCreationData {
+            email: "john@example.com".to_string(),
+            name: "John Doe".to_string(),
+            age: 30,
+            profile: None,
+        }, &SystemClock, &UuidV7Generator::default())
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_outage_fails_fast_with_port_error() {
+        let repository = CircuitBreakingUserRepository::new(Arc::new(UnavailableRepository), breaker());
+
+        repository.get_by_id(&tenant(), Uuid::new_v4()).await.unwrap_err();
+        repository.get_by_id(&tenant(), Uuid::new_v4()).await.unwrap_err();
+        let error = repository.get_by_id(&tenant(), Uuid::new_v4()).await.unwrap_err();
+
+        assert_eq!(error.port, "UserRepository");
+        assert_eq!(error.code, "CIRCUIT_OPEN");
+        assert_eq!(ApiError::from(error).status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_unique_violations_keep_the_circuit_closed() {
+        let breaker = breaker();
+        let repository = CircuitBreakingUserRepository::new(Arc::new(InMemoryUserRepository::new()), breaker.clone());
+        let user = user();
+
+        repository.create(&user).await.unwrap();
+        repository.create(&user).await.unwrap_err();
+        repository.create(&user).await.unwrap_err();
+
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
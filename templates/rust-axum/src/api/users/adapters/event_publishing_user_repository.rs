@@ -0,0 +1,162 @@
+//! BRIK v5 Event Publishing User Repository - Emits user events after writes
+//!
+//! Events are published once the inner repository committed the change. A
+//! failed publish is logged rather than failing the request, since the write
+//! already happened.
+//!
+//! This feeds in-process subscribers only and is best effort. Durable,
+//! cross-service delivery goes through the outbox written by
+//! `PostgresUserRepository`.
+
+use crate::api::users::domain::entities::user::User;
+use crate::api::users::domain::events::user_event::UserEvent;
+use crate::api::users::domain::ports::user_event_publisher::UserEventPublisher;
+use crate::api::users::domain::ports::user_repository::UserRepository;
+use crate::api::users::domain::queries::list_users_query::ListUsersQuery;
+use crate::shared::errors::port_error::PortError;
+use crate::brik_log;
+use crate::shared::tenancy::tenant::TenantId;
+use crate::shared::time::clock::{SharedClock, SystemClock};
+use crate::shared::types::result::BrikResult;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct EventPublishingUserRepository {
+    inner: Arc<dyn UserRepository>,
+    publisher: Arc<dyn UserEventPublisher>,
+    /// When deletions happened; other events carry the user's timestamps
+    clock: SharedClock,
+}
+
+impl EventPublishingUserRepository {
+    pub fn new(inner: Arc<dyn UserRepository>, publisher: Arc<dyn UserEventPublisher>) -> Self {
+        Self {
+            inner,
+            publisher,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    async fn publish(&self, event: UserEvent) {
+        let kind = event.kind;
+        let user_id = event.user_id;
+
+        if let Err(error) = self.publisher.publish(event).await {
+            brik_log!(
+                WARN,
+                "Failed to publish user event",
+                port = error.port.as_str(),
+                event = kind.as_str(),
+                subject_user_id = %user_id,
+                error = error.message.as_str()
+            );
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for EventPublishingUserRepository {
+    async fn create(&self, user: &User) -> BrikResult<(), PortError> {
+        self.inner.create(user).await?;
+        self.publish(UserEvent::created(user)).await;
+        Ok(())
+    }
+
+    async fn get_by_id(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<User>, PortError> {
+        self.inner.get_by_id(tenant, id).await
+    }
+
+    async fn get_by_email(&self, tenant: &TenantId, email: &str) -> BrikResult<Option<User>, PortError> {
+        self.inner.get_by_email(tenant, email).await
+    }
+
+    async fn list(&self, tenant: &TenantId, query: &ListUsersQuery) -> BrikResult<Vec<User>, PortError> {
+        self.inner.list(tenant, query).await
+    }
+
+    async fn update(&self, user: &User) -> BrikResult<(), PortError> {
+        self.inner.update(user).await?;
+        self.publish(UserEvent::updated(user)).await;
+        Ok(())
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: Uuid) -> BrikResult<bool, PortError> {
+        let deleted = self.inner.delete(tenant, id).await?;
+        if deleted {
+            self.publish(UserEvent::deleted(tenant, id, self.clock.as_ref())).await;
+        }
+        Ok(deleted)
+    }
+
+    async fn get_password_hash(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<String>, PortError> {
+        self.inner.get_password_hash(tenant, id).await
+    }
+
+    async fn set_password_hash(&self, tenant: &TenantId, id: Uuid, hash: &str) -> BrikResult<bool, PortError> {
+        self.inner.set_password_hash(tenant, id, hash).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::ids::id_generator::UuidV7Generator;
+    use crate::shared::time::clock::{Clock, ManualClock, SystemClock};
+    use crate::api::users::adapters::in_memory_user_repository::InMemoryUserRepository;
+    use crate::api::users::adapters::user_event_stream::UserEventStream;
+    use crate::api::users::domain::entities::user::{UserCreationData, UserUpdateData};
+    use crate::api::users::domain::events::user_event::UserEventKind;
+
+    #[tokio::test]
+    async fn test_writes_publish_events() {
+        let events = Arc::new(UserEventStream::default());
+        let repository = EventPublishingUserRepository::new(Arc::new(InMemoryUserRepository::new()), events.clone());
+        let user = User::create(TenantId::parse("acme").unwrap(), UserCreationData {
+            email: "john@example.com".to_string(),
+            name: "John Doe".to_string(),
+            age: 30,
+            profile: None,
+        }, &SystemClock, &UuidV7Generator::default())
+        .unwrap();
+
+        repository.create(&user).await.unwrap();
+        repository.create(&user).await.unwrap_err();
+        repository
+            .update(&user.update(UserUpdateData { age: Some(31), ..UserUpdateData::default() }, &SystemClock).unwrap())
+            .await
+            .unwrap();
+        repository.delete(&user.tenant_id, user.id).await.unwrap();
+        repository.delete(&user.tenant_id, user.id).await.unwrap();
+
+        let kinds: Vec<_> = events.subscribe(Some(0)).replay.iter().map(|event| event.event.kind).collect();
+        assert_eq!(kinds, vec![UserEventKind::Created, UserEventKind::Updated, UserEventKind::Deleted]);
+    }
+
+    #[tokio::test]
+    async fn test_deletions_are_stamped_by_the_clock() {
+        let clock = ManualClock::starting_now();
+        let events = Arc::new(UserEventStream::default());
+        let repository = EventPublishingUserRepository::new(Arc::new(InMemoryUserRepository::new()), events.clone())
+            .with_clock(clock.clone());
+        let user = User::create(TenantId::parse("acme").unwrap(), UserCreationData {
+            email: "john@example.com".to_string(),
+            name: "John Doe".to_string(),
+            age: 30,
+            profile: None,
+        }, clock.as_ref(), &UuidV7Generator::default())
+        .unwrap();
+        repository.create(&user).await.unwrap();
+
+        clock.advance(std::time::Duration::from_secs(7200));
+        repository.delete(&user.tenant_id, user.id).await.unwrap();
+
+        let replay = events.subscribe(Some(0)).replay;
+        assert_eq!(replay[1].event.kind, UserEventKind::Deleted);
+        assert_eq!(replay[1].event.occurred_at, clock.now());
+    }
+}
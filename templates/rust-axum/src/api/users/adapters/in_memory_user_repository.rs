@@ -0,0 +1,260 @@
+//! BRIK v5 In-Memory User Repository - Adapter for tests and local runs
+
+use crate::api::users::domain::entities::user::User;
+use crate::api::users::domain::ports::user_repository::UserRepository;
+use crate::api::users::domain::queries::list_users_query::ListUsersQuery;
+use crate::shared::pagination::list_query::SortDirection;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::tenancy::tenant::TenantId;
+use crate::shared::types::result::BrikResult;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+const PORT_NAME: &str = "UserRepository";
+
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    users: RwLock<HashMap<Uuid, User>>,
+    password_hashes: RwLock<HashMap<Uuid, String>>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn create(&self, user: &User) -> BrikResult<(), PortError> {
+        let mut users = self.users.write().await;
+
+        if users
+            .values()
+            .any(|existing| existing.tenant_id == user.tenant_id && existing.email == user.email)
+        {
+            return Err(ErrorCode::UniqueViolation.port_error(PORT_NAME, "Email already registered"));
+        }
+
+        users.insert(user.id, user.clone());
+        Ok(())
+    }
+
+    async fn get_by_id(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<User>, PortError> {
+        Ok(self.users.read().await.get(&id).filter(|user| &user.tenant_id == tenant).cloned())
+    }
+
+    async fn get_by_email(&self, tenant: &TenantId, email: &str) -> BrikResult<Option<User>, PortError> {
+        Ok(self
+            .users
+            .read()
+            .await
+            .values()
+            .find(|user| &user.tenant_id == tenant && user.email == email)
+            .cloned())
+    }
+
+    async fn list(&self, tenant: &TenantId, query: &ListUsersQuery) -> BrikResult<Vec<User>, PortError> {
+        let sort = query.page.sort;
+        let position = |user: &User| (sort.field.value_of(user), user.id);
+        let in_order = |ordering: Ordering| match sort.direction {
+            SortDirection::Asc => ordering,
+            SortDirection::Desc => ordering.reverse(),
+        };
+
+        let mut users: Vec<User> = self
+            .users
+            .read()
+            .await
+            .values()
+            .filter(|user| &user.tenant_id == tenant)
+            .filter(|user| query.filter.matches(user))
+            .filter(|user| {
+                query.after.as_ref().is_none_or(|after| {
+                    position(user).partial_cmp(after).map(in_order) == Some(Ordering::Greater)
+                })
+            })
+            .cloned()
+            .collect();
+
+        users.sort_by(|a, b| in_order(position(a).partial_cmp(&position(b)).unwrap_or(Ordering::Equal)));
+        users.truncate(query.page.limit as usize + 1);
+        Ok(users)
+    }
+
+    async fn update(&self, user: &User) -> BrikResult<(), PortError> {
+        let mut users = self.users.write().await;
+
+        match users.get(&user.id).filter(|stored| stored.tenant_id == user.tenant_id) {
+            Some(stored) if stored.version + 1 == user.version => {
+                users.insert(user.id, user.clone());
+                Ok(())
+            }
+            Some(_) => Err(ErrorCode::VersionConflict.port_error(PORT_NAME, "User was modified by another request")),
+            None => Err(ErrorCode::NotFound.port_error(PORT_NAME, "User does not exist")),
+        }
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: Uuid) -> BrikResult<bool, PortError> {
+        let mut users = self.users.write().await;
+
+        match users.get(&id) {
+            Some(user) if &user.tenant_id == tenant => {
+                self.password_hashes.write().await.remove(&id);
+                Ok(users.remove(&id).is_some())
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn get_password_hash(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<String>, PortError> {
+        let users = self.users.read().await;
+
+        match users.get(&id) {
+            Some(user) if &user.tenant_id == tenant => Ok(self.password_hashes.read().await.get(&id).cloned()),
+            _ => Ok(None),
+        }
+    }
+
+    async fn set_password_hash(&self, tenant: &TenantId, id: Uuid, hash: &str) -> BrikResult<bool, PortError> {
+        let users = self.users.read().await;
+
+        match users.get(&id) {
+            Some(user) if &user.tenant_id == tenant => {
+                self.password_hashes.write().await.insert(id, hash.to_string());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::ids::id_generator::UuidV7Generator;
+    use crate::shared::time::clock::SystemClock;
+    use crate::api::users::domain::entities::user::{UserCreationData, UserUpdateData};
+
+    fn tenant(id: &str) -> TenantId {
+        TenantId::parse(id).unwrap()
+    }
+
+    fn new_user(email: &str) -> User {
+        user_in("acme", email)
+    }
+
+    fn user_in(tenant_id: &str, email: &str) -> User {
+        User::create(tenant(tenant_id), UserCreationData {
+            email: email.to_string(),
+            name: "John Doe".to_string(),
+            age: 30,
+            profile: None,
+        }, &SystemClock, &UuidV7Generator::default())
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_and_get() {
+        let repository = InMemoryUserRepository::new();
+        let user = new_user("john@example.com");
+
+        repository.create(&user).await.unwrap();
+
+        assert_eq!(repository.get_by_id(&user.tenant_id, user.id).await.unwrap(), Some(user.clone()));
+        assert_eq!(repository.get_by_email(&user.tenant_id, "john@example.com").await.unwrap(), Some(user));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_email_is_rejected() {
+        let repository = InMemoryUserRepository::new();
+        repository.create(&new_user("john@example.com")).await.unwrap();
+
+        let error = repository.create(&new_user("john@example.com")).await.unwrap_err();
+        assert_eq!(error.code, "UNIQUE_VIOLATION");
+    }
+
+    #[tokio::test]
+    async fn test_list_pages_through_filtered_users() {
+        let repository = InMemoryUserRepository::new();
+        for email in ["a@example.com", "b@example.com", "c@example.com", "d@example.org"] {
+            repository.create(&new_user(email)).await.unwrap();
+        }
+        let params: HashMap<String, String> = [("sort", "email"), ("limit", "2"), ("email_domain", "example.com")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let query = ListUsersQuery::from_params(&params).unwrap();
+
+        let first = query.into_page(repository.list(&tenant("acme"), &query).await.unwrap());
+        assert_eq!(first.data.iter().map(|u| u.email.as_str()).collect::<Vec<_>>(), ["a@example.com", "b@example.com"]);
+        assert!(first.page.has_more);
+
+        let mut params = params;
+        params.insert("cursor".to_string(), first.page.next_cursor.unwrap());
+        let query = ListUsersQuery::from_params(&params).unwrap();
+
+        let second = query.into_page(repository.list(&tenant("acme"), &query).await.unwrap());
+        assert_eq!(second.data.iter().map(|u| u.email.as_str()).collect::<Vec<_>>(), ["c@example.com"]);
+        assert!(!second.page.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_tenants_do_not_see_each_other() {
+        let repository = InMemoryUserRepository::new();
+        let acme = user_in("acme", "john@example.com");
+        let globex = tenant("globex");
+        repository.create(&acme).await.unwrap();
+        // Emails are only unique within a tenant
+        repository.create(&user_in("globex", "john@example.com")).await.unwrap();
+
+        assert_eq!(repository.get_by_id(&globex, acme.id).await.unwrap(), None);
+        assert_ne!(repository.get_by_email(&globex, "john@example.com").await.unwrap().unwrap().id, acme.id);
+        let query = ListUsersQuery::from_params(&HashMap::new()).unwrap();
+        assert_eq!(repository.list(&globex, &query).await.unwrap().len(), 1);
+
+        let hijacked = User { tenant_id: globex.clone(), ..acme.update(UserUpdateData::default(), &SystemClock).unwrap() };
+        assert_eq!(repository.update(&hijacked).await.unwrap_err().code, "NOT_FOUND");
+        assert!(!repository.delete(&globex, acme.id).await.unwrap());
+        assert!(repository.get_by_id(&acme.tenant_id, acme.id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_stale_update_is_rejected() {
+        let repository = InMemoryUserRepository::new();
+        let user = new_user("john@example.com");
+        repository.create(&user).await.unwrap();
+
+        let first = user.update(UserUpdateData { age: Some(31), ..UserUpdateData::default() }, &SystemClock).unwrap();
+        let stale = user.update(UserUpdateData { age: Some(32), ..UserUpdateData::default() }, &SystemClock).unwrap();
+
+        repository.update(&first).await.unwrap();
+        let error = repository.update(&stale).await.unwrap_err();
+        assert_eq!(error.code, "VERSION_CONFLICT");
+    }
+
+    #[tokio::test]
+    async fn test_password_hashes_follow_the_user() {
+        let repository = InMemoryUserRepository::new();
+        let user = new_user("john@example.com");
+        repository.create(&user).await.unwrap();
+        assert_eq!(repository.get_password_hash(&user.tenant_id, user.id).await.unwrap(), None);
+
+        assert!(repository.set_password_hash(&user.tenant_id, user.id, "$2b$04$hash").await.unwrap());
+        assert!(!repository.set_password_hash(&tenant("globex"), user.id, "$2b$04$other").await.unwrap());
+
+        assert_eq!(repository.get_password_hash(&tenant("globex"), user.id).await.unwrap(), None);
+        assert_eq!(
+            repository.get_password_hash(&user.tenant_id, user.id).await.unwrap().as_deref(),
+            Some("$2b$04$hash")
+        );
+        // The user's version is untouched, so pending updates still apply
+        assert_eq!(repository.get_by_id(&user.tenant_id, user.id).await.unwrap(), Some(user.clone()));
+
+        repository.delete(&user.tenant_id, user.id).await.unwrap();
+        assert_eq!(repository.get_password_hash(&user.tenant_id, user.id).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,336 @@
+//! BRIK v5 PostgreSQL User Repository - sqlx adapter for the user port
+//!
+//! Every write appends its `UserEvent` to the outbox in the same transaction,
+//! so an event is recorded if and only if the change is committed.
+
+use crate::api::users::domain::entities::user::{User, UserProfile};
+use crate::api::users::domain::events::user_event::UserEvent;
+use crate::api::users::domain::ports::user_repository::UserRepository;
+use crate::api::users::domain::queries::list_users_query::{ListUsersQuery, SortValue};
+use crate::api::users::dto::user_dto;
+use crate::shared::pagination::list_query::{SortDirection, SortField};
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::outbox::outbox_store::{self, NewOutboxEvent};
+use crate::shared::tenancy::tenant::TenantId;
+use crate::shared::time::clock::{SharedClock, SystemClock};
+use crate::shared::types::result::BrikResult;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, types::Json, PgPool, Postgres, QueryBuilder, Row};
+use uuid::Uuid;
+
+const PORT_NAME: &str = "UserRepository";
+
+/// `aggregate_type` of user rows in the outbox
+pub const OUTBOX_AGGREGATE_TYPE: &str = "user";
+
+const SELECT_COLUMNS: &str =
+    "id, tenant_id, email, name, age, profile, created_at, updated_at, version";
+
+pub struct PostgresUserRepository {
+    pool: PgPool,
+    /// When deletions happened; other events carry the user's timestamps
+    clock: SharedClock,
+}
+
+impl PostgresUserRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Builds the filtered keyset query; the sort column comes from the
+    /// `UserSortField` whitelist, every value is bound
+    fn list_query<'a>(tenant: &'a TenantId, query: &'a ListUsersQuery) -> QueryBuilder<'a, Postgres> {
+        let filter = &query.filter;
+        let column = query.page.sort.field.as_str();
+        let mut builder = QueryBuilder::new(format!("SELECT {} FROM users WHERE tenant_id = ", SELECT_COLUMNS));
+        builder.push_bind(tenant.as_str());
+
+        if let Some(domain) = &filter.email_domain {
+            builder.push(" AND lower(split_part(email, '@', 2)) = ").push_bind(domain);
+        }
+        if let Some(min_age) = filter.min_age {
+            builder.push(" AND age >= ").push_bind(min_age);
+        }
+        if let Some(max_age) = filter.max_age {
+            builder.push(" AND age <= ").push_bind(max_age);
+        }
+        if let Some(created_after) = filter.created_after {
+            builder.push(" AND created_at >= ").push_bind(created_after);
+        }
+        if let Some(created_before) = filter.created_before {
+            builder.push(" AND created_at < ").push_bind(created_before);
+        }
+
+        let (comparison, direction) = match query.page.sort.direction {
+            SortDirection::Asc => (">", SortDirection::Asc.as_sql()),
+            SortDirection::Desc => ("<", SortDirection::Desc.as_sql()),
+        };
+
+        if let Some((value, id)) = &query.after {
+            builder.push(format!(" AND ({}, id) {} (", column, comparison));
+            match value {
+                SortValue::Text(text) => builder.push_bind(text),
+                SortValue::Integer(number) => builder.push_bind(*number),
+                SortValue::Timestamp(timestamp) => builder.push_bind(*timestamp),
+            };
+            builder.push(", ").push_bind(*id).push(")");
+        }
+
+        builder.push(format!(" ORDER BY {0} {1}, id {1} LIMIT ", column, direction));
+        builder.push_bind(i64::from(query.page.limit) + 1);
+        builder
+    }
+
+    fn map_row(row: PgRow) -> BrikResult<User, PortError> {
+        let read = |e: sqlx::Error| ErrorCode::RowMappingFailed.port_error(PORT_NAME, &e.to_string());
+
+        let tenant_id = row.try_get::<String, _>("tenant_id").map_err(read)?;
+        let tenant_id = TenantId::parse(&tenant_id).ok_or_else(|| {
+            ErrorCode::RowMappingFailed.port_error(PORT_NAME, &format!("invalid tenant_id '{}'", tenant_id))
+        })?;
+
+        Ok(User::from_persistence(
+            row.try_get::<Uuid, _>("id").map_err(read)?,
+            tenant_id,
+            row.try_get::<String, _>("email").map_err(read)?,
+            row.try_get::<String, _>("name").map_err(read)?,
+            row.try_get::<i32, _>("age").map_err(read)?,
+            row.try_get::<Json<UserProfile>, _>("profile").map_err(read)?.0,
+            row.try_get::<DateTime<Utc>, _>("created_at").map_err(read)?,
+            row.try_get::<DateTime<Utc>, _>("updated_at").map_err(read)?,
+            row.try_get::<i64, _>("version").map_err(read)?,
+        ))
+    }
+}
+
+/// Outbox row for a user event; the payload uses the public user DTO since
+/// it leaves the service
+pub(crate) fn outbox_event(event: &UserEvent) -> NewOutboxEvent {
+    NewOutboxEvent {
+        aggregate_type: OUTBOX_AGGREGATE_TYPE,
+        aggregate_id: event.user_id,
+        event_type: event.kind.as_str().to_string(),
+        payload: serde_json::json!({
+            "tenant_id": event.tenant_id,
+            "user_id": event.user_id,
+            "user": event.user.clone().map(user_dto::User::from),
+        }),
+        occurred_at: event.occurred_at,
+    }
+}
+
+/// Maps sqlx failures onto port error codes
+pub(crate) fn map_sqlx_error(error: sqlx::Error) -> PortError {
+    match &error {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            ErrorCode::UniqueViolation.port_error(PORT_NAME, "Email already registered")
+        }
+        sqlx::Error::PoolTimedOut | sqlx::Error::Io(_) => {
+            ErrorCode::DatabaseUnavailable.port_error(PORT_NAME, &error.to_string())
+        }
+        _ => ErrorCode::DatabaseError.port_error(PORT_NAME, &error.to_string()),
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for PostgresUserRepository {
+    async fn create(&self, user: &User) -> BrikResult<(), PortError> {
+        let mut transaction = self.pool.begin().await.map_err(map_sqlx_error)?;
+
+        sqlx::query(
+            "INSERT INTO users (id, tenant_id, email, name, age, profile, created_at, updated_at, version) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        )
+        .bind(user.id)
+        .bind(user.tenant_id.as_str())
+        .bind(&user.email)
+        .bind(&user.name)
+        .bind(user.age)
+        .bind(Json(&user.profile))
+        .bind(user.created_at)
+        .bind(user.updated_at)
+        .bind(user.version)
+        .execute(&mut *transaction)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        outbox_store::append(&mut transaction, &outbox_event(&UserEvent::created(user)))
+            .await
+            .map_err(map_sqlx_error)?;
+
+        transaction.commit().await.map_err(map_sqlx_error)
+    }
+
+    async fn get_by_id(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<User>, PortError> {
+        sqlx::query(&format!("SELECT {} FROM users WHERE tenant_id = $1 AND id = $2", SELECT_COLUMNS))
+            .bind(tenant.as_str())
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?
+            .map(Self::map_row)
+            .transpose()
+    }
+
+    async fn get_by_email(&self, tenant: &TenantId, email: &str) -> BrikResult<Option<User>, PortError> {
+        sqlx::query(&format!("SELECT {} FROM users WHERE tenant_id = $1 AND email = $2", SELECT_COLUMNS))
+            .bind(tenant.as_str())
+            .bind(email)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?
+            .map(Self::map_row)
+            .transpose()
+    }
+
+    async fn list(&self, tenant: &TenantId, query: &ListUsersQuery) -> BrikResult<Vec<User>, PortError> {
+        Self::list_query(tenant, query)
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?
+            .into_iter()
+            .map(Self::map_row)
+            .collect()
+    }
+
+    async fn update(&self, user: &User) -> BrikResult<(), PortError> {
+        let mut transaction = self.pool.begin().await.map_err(map_sqlx_error)?;
+
+        let result = sqlx::query(
+            "UPDATE users SET name = $2, age = $3, profile = $4, updated_at = $5, version = $6 \
+             WHERE id = $1 AND version = $6 - 1 AND tenant_id = $7",
+        )
+        .bind(user.id)
+        .bind(&user.name)
+        .bind(user.age)
+        .bind(Json(&user.profile))
+        .bind(user.updated_at)
+        .bind(user.version)
+        .bind(user.tenant_id.as_str())
+        .execute(&mut *transaction)
+        .await
+        .map_err(map_sqlx_error)?;
+
+        // Dropping the transaction rolls it back
+        if result.rows_affected() == 0 {
+            return Err(ErrorCode::VersionConflict.port_error(PORT_NAME, "User was modified by another request"));
+        }
+
+        outbox_store::append(&mut transaction, &outbox_event(&UserEvent::updated(user)))
+            .await
+            .map_err(map_sqlx_error)?;
+
+        transaction.commit().await.map_err(map_sqlx_error)
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: Uuid) -> BrikResult<bool, PortError> {
+        let mut transaction = self.pool.begin().await.map_err(map_sqlx_error)?;
+
+        let result = sqlx::query("DELETE FROM users WHERE tenant_id = $1 AND id = $2")
+            .bind(tenant.as_str())
+            .bind(id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        outbox_store::append(&mut transaction, &outbox_event(&UserEvent::deleted(tenant, id, self.clock.as_ref())))
+            .await
+            .map_err(map_sqlx_error)?;
+
+        transaction.commit().await.map_err(map_sqlx_error)?;
+        Ok(true)
+    }
+
+    async fn get_password_hash(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<String>, PortError> {
+        let hash: Option<Option<String>> =
+            sqlx::query_scalar("SELECT password_hash FROM users WHERE tenant_id = $1 AND id = $2")
+                .bind(tenant.as_str())
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(map_sqlx_error)?;
+
+        Ok(hash.flatten())
+    }
+
+    async fn set_password_hash(&self, tenant: &TenantId, id: Uuid, hash: &str) -> BrikResult<bool, PortError> {
+        // Not a change other services see, so no outbox event
+        let result = sqlx::query("UPDATE users SET password_hash = $3 WHERE tenant_id = $1 AND id = $2")
+            .bind(tenant.as_str())
+            .bind(id)
+            .bind(hash)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::time::clock::SystemClock;
+
+    #[test]
+    fn test_map_sqlx_error_pool_timeout() {
+        let error = map_sqlx_error(sqlx::Error::PoolTimedOut);
+
+        assert_eq!(error.port, "UserRepository");
+        assert_eq!(error.code, "DATABASE_UNAVAILABLE");
+    }
+
+    #[test]
+    fn test_list_query_uses_keyset_and_binds_values() {
+        let params = [("sort", "-age"), ("min_age", "18"), ("email_domain", "example.com")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let mut query = ListUsersQuery::from_params(&params).unwrap();
+        query.after = Some((SortValue::Integer(40), Uuid::new_v4()));
+
+        let tenant = TenantId::parse("acme").unwrap();
+
+        let sql = PostgresUserRepository::list_query(&tenant, &query).into_sql();
+
+        assert!(sql.contains("WHERE tenant_id = $1"));
+        assert!(sql.contains("lower(split_part(email, '@', 2)) = $2"));
+        assert!(sql.contains("age >= $3"));
+        assert!(sql.contains("(age, id) < ($4, $5)"));
+        assert!(sql.ends_with("ORDER BY age DESC, id DESC LIMIT $6"));
+    }
+
+    #[test]
+    fn test_outbox_event_for_user_event() {
+        let id = Uuid::new_v4();
+        let tenant = TenantId::parse("acme").unwrap();
+
+        let event = outbox_event(&UserEvent::deleted(&tenant, id, &SystemClock));
+
+        assert_eq!(event.aggregate_type, "user");
+        assert_eq!(event.aggregate_id, id);
+        assert_eq!(event.event_type, "user.deleted");
+        assert_eq!(event.payload, serde_json::json!({ "tenant_id": "acme", "user_id": id, "user": null }));
+    }
+
+    #[test]
+    fn test_map_sqlx_error_fallback() {
+        let error = map_sqlx_error(sqlx::Error::RowNotFound);
+
+        assert_eq!(error.code, "DATABASE_ERROR");
+    }
+}
@@ -0,0 +1,137 @@
+//! BRIK v5 Retrying User Repository - Retries reads that hit a transient failure
+//!
+//! Writes run once: a create or update whose reply was lost may have been
+//! committed, and repeating it would answer with a unique violation or a
+//! version conflict for a change that succeeded.
+
+use crate::api::users::domain::entities::user::User;
+use crate::api::users::domain::ports::user_repository::UserRepository;
+use crate::api::users::domain::queries::list_users_query::ListUsersQuery;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::resilience::retry::{Idempotency, RetryPolicy};
+use crate::shared::tenancy::tenant::TenantId;
+use crate::shared::types::result::BrikResult;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct RetryingUserRepository {
+    inner: Arc<dyn UserRepository>,
+    policy: RetryPolicy,
+}
+
+impl RetryingUserRepository {
+    pub fn new(inner: Arc<dyn UserRepository>, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for RetryingUserRepository {
+    async fn create(&self, user: &User) -> BrikResult<(), PortError> {
+        self.policy.run(Idempotency::NonIdempotent, || self.inner.create(user)).await
+    }
+
+    async fn get_by_id(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<User>, PortError> {
+        self.policy.run(Idempotency::Idempotent, || self.inner.get_by_id(tenant, id)).await
+    }
+
+    async fn get_by_email(&self, tenant: &TenantId, email: &str) -> BrikResult<Option<User>, PortError> {
+        self.policy.run(Idempotency::Idempotent, || self.inner.get_by_email(tenant, email)).await
+    }
+
+    async fn list(&self, tenant: &TenantId, query: &ListUsersQuery) -> BrikResult<Vec<User>, PortError> {
+        self.policy.run(Idempotency::Idempotent, || self.inner.list(tenant, query)).await
+    }
+
+    async fn update(&self, user: &User) -> BrikResult<(), PortError> {
+        self.policy.run(Idempotency::NonIdempotent, || self.inner.update(user)).await
+    }
+
+    async fn delete(&self, tenant: &TenantId, id: Uuid) -> BrikResult<bool, PortError> {
+        // A repeated delete would report `false` for a user it just removed
+        self.policy.run(Idempotency::NonIdempotent, || self.inner.delete(tenant, id)).await
+    }
+
+    async fn get_password_hash(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<String>, PortError> {
+        self.policy.run(Idempotency::Idempotent, || self.inner.get_password_hash(tenant, id)).await
+    }
+
+    async fn set_password_hash(&self, tenant: &TenantId, id: Uuid, hash: &str) -> BrikResult<bool, PortError> {
+        // Writing the same hash twice leaves the same row
+        self.policy.run(Idempotency::Idempotent, || self.inner.set_password_hash(tenant, id, hash)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::resilience::retry::RetryConfig;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn tenant() -> TenantId {
+        TenantId::parse("acme").unwrap()
+    }
+
+    /// Fails every call with a transient error, counting the calls
+    #[derive(Default)]
+    struct FlakyRepository {
+        calls: AtomicU32,
+    }
+
+    impl FlakyRepository {
+        fn fail<T>(&self) -> BrikResult<T, PortError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Err(PortError::new("UserRepository", "DATABASE_UNAVAILABLE", "connection reset"))
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl UserRepository for FlakyRepository {
+        async fn create(&self, _: &User) -> BrikResult<(), PortError> {
+            self.fail()
+        }
+
+        async fn get_by_id(&self, _: &TenantId, _: Uuid) -> BrikResult<Option<User>, PortError> {
+            self.fail()
+        }
+
+        async fn get_by_email(&self, _: &TenantId, _: &str) -> BrikResult<Option<User>, PortError> {
+            self.fail()
+        }
+
+        async fn list(&self, _: &TenantId, _: &ListUsersQuery) -> BrikResult<Vec<User>, PortError> {
+            self.fail()
+        }
+
+        async fn update(&self, _: &User) -> BrikResult<(), PortError> {
+            self.fail()
+        }
+
+        async fn delete(&self, _: &TenantId, _: Uuid) -> BrikResult<bool, PortError> {
+            self.fail()
+        }
+
+        async fn get_password_hash(&self, _: &TenantId, _: Uuid) -> BrikResult<Option<String>, PortError> {
+            self.fail()
+        }
+
+        async fn set_password_hash(&self, _: &TenantId, _: Uuid, _: &str) -> BrikResult<bool, PortError> {
+            self.fail()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reads_are_retried_writes_are_not() {
+        let inner = Arc::new(FlakyRepository::default());
+        let repository = RetryingUserRepository::new(
+            inner.clone(),
+            RetryPolicy::new("UserRepository", RetryConfig { max_attempts: 3, ..RetryConfig::default() }),
+        );
+
+        repository.get_by_id(&tenant(), Uuid::new_v4()).await.unwrap_err();
+        assert_eq!(inner.calls.swap(0, Ordering::SeqCst), 3);
+
+        repository.delete(&tenant(), Uuid::new_v4()).await.unwrap_err();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,17 @@
+//! BRIK v5 User Event Stream - In-process publisher feeding WebSocket subscribers
+
+use crate::api::users::domain::events::user_event::UserEvent;
+use crate::api::users::domain::ports::user_event_publisher::UserEventPublisher;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::events::event_stream::EventStream;
+use crate::shared::types::result::BrikResult;
+
+pub type UserEventStream = EventStream<UserEvent>;
+
+#[async_trait::async_trait]
+impl UserEventPublisher for UserEventStream {
+    async fn publish(&self, event: UserEvent) -> BrikResult<(), PortError> {
+        EventStream::publish(self, event);
+        Ok(())
+    }
+}
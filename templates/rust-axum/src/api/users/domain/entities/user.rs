@@ -0,0 +1,414 @@
+//! BRIK v5 User Domain Entity
+//! Pure domain logic with invariants
+
+use crate::api::users::domain::errors::domain_error::DomainError;
+use crate::validate_all;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::ids::id_generator::IdGenerator;
+use crate::shared::tenancy::tenant::TenantId;
+use crate::shared::time::clock::Clock;
+use crate::shared::types::result::BrikResult;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserProfile {
+    pub bio: Option<String>,
+    pub website: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserCreationData {
+    pub email: String,
+    pub name: String,
+    pub age: i32,
+    pub profile: Option<UserProfile>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UserUpdateData {
+    pub name: Option<String>,
+    pub age: Option<i32>,
+    pub profile: Option<UserProfile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User {
+    pub id: Uuid,
+    /// Fixed at creation; users never move between tenants
+    pub tenant_id: TenantId,
+    pub email: String,
+    pub name: String,
+    pub age: i32,
+    pub profile: UserProfile,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i64,
+}
+
+impl User {
+    /// Factory method for creating a new user; every invalid field is reported
+    pub fn create(
+        tenant_id: TenantId,
+        data: UserCreationData,
+        clock: &dyn Clock,
+        ids: &dyn IdGenerator,
+    ) -> BrikResult<User, DomainError> {
+        let (name, age, email, profile) = validate_all!(
+            Self::validate_name(&data.name),
+            Self::validate_age(data.age),
+            Self::validate_email(&data.email),
+            data.profile.map_or(Ok(UserProfile::default()), Self::validate_profile),
+        )
+        .into_result()
+        .map_err(Self::invalid)?;
+
+        let now = clock.now();
+
+        Ok(User {
+            id: ids.generate(),
+            tenant_id,
+            email,
+            name,
+            age,
+            profile,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+        })
+    }
+
+    /// Factory method for reconstructing a user from persistence
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_persistence(
+        id: Uuid,
+        tenant_id: TenantId,
+        email: String,
+        name: String,
+        age: i32,
+        profile: UserProfile,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+        version: i64,
+    ) -> Self {
+        User {
+            id,
+            tenant_id,
+            email,
+            name,
+            age,
+            profile,
+            created_at,
+            updated_at,
+            version,
+        }
+    }
+
+    /// Update user with new data, bumping the version; every invalid field is reported
+    pub fn update(&self, data: UserUpdateData, clock: &dyn Clock) -> BrikResult<User, DomainError> {
+        let (name, age, profile) = validate_all!(
+            data.name.map_or_else(|| Ok(self.name.clone()), |name| Self::validate_name(&name)),
+            data.age.map_or(Ok(self.age), Self::validate_age),
+            data.profile.map_or_else(|| Ok(self.profile.clone()), Self::validate_profile),
+        )
+        .into_result()
+        .map_err(Self::invalid)?;
+
+        let profile = UserProfile {
+            bio: profile.bio.or_else(|| self.profile.bio.clone()),
+            website: profile.website.or_else(|| self.profile.website.clone()),
+            avatar_url: profile.avatar_url.or_else(|| self.profile.avatar_url.clone()),
+        };
+
+        Ok(User {
+            id: self.id,
+            tenant_id: self.tenant_id.clone(),
+            email: self.email.clone(),
+            name,
+            age,
+            profile,
+            created_at: self.created_at,
+            updated_at: clock.now(),
+            version: self.version + 1,
+        })
+    }
+
+    /// Point the profile at a newly stored avatar, bumping the version
+    pub fn change_avatar(&self, avatar_url: String, clock: &dyn Clock) -> BrikResult<User, DomainError> {
+        self.update(
+            UserUpdateData {
+                profile: Some(UserProfile {
+                    avatar_url: Some(avatar_url),
+                    ..UserProfile::default()
+                }),
+                ..UserUpdateData::default()
+            },
+            clock,
+        )
+    }
+
+    /// Check if user can be deleted
+    pub fn can_be_deleted(&self, clock: &dyn Clock) -> BrikResult<(), DomainError> {
+        // Business rule: Users created less than 1 hour ago cannot be deleted
+        // (to prevent accidental deletions)
+        if self.created_at > clock.now() - Duration::hours(1) {
+            return Err(ErrorCode::UserDeletionTooEarly.domain_error());
+        }
+
+        Ok(())
+    }
+
+    /// A single failed check as is; several under `INVALID_USER` with each in `errors`
+    fn invalid(mut errors: Vec<DomainError>) -> DomainError {
+        if errors.len() == 1 {
+            return errors.remove(0);
+        }
+        ErrorCode::InvalidUser.domain_error().with_errors(errors)
+    }
+
+    // Validation methods
+    fn validate_name(name: &str) -> BrikResult<String, DomainError> {
+        let trimmed = name.trim();
+
+        if trimmed.chars().count() < 2 {
+            return Err(ErrorCode::InvalidUserName.domain_error_with("Name must be at least 2 characters long"));
+        }
+
+        if trimmed.chars().count() > 100 {
+            return Err(ErrorCode::InvalidUserName.domain_error_with("Name must not exceed 100 characters"));
+        }
+
+        // Basic sanitization - no control characters
+        if trimmed.chars().any(char::is_control) {
+            return Err(ErrorCode::InvalidUserName.domain_error_with("Name contains invalid characters"));
+        }
+
+        Ok(trimmed.to_string())
+    }
+
+    fn validate_age(age: i32) -> BrikResult<i32, DomainError> {
+        if age < 13 {
+            return Err(
+                ErrorCode::InvalidUserAge.domain_error_with("Users must be at least 13 years old (COPPA compliance)")
+            );
+        }
+
+        if age > 150 {
+            return Err(ErrorCode::InvalidUserAge.domain_error_with("Age must be realistic (maximum 150 years)"));
+        }
+
+        Ok(age)
+    }
+
+    fn validate_email(email: &str) -> BrikResult<String, DomainError> {
+        let normalized = email.trim().to_lowercase();
+
+        let valid = match normalized.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && domain.contains('.')
+                    && !domain.starts_with('.')
+                    && !domain.ends_with('.')
+                    && !normalized.contains(char::is_whitespace)
+            }
+            None => false,
+        };
+
+        if !valid {
+            return Err(ErrorCode::InvalidUserEmail.domain_error());
+        }
+
+        Ok(normalized)
+    }
+
+    fn validate_profile(profile: UserProfile) -> BrikResult<UserProfile, DomainError> {
+        if let Some(bio) = &profile.bio {
+            if bio.chars().count() > 500 {
+                return Err(ErrorCode::InvalidUserProfile.domain_error_with("Bio must not exceed 500 characters"));
+            }
+        }
+
+        if let Some(website) = &profile.website {
+            if !is_http_url(website) {
+                return Err(ErrorCode::InvalidUserProfile.domain_error_with("Website must be a valid URL"));
+            }
+        }
+
+        if let Some(avatar_url) = &profile.avatar_url {
+            if !is_http_url(avatar_url) {
+                return Err(
+                    ErrorCode::InvalidUserProfile.domain_error_with("Avatar URL must be a valid HTTP/HTTPS URL")
+                );
+            }
+        }
+
+        Ok(profile)
+    }
+}
+
+fn is_http_url(value: &str) -> bool {
+    value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"))
+        .map(|rest| !rest.is_empty() && !rest.starts_with('/'))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::ids::id_generator::{id_created_at, SequentialIdGenerator, UuidV7Generator};
+    use crate::shared::time::clock::{ManualClock, SystemClock};
+
+    fn tenant() -> TenantId {
+        TenantId::parse("acme").unwrap()
+    }
+
+    fn creation_data() -> UserCreationData {
+        UserCreationData {
+            email: "John.Doe@Example.com".to_string(),
+            name: "  John Doe ".to_string(),
+            age: 30,
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn test_create_user_normalizes_fields() {
+        let user = User::create(tenant(), creation_data(), &SystemClock, &UuidV7Generator::default()).unwrap();
+
+        assert_eq!(user.email, "john.doe@example.com");
+        assert_eq!(user.name, "John Doe");
+        assert_eq!(user.version, 1);
+        assert_eq!(user.created_at, user.updated_at);
+    }
+
+    #[test]
+    fn test_create_user_rejects_underage() {
+        let mut data = creation_data();
+        data.age = 12;
+
+        let error = User::create(tenant(), data, &SystemClock, &UuidV7Generator::default()).unwrap_err();
+        assert_eq!(error.code, "INVALID_USER_AGE");
+    }
+
+    #[test]
+    fn test_create_user_rejects_invalid_email() {
+        let mut data = creation_data();
+        data.email = "not-an-email".to_string();
+
+        let error = User::create(tenant(), data, &SystemClock, &UuidV7Generator::default()).unwrap_err();
+        assert_eq!(error.code, "INVALID_USER_EMAIL");
+    }
+
+    #[test]
+    fn test_create_user_rejects_invalid_avatar_url() {
+        let mut data = creation_data();
+        data.profile = Some(UserProfile {
+            avatar_url: Some("ftp://example.com/a.png".to_string()),
+            ..UserProfile::default()
+        });
+
+        let error = User::create(tenant(), data, &SystemClock, &UuidV7Generator::default()).unwrap_err();
+        assert_eq!(error.code, "INVALID_USER_PROFILE");
+    }
+
+    #[test]
+    fn test_create_user_reports_every_invalid_field() {
+        let mut data = creation_data();
+        data.name = "J".to_string();
+        data.age = 12;
+
+        let error = User::create(tenant(), data, &SystemClock, &UuidV7Generator::default()).unwrap_err();
+
+        assert_eq!(error.code, "INVALID_USER");
+        assert_eq!(error.http_status, 400);
+        let codes: Vec<_> = error.errors.iter().map(|error| error.code.as_str()).collect();
+        assert_eq!(codes, ["INVALID_USER_NAME", "INVALID_USER_AGE"]);
+    }
+
+    #[test]
+    fn test_update_user_merges_profile_and_bumps_version() {
+        let mut data = creation_data();
+        data.profile = Some(UserProfile {
+            bio: Some("Original bio".to_string()),
+            ..UserProfile::default()
+        });
+        let user = User::create(tenant(), data, &SystemClock, &UuidV7Generator::default()).unwrap();
+
+        let updated = user
+            .update(UserUpdateData {
+                name: Some("Jane Doe".to_string()),
+                profile: Some(UserProfile {
+                    website: Some("https://janedoe.com".to_string()),
+                    ..UserProfile::default()
+                }),
+                ..UserUpdateData::default()
+            }, &SystemClock)
+            .unwrap();
+
+        assert_eq!(updated.name, "Jane Doe");
+        assert_eq!(updated.profile.bio.as_deref(), Some("Original bio"));
+        assert_eq!(updated.profile.website.as_deref(), Some("https://janedoe.com"));
+        assert_eq!(updated.version, 2);
+        assert_eq!(updated.tenant_id, user.tenant_id);
+    }
+
+    #[test]
+    fn test_change_avatar_keeps_other_profile_fields() {
+        let mut data = creation_data();
+        data.profile = Some(UserProfile {
+            bio: Some("Original bio".to_string()),
+            ..UserProfile::default()
+        });
+        let user = User::create(tenant(), data, &SystemClock, &UuidV7Generator::default()).unwrap();
+
+        let updated = user.change_avatar("https://cdn.example.com/avatars/1.png".to_string(), &SystemClock).unwrap();
+        assert_eq!(updated.profile.avatar_url.as_deref(), Some("https://cdn.example.com/avatars/1.png"));
+        assert_eq!(updated.profile.bio.as_deref(), Some("Original bio"));
+
+        let error = user.change_avatar("/avatars/1.png".to_string(), &SystemClock).unwrap_err();
+        assert_eq!(error.code, "INVALID_USER_PROFILE");
+    }
+
+    #[test]
+    fn test_recent_user_cannot_be_deleted() {
+        let clock = ManualClock::starting_now();
+        let user = User::create(tenant(), creation_data(), clock.as_ref(), &UuidV7Generator::default()).unwrap();
+
+        let error = user.can_be_deleted(clock.as_ref()).unwrap_err();
+        assert_eq!(error.code, "USER_DELETION_TOO_EARLY");
+
+        clock.advance(std::time::Duration::from_secs(59 * 60));
+        assert!(user.can_be_deleted(clock.as_ref()).is_err());
+        clock.advance(std::time::Duration::from_secs(60));
+        assert!(user.can_be_deleted(clock.as_ref()).is_ok());
+    }
+
+    #[test]
+    fn test_timestamps_come_from_the_clock() {
+        let clock = ManualClock::starting_now();
+        let user = User::create(tenant(), creation_data(), clock.as_ref(), &UuidV7Generator::default()).unwrap();
+
+        clock.advance(std::time::Duration::from_secs(30));
+        let updated = user.update(UserUpdateData::default(), clock.as_ref()).unwrap();
+
+        assert_eq!(updated.created_at, user.created_at);
+        assert_eq!(updated.updated_at, user.created_at + Duration::seconds(30));
+    }
+
+    #[test]
+    fn test_id_records_when_the_user_was_created() {
+        let clock = ManualClock::new(DateTime::from_timestamp_millis(1_700_000_000_123).unwrap());
+        let ids = SequentialIdGenerator::new(clock.clone());
+
+        let first = User::create(tenant(), creation_data(), clock.as_ref(), &ids).unwrap();
+        clock.advance(std::time::Duration::from_millis(1));
+        let second = User::create(tenant(), creation_data(), clock.as_ref(), &ids).unwrap();
+
+        assert!(first.id < second.id);
+        assert_eq!(id_created_at(&first.id), Some(first.created_at));
+    }
+}
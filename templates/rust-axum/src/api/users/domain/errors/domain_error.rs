@@ -0,0 +1,52 @@
+//! BRIK v5 Domain Error - Business rule violations
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Domain rule {code} violated: {message}")]
+pub struct DomainError {
+    pub code: String,
+    pub message: String,
+    pub http_status: u16,
+    /// Values interpolated into the localized message
+    pub params: Vec<(String, String)>,
+    /// Every failed check when several were reported at once
+    pub errors: Vec<DomainError>,
+}
+
+impl DomainError {
+    pub fn new(code: &str, message: &str, http_status: u16) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.to_string(),
+            http_status,
+            params: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn with_params(mut self, params: &[(&str, &str)]) -> Self {
+        self.params = params.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        self
+    }
+
+    pub fn with_errors(mut self, errors: Vec<DomainError>) -> Self {
+        self.errors = errors;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_error_display() {
+        let error = DomainError::new("INVALID_USER_AGE", "Age must be a valid integer", 400);
+        let error_string = format!("{}", error);
+
+        assert!(error_string.contains("INVALID_USER_AGE"));
+        assert!(error_string.contains("Age must be a valid integer"));
+        assert_eq!(error.http_status, 400);
+    }
+}
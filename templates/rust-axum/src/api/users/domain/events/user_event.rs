@@ -0,0 +1,101 @@
+//! BRIK v5 User Domain Events - Facts about user lifecycle changes
+
+use crate::api::users::domain::entities::user::User;
+use crate::shared::tenancy::tenant::TenantId;
+use crate::shared::time::clock::Clock;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UserEventKind {
+    #[serde(rename = "user.created")]
+    Created,
+    #[serde(rename = "user.updated")]
+    Updated,
+    #[serde(rename = "user.deleted")]
+    Deleted,
+}
+
+impl UserEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserEventKind::Created => "user.created",
+            UserEventKind::Updated => "user.updated",
+            UserEventKind::Deleted => "user.deleted",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserEvent {
+    pub kind: UserEventKind,
+    pub tenant_id: TenantId,
+    pub user_id: Uuid,
+    /// State after the change; absent for deletions
+    pub user: Option<User>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl UserEvent {
+    pub fn created(user: &User) -> Self {
+        Self::with_user(UserEventKind::Created, user)
+    }
+
+    pub fn updated(user: &User) -> Self {
+        Self::with_user(UserEventKind::Updated, user)
+    }
+
+    /// Deletions carry no user state, so the time comes from `clock`
+    pub fn deleted(tenant_id: &TenantId, user_id: Uuid, clock: &dyn Clock) -> Self {
+        Self {
+            kind: UserEventKind::Deleted,
+            tenant_id: tenant_id.clone(),
+            user_id,
+            user: None,
+            occurred_at: clock.now(),
+        }
+    }
+
+    fn with_user(kind: UserEventKind, user: &User) -> Self {
+        Self {
+            kind,
+            tenant_id: user.tenant_id.clone(),
+            user_id: user.id,
+            user: Some(user.clone()),
+            occurred_at: user.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::ids::id_generator::UuidV7Generator;
+    use crate::shared::time::clock::{ManualClock, SystemClock};
+    use chrono::TimeZone;
+    use crate::api::users::domain::entities::user::UserCreationData;
+
+    #[test]
+    fn test_event_constructors() {
+        let user = User::create(TenantId::parse("acme").unwrap(), UserCreationData {
+            email: "john@example.com".to_string(),
+            name: "John Doe".to_string(),
+            age: 30,
+            profile: None,
+        }, &SystemClock, &UuidV7Generator::default())
+        .unwrap();
+
+        let created = UserEvent::created(&user);
+        assert_eq!(created.kind, UserEventKind::Created);
+        assert_eq!(created.occurred_at, user.updated_at);
+
+        let clock = ManualClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap());
+        let deleted = UserEvent::deleted(&user.tenant_id, user.id, clock.as_ref());
+        assert_eq!(deleted.occurred_at, clock.now());
+        assert_eq!(deleted.user_id, user.id);
+        assert_eq!(deleted.tenant_id, user.tenant_id);
+        assert!(deleted.user.is_none());
+        assert_eq!(serde_json::to_value(deleted.kind).unwrap(), "user.deleted");
+    }
+}
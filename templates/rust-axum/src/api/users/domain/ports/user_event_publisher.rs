@@ -0,0 +1,10 @@
+//! BRIK v5 User Event Publisher Port
+
+use crate::api::users::domain::events::user_event::UserEvent;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::types::result::BrikResult;
+
+#[async_trait::async_trait]
+pub trait UserEventPublisher: Send + Sync {
+    async fn publish(&self, event: UserEvent) -> BrikResult<(), PortError>;
+}
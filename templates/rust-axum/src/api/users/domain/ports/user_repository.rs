@@ -0,0 +1,44 @@
+//! BRIK v5 User Repository Port
+//!
+//! Every lookup takes the caller's tenant, and writes are scoped to the
+//! tenant of the user being written: a user of another tenant is reported as
+//! absent, exactly like one that does not exist.
+//!
+//! Password hashes are kept apart from `User`, so they never reach DTOs,
+//! events or the user cache.
+
+use crate::api::users::domain::entities::user::User;
+use crate::api::users::domain::queries::list_users_query::ListUsersQuery;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::tenancy::tenant::TenantId;
+use crate::shared::types::result::BrikResult;
+use uuid::Uuid;
+
+/// Persistence port for users; adapters live in `api::users::adapters`
+#[async_trait::async_trait]
+pub trait UserRepository: Send + Sync {
+    /// Emails are unique per tenant
+    async fn create(&self, user: &User) -> BrikResult<(), PortError>;
+
+    async fn get_by_id(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<User>, PortError>;
+
+    async fn get_by_email(&self, tenant: &TenantId, email: &str) -> BrikResult<Option<User>, PortError>;
+
+    /// Returns the users after `query.after` in sort order, at most
+    /// `query.page.limit + 1` of them so callers can tell whether more exist
+    async fn list(&self, tenant: &TenantId, query: &ListUsersQuery) -> BrikResult<Vec<User>, PortError>;
+
+    /// Persists a new version of the user; fails with `VERSION_CONFLICT` when
+    /// the stored version is not the one the update was based on
+    async fn update(&self, user: &User) -> BrikResult<(), PortError>;
+
+    /// Returns whether a user was actually deleted
+    async fn delete(&self, tenant: &TenantId, id: Uuid) -> BrikResult<bool, PortError>;
+
+    /// `None` when the user does not exist or has no password
+    async fn get_password_hash(&self, tenant: &TenantId, id: Uuid) -> BrikResult<Option<String>, PortError>;
+
+    /// Replaces the stored hash without touching the user's version; returns
+    /// whether the user exists
+    async fn set_password_hash(&self, tenant: &TenantId, id: Uuid, hash: &str) -> BrikResult<bool, PortError>;
+}
@@ -0,0 +1,271 @@
+//! BRIK v5 List Users Query - Filters, sort whitelist and keyset position
+
+use crate::api::users::domain::entities::user::User;
+use crate::api::users::gates::gate_result::GateError;
+use crate::shared::pagination::cursor::Cursor;
+use crate::shared::pagination::list_query::{PageRequest, QueryParser, Sort, SortField};
+use crate::shared::pagination::page::Page;
+use crate::shared::types::result::BrikResult;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserSortField {
+    CreatedAt,
+    Name,
+    Email,
+    Age,
+}
+
+impl SortField for UserSortField {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "created_at" => Some(UserSortField::CreatedAt),
+            "name" => Some(UserSortField::Name),
+            "email" => Some(UserSortField::Email),
+            "age" => Some(UserSortField::Age),
+            _ => None,
+        }
+    }
+
+    /// Doubles as the column name, so only whitelisted names reach SQL
+    fn as_str(&self) -> &'static str {
+        match self {
+            UserSortField::CreatedAt => "created_at",
+            UserSortField::Name => "name",
+            UserSortField::Email => "email",
+            UserSortField::Age => "age",
+        }
+    }
+
+    fn allowed() -> &'static [&'static str] {
+        &["created_at", "name", "email", "age"]
+    }
+}
+
+/// Typed value of the sort key, as stored in a cursor
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum SortValue {
+    Text(String),
+    Integer(i32),
+    Timestamp(DateTime<Utc>),
+}
+
+impl UserSortField {
+    pub fn value_of(&self, user: &User) -> SortValue {
+        match self {
+            UserSortField::CreatedAt => SortValue::Timestamp(user.created_at),
+            UserSortField::Name => SortValue::Text(user.name.clone()),
+            UserSortField::Email => SortValue::Text(user.email.clone()),
+            UserSortField::Age => SortValue::Integer(user.age),
+        }
+    }
+
+    fn decode_value(&self, key: &serde_json::Value) -> Option<SortValue> {
+        match self {
+            UserSortField::CreatedAt => serde_json::from_value(key.clone()).ok().map(SortValue::Timestamp),
+            UserSortField::Name | UserSortField::Email => key.as_str().map(|s| SortValue::Text(s.to_string())),
+            UserSortField::Age => key.as_i64().and_then(|n| i32::try_from(n).ok()).map(SortValue::Integer),
+        }
+    }
+}
+
+impl SortValue {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            SortValue::Text(value) => serde_json::json!(value),
+            SortValue::Integer(value) => serde_json::json!(value),
+            SortValue::Timestamp(value) => serde_json::json!(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserFilter {
+    /// Matches the part after `@`, case-insensitively
+    pub email_domain: Option<String>,
+    pub min_age: Option<i32>,
+    pub max_age: Option<i32>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+impl UserFilter {
+    pub fn matches(&self, user: &User) -> bool {
+        let domain_matches = self.email_domain.as_ref().is_none_or(|domain| {
+            user.email
+                .rsplit_once('@')
+                .is_some_and(|(_, user_domain)| user_domain.eq_ignore_ascii_case(domain))
+        });
+
+        domain_matches
+            && self.min_age.is_none_or(|min| user.age >= min)
+            && self.max_age.is_none_or(|max| user.age <= max)
+            && self.created_after.is_none_or(|after| user.created_at >= after)
+            && self.created_before.is_none_or(|before| user.created_at < before)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListUsersQuery {
+    pub filter: UserFilter,
+    pub page: PageRequest<UserSortField>,
+    /// Sort key and id of the last user of the previous page
+    pub after: Option<(SortValue, Uuid)>,
+}
+
+impl ListUsersQuery {
+    pub fn default_sort() -> Sort<UserSortField> {
+        Sort::desc(UserSortField::CreatedAt)
+    }
+
+    /// Parses `GET /users` query parameters, reporting every invalid one at once
+    pub fn from_params(params: &HashMap<String, String>) -> BrikResult<Self, GateError> {
+        let mut parser = QueryParser::new(params);
+
+        let email_domain = parser.string("email_domain").map(|domain| domain.to_lowercase());
+        if email_domain.as_ref().is_some_and(|domain| domain.contains('@') || !domain.contains('.')) {
+            parser.error("email_domain must be a domain such as example.com".to_string());
+        }
+
+        let min_age = parser.number("min_age", 13..=150);
+        let max_age = parser.number("max_age", 13..=150);
+        parser.check_range("min_age", min_age, "max_age", max_age);
+
+        let created_after = parser.datetime("created_after");
+        let created_before = parser.datetime("created_before");
+        parser.check_range("created_after", created_after, "created_before", created_before);
+
+        let page = parser.page_request(Self::default_sort());
+
+        let after = page.cursor.as_ref().and_then(|cursor| {
+            let value = page.sort.field.decode_value(&cursor.key);
+            if value.is_none() {
+                parser.error("cursor is malformed".to_string());
+            }
+            value.map(|value| (value, cursor.id))
+        });
+
+        parser.finish(Self {
+            filter: UserFilter {
+                email_domain,
+                min_age,
+                max_age,
+                created_after,
+                created_before,
+            },
+            page,
+            after,
+        })
+    }
+
+    pub fn cursor_for(&self, user: &User) -> Cursor {
+        let field = self.page.sort.field;
+        Cursor::new(self.page.sort.to_param(), field.value_of(user).to_json(), user.id)
+    }
+
+    /// Turns the `limit + 1` rows returned by the repository into a page
+    pub fn into_page(&self, users: Vec<User>) -> Page<User> {
+        Page::from_rows(users, &self.page, |user| self.cursor_for(user), |user| user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::ids::id_generator::UuidV7Generator;
+    use crate::shared::time::clock::SystemClock;
+    use crate::api::users::domain::entities::user::UserCreationData;
+    use crate::shared::tenancy::tenant::TenantId;
+    use crate::shared::pagination::list_query::SortDirection;
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn user(email: &str, age: i32) -> User {
+        User::create(TenantId::parse("acme").unwrap(), UserCreationData {
+            email: email.to_string(),
+            name: "John Doe".to_string(),
+            age,
+            profile: None,
+        }, &SystemClock, &UuidV7Generator::default())
+        .unwrap()
+    }
+
+    #[test]
+    fn test_defaults_to_newest_first() {
+        let query = ListUsersQuery::from_params(&params(&[])).unwrap();
+
+        assert_eq!(query.page.sort.field, UserSortField::CreatedAt);
+        assert_eq!(query.page.sort.direction, SortDirection::Desc);
+        assert_eq!(query.filter, UserFilter::default());
+        assert!(query.after.is_none());
+    }
+
+    #[test]
+    fn test_parses_filters() {
+        let query = ListUsersQuery::from_params(&params(&[
+            ("email_domain", "Example.com"),
+            ("min_age", "18"),
+            ("max_age", "65"),
+            ("created_after", "2024-01-01T00:00:00Z"),
+            ("sort", "age"),
+        ]))
+        .unwrap();
+
+        assert_eq!(query.filter.email_domain.as_deref(), Some("example.com"));
+        assert_eq!(query.filter.min_age, Some(18));
+        assert_eq!(query.filter.max_age, Some(65));
+        assert!(query.filter.created_after.is_some());
+        assert_eq!(query.page.sort, Sort::asc(UserSortField::Age));
+    }
+
+    #[test]
+    fn test_rejects_invalid_filters() {
+        let error = ListUsersQuery::from_params(&params(&[
+            ("email_domain", "@example"),
+            ("min_age", "70"),
+            ("max_age", "30"),
+        ]))
+        .unwrap_err();
+
+        assert_eq!(error.code, "INVALID_QUERY");
+        assert!(error.message.contains("email_domain"));
+        assert!(error.message.contains("min_age must not be greater than max_age"));
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let query = ListUsersQuery::from_params(&params(&[("sort", "-age")])).unwrap();
+        let last = user("john@example.com", 42);
+
+        let cursor = query.cursor_for(&last).encode();
+        let next = ListUsersQuery::from_params(&params(&[("sort", "-age"), ("cursor", &cursor)])).unwrap();
+
+        assert_eq!(next.after, Some((SortValue::Integer(42), last.id)));
+    }
+
+    #[test]
+    fn test_cursor_with_wrong_key_type_is_rejected() {
+        let cursor = Cursor::new("age".to_string(), serde_json::json!("old"), Uuid::new_v4()).encode();
+
+        let error = ListUsersQuery::from_params(&params(&[("sort", "age"), ("cursor", &cursor)])).unwrap_err();
+
+        assert!(error.message.contains("cursor is malformed"));
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let filter = UserFilter {
+            email_domain: Some("example.com".to_string()),
+            min_age: Some(18),
+            ..UserFilter::default()
+        };
+
+        assert!(filter.matches(&user("john@example.com", 30)));
+        assert!(!filter.matches(&user("john@example.org", 30)));
+        assert!(!filter.matches(&user("kid@example.com", 15)));
+    }
+}
@@ -0,0 +1,112 @@
+//! BRIK v5 User Passwords - Setting and checking passwords through the hasher
+//!
+//! A successful check upgrades hashes made with another algorithm or outdated
+//! parameters: the fresh hash from `PasswordVerification::Valid` replaces the
+//! stored one. Failing to store it is logged, not reported, since the password
+//! was correct and the next check retries the upgrade.
+
+use crate::api::users::domain::ports::user_repository::UserRepository;
+use crate::brik_log;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::security::password_hasher::{PasswordHasher, PasswordVerification};
+use crate::shared::tenancy::tenant::TenantId;
+use crate::shared::types::result::BrikResult;
+use uuid::Uuid;
+
+/// Hashes and stores `password`; returns whether the user exists
+pub async fn set_password(
+    repository: &dyn UserRepository,
+    hasher: &dyn PasswordHasher,
+    tenant: &TenantId,
+    id: Uuid,
+    password: &str,
+) -> BrikResult<bool, PortError> {
+    let hash = hasher.hash(password).await?;
+    repository.set_password_hash(tenant, id, &hash).await
+}
+
+/// Whether `password` matches the user's; users without a password never match
+pub async fn verify_password(
+    repository: &dyn UserRepository,
+    hasher: &dyn PasswordHasher,
+    tenant: &TenantId,
+    id: Uuid,
+    password: &str,
+) -> BrikResult<bool, PortError> {
+    let Some(stored_hash) = repository.get_password_hash(tenant, id).await? else {
+        return Ok(false);
+    };
+
+    match hasher.verify(password, &stored_hash).await? {
+        PasswordVerification::Invalid => Ok(false),
+        PasswordVerification::Valid { rehashed } => {
+            if let Some(rehashed) = rehashed {
+                if let Err(error) = repository.set_password_hash(tenant, id, &rehashed).await {
+                    brik_log!(
+                        WARN,
+                        "Failed to store upgraded password hash",
+                        port = error.port.as_str(),
+                        subject_user_id = %id,
+                        error = error.message.as_str()
+                    );
+                }
+            }
+            Ok(true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::users::adapters::in_memory_user_repository::InMemoryUserRepository;
+    use crate::api::users::domain::entities::user::{User, UserCreationData};
+    use crate::shared::ids::id_generator::UuidV7Generator;
+    use crate::shared::security::password_hasher::{Argon2Hasher, BcryptHasher};
+    use crate::shared::time::clock::SystemClock;
+
+    async fn stored_user(repository: &InMemoryUserRepository) -> User {
+        let user = User::create(TenantId::parse("acme").unwrap(), UserCreationData {
+            email: "john@example.com".to_string(),
+            name: "John Doe".to_string(),
+            age: 30,
+            profile: None,
+        }, &SystemClock, &UuidV7Generator::default())
+        .unwrap();
+        repository.create(&user).await.unwrap();
+        user
+    }
+
+    #[tokio::test]
+    async fn test_verifies_the_stored_password() {
+        let repository = InMemoryUserRepository::new();
+        let hasher = BcryptHasher::new(4);
+        let user = stored_user(&repository).await;
+        assert!(!verify_password(&repository, &hasher, &user.tenant_id, user.id, "s3cret").await.unwrap());
+
+        assert!(set_password(&repository, &hasher, &user.tenant_id, user.id, "s3cret").await.unwrap());
+
+        assert!(verify_password(&repository, &hasher, &user.tenant_id, user.id, "s3cret").await.unwrap());
+        assert!(!verify_password(&repository, &hasher, &user.tenant_id, user.id, "wrong").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_outdated_hashes_are_upgraded_on_success() {
+        let repository = InMemoryUserRepository::new();
+        let user = stored_user(&repository).await;
+        set_password(&repository, &BcryptHasher::new(4), &user.tenant_id, user.id, "s3cret").await.unwrap();
+        let hasher = Argon2Hasher::new(64, 1, 1).unwrap();
+
+        assert!(!verify_password(&repository, &hasher, &user.tenant_id, user.id, "wrong").await.unwrap());
+        let stored = repository.get_password_hash(&user.tenant_id, user.id).await.unwrap().unwrap();
+        assert!(stored.starts_with("$2b$04$"));
+
+        assert!(verify_password(&repository, &hasher, &user.tenant_id, user.id, "s3cret").await.unwrap());
+        let upgraded = repository.get_password_hash(&user.tenant_id, user.id).await.unwrap().unwrap();
+        assert!(upgraded.starts_with("$argon2id$v=19$m=64,t=1,p=1$"));
+        assert_eq!(
+            hasher.verify("s3cret", &upgraded).await.unwrap(),
+            PasswordVerification::Valid { rehashed: None }
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! BRIK v5 User DTOs - Wire format for the users endpoints
+//! Type names match the schema names in `openapi.yaml`
+
+use crate::api::users::domain::entities::user as domain;
+use crate::shared::pagination::page::{Page, PageInfo};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct UserProfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(max_length = 500, example = "Software developer and BRIK architecture enthusiast")]
+    pub bio: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = "uri", example = "https://johndoe.com")]
+    pub website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = "uri", example = "https://example.com/avatars/johndoe.jpg")]
+    pub avatar_url: Option<String>,
+}
+
+impl From<domain::UserProfile> for UserProfile {
+    fn from(profile: domain::UserProfile) -> Self {
+        Self {
+            bio: profile.bio,
+            website: profile.website,
+            avatar_url: profile.avatar_url,
+        }
+    }
+}
+
+impl From<UserProfile> for domain::UserProfile {
+    fn from(profile: UserProfile) -> Self {
+        Self {
+            bio: profile.bio,
+            website: profile.website,
+            avatar_url: profile.avatar_url,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct User {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+    #[schema(format = "email", example = "john.doe@example.com")]
+    pub email: String,
+    #[schema(min_length = 2, max_length = 100, example = "John Doe")]
+    pub name: String,
+    #[schema(minimum = 13, maximum = 150, example = 30)]
+    pub age: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<UserProfile>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[schema(example = 1)]
+    pub version: i64,
+}
+
+impl From<domain::User> for User {
+    fn from(user: domain::User) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            name: user.name,
+            age: user.age,
+            profile: Some(user.profile.into()),
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            version: user.version,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateUserRequest {
+    #[schema(format = "email", example = "john.doe@example.com")]
+    pub email: String,
+    #[schema(min_length = 2, max_length = 100, example = "John Doe")]
+    pub name: String,
+    #[schema(minimum = 13, maximum = 150, example = 30)]
+    pub age: i32,
+    pub profile: Option<UserProfile>,
+}
+
+impl From<CreateUserRequest> for domain::UserCreationData {
+    fn from(request: CreateUserRequest) -> Self {
+        Self {
+            email: request.email,
+            name: request.name,
+            age: request.age,
+            profile: request.profile.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateUserMetadata {
+    #[schema(example = "req_abc123def456")]
+    pub correlation_id: String,
+    #[schema(example = "user-creation-2024-01-15-abc123")]
+    pub idempotency_key: String,
+    /// true if this response was returned from idempotency cache
+    pub cached: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateUserResponse {
+    pub user: User,
+    #[schema(inline)]
+    pub metadata: CreateUserMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ResponseMetadata {
+    #[schema(example = "req_abc123def456")]
+    pub correlation_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetUserResponse {
+    pub user: User,
+    #[schema(inline)]
+    pub metadata: ResponseMetadata,
+}
+
+/// Multipart body of `PUT /users/{id}/avatar`
+#[derive(Debug, ToSchema)]
+pub struct AvatarUpload {
+    /// JPEG, PNG or WebP image, at most 2 MiB
+    #[schema(value_type = String, format = Binary)]
+    pub avatar: Vec<u8>,
+}
+
+/// `Page<User>` envelope of `GET /users`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserPage {
+    pub data: Vec<User>,
+    pub page: PageInfo,
+    #[schema(inline)]
+    pub metadata: ResponseMetadata,
+}
+
+impl UserPage {
+    pub fn new(page: Page<domain::User>, metadata: ResponseMetadata) -> Self {
+        Self {
+            data: page.data.into_iter().map(Into::into).collect(),
+            page: page.page,
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::ids::id_generator::UuidV7Generator;
+    use crate::shared::time::clock::SystemClock;
+    use crate::shared::tenancy::tenant::TenantId;
+
+    #[test]
+    fn test_create_user_request_deserialization() {
+        let request: CreateUserRequest = serde_json::from_value(serde_json::json!({
+            "email": "jane.doe@example.com",
+            "name": "Jane Doe",
+            "age": 28,
+            "profile": { "bio": "Software engineer" }
+        }))
+        .unwrap();
+
+        let data = domain::UserCreationData::from(request);
+        assert_eq!(data.profile.unwrap().bio.as_deref(), Some("Software engineer"));
+    }
+
+    #[test]
+    fn test_user_dto_serializes_snake_case() {
+        let user = domain::User::create(TenantId::parse("acme").unwrap(), domain::UserCreationData {
+            email: "john.doe@example.com".to_string(),
+            name: "John Doe".to_string(),
+            age: 30,
+            profile: None,
+        }, &SystemClock, &UuidV7Generator::default())
+        .unwrap();
+
+        let json = serde_json::to_value(User::from(user)).unwrap();
+        assert!(json.get("created_at").is_some());
+        assert!(json.get("updated_at").is_some());
+        assert_eq!(json["version"], 1);
+    }
+}
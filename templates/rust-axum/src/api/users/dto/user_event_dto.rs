@@ -0,0 +1,59 @@
+//! BRIK v5 User Event DTOs - Messages sent over the user events WebSocket
+
+use crate::api::users::domain::events::user_event::{UserEvent, UserEventKind};
+use crate::api::users::dto::user_dto::User;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UserEventMessage {
+    Event {
+        /// Pass back as `last_event_id` when reconnecting
+        id: u64,
+        event: UserEventKind,
+        user_id: Uuid,
+        occurred_at: DateTime<Utc>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        user: Option<Box<User>>,
+    },
+    /// Events after the requested id are no longer retained; reload state
+    /// over the REST endpoints before relying on the stream again
+    ResyncRequired { last_event_id: u64 },
+}
+
+impl UserEventMessage {
+    pub fn event(id: u64, event: &UserEvent) -> Self {
+        UserEventMessage::Event {
+            id,
+            event: event.kind,
+            user_id: event.user_id,
+            occurred_at: event.occurred_at,
+            user: event.user.clone().map(|user| Box::new(user.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::time::clock::SystemClock;
+    use crate::shared::tenancy::tenant::TenantId;
+
+    #[test]
+    fn test_message_wire_format() {
+        let event = UserEvent::deleted(&TenantId::parse("acme").unwrap(), Uuid::nil(), &SystemClock);
+
+        let json = serde_json::to_value(UserEventMessage::event(7, &event)).unwrap();
+
+        assert_eq!(json["type"], "event");
+        assert_eq!(json["id"], 7);
+        assert_eq!(json["event"], "user.deleted");
+        assert!(json.get("user").is_none());
+        assert_eq!(
+            serde_json::to_value(UserEventMessage::ResyncRequired { last_event_id: 3 }).unwrap(),
+            serde_json::json!({ "type": "resync_required", "last_event_id": 3 })
+        );
+    }
+}
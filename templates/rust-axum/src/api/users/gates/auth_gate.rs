@@ -1,23 +1,37 @@
 //! BRIK v5 Authentication Gate for Rust
 
+use crate::shared::config::settings::AuthSettings;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::security::secret::Secret;
+use crate::shared::tenancy::tenant::{TenantId, TenantRegistry};
+use crate::shared::time::clock::{SharedClock, SystemClock};
 use crate::shared::types::result::BrikResult;
-use super::gate_result::{GateResult, GateTimer, RequestGate, GateError};
-use axum::{
-    extract::Request,
-    http::HeaderMap,
-};
-use jsonwebtoken::{decode, DecodingKey, Validation, Algorithm};
+use crate::shared::observability::metrics::{CACHE_HITS_TOTAL, CACHE_MISSES_TOTAL};
+use super::credentials::{bearer_token, JwtClaims, TokenVerifier};
+use super::gate_result::{GateResult, GateTimer, RequestGate};
+use super::token_cache::TokenCache;
+use axum::http::HeaderMap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthContext {
     pub user_id: String,
+    /// Every repository call made for this request is scoped to it
+    pub tenant_id: TenantId,
     pub scopes: Vec<String>,
     pub email: Option<String>,
     pub roles: Option<Vec<String>>,
 }
 
+impl AuthContext {
+    /// Same rules as the gate: exact scope, `resource:*`, `*:*` or the admin
+    /// override for admin-only scopes
+    pub fn has_scope(&self, scope: &SecurityScope) -> bool {
+        scope_granted(&self.scopes, self.roles.as_deref().unwrap_or_default(), scope)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SecurityScope {
     pub resource: String,
@@ -32,37 +46,102 @@ pub struct ScopeConstraints {
     pub admin_only: bool,
 }
 
-#[derive(Debug, Deserialize)]
-struct JwtClaims {
-    sub: Option<String>,
-    user_id: Option<String>,
-    email: Option<String>,
-    scopes: Option<Vec<String>>,
-    roles: Option<Vec<String>>,
-    exp: usize,
+fn scope_granted(user_scopes: &[String], user_roles: &[String], required: &SecurityScope) -> bool {
+    let scope_string = format!("{}:{}", required.resource, required.action);
+
+    // Check direct scope
+    if user_scopes.contains(&scope_string) {
+        return true;
+    }
+
+    // Check wildcard scopes
+    let resource_wildcard = format!("{}:*", required.resource);
+    if user_scopes.contains(&resource_wildcard) || user_scopes.contains(&"*:*".to_string()) {
+        return true;
+    }
+
+    // Check admin override
+    matches!(
+        &required.constraints,
+        Some(constraints) if constraints.admin_only && user_roles.contains(&"admin".to_string())
+    )
 }
 
 pub struct AuthGate {
-    jwt_secret: String,
+    /// Primary secret first, then secrets still honoured during a rotation
+    jwt_secrets: Vec<Secret<String>>,
+    /// Decoding keys for `jwt_secrets`, rebuilt only when they change
+    verifier: TokenVerifier,
+    token_cache: Option<Arc<TokenCache>>,
     required_scopes: Vec<SecurityScope>,
+    tenants: Arc<TenantRegistry>,
+    /// Token expiry, cache entries and gate timings
+    clock: SharedClock,
 }
 
 impl AuthGate {
-    pub fn new(jwt_secret: String, required_scopes: Vec<SecurityScope>) -> Self {
+    pub fn new(jwt_secret: Secret<String>, required_scopes: Vec<SecurityScope>) -> Self {
         Self {
-            jwt_secret,
+            verifier: TokenVerifier::new([jwt_secret.expose().as_bytes()]),
+            jwt_secrets: vec![jwt_secret],
+            token_cache: None,
             required_scopes,
+            tenants: Arc::new(TenantRegistry::default()),
+            clock: SystemClock::shared(),
         }
     }
 
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Tenant claim, default tenant and suspensions; `TenantRegistry::default()` otherwise
+    pub fn with_tenants(mut self, tenants: Arc<TenantRegistry>) -> Self {
+        self.tenants = tenants;
+        self
+    }
+
+    /// Tokens signed with these secrets keep verifying until they are removed
+    pub fn with_previous_secrets(mut self, previous: Vec<Secret<String>>) -> Self {
+        self.jwt_secrets.truncate(1);
+        self.jwt_secrets.extend(previous);
+        self.verifier = TokenVerifier::new(self.jwt_secrets.iter().map(|secret| secret.expose().as_bytes()));
+        self
+    }
+
+    /// Skips signature verification for tokens already verified and not yet
+    /// expired; share a cache only between gates with the same secrets
+    pub fn with_token_cache(mut self, token_cache: Arc<TokenCache>) -> Self {
+        self.token_cache = Some(token_cache);
+        self
+    }
+
+    pub fn from_settings(settings: &AuthSettings, required_scopes: Vec<SecurityScope>) -> Self {
+        Self::new(settings.jwt_secret.clone(), required_scopes)
+            .with_previous_secrets(settings.previous_jwt_secrets.clone())
+    }
+
+    fn decode_claims(&self, token: &str) -> Result<Arc<JwtClaims>, jsonwebtoken::errors::Error> {
+        let now = self.clock.now().timestamp().max(0) as u64;
+        let Some(cache) = &self.token_cache else {
+            return self.verifier.decode(token, now).map(Arc::new);
+        };
+
+        if let Some(claims) = cache.get(token, now) {
+            metrics::counter!(CACHE_HITS_TOTAL, "cache" => "auth_tokens").increment(1);
+            return Ok(claims);
+        }
+        metrics::counter!(CACHE_MISSES_TOTAL, "cache" => "auth_tokens").increment(1);
+
+        let claims = Arc::new(self.verifier.decode(token, now)?);
+        cache.insert(token, claims.clone());
+        Ok(claims)
+    }
+
     fn extract_bearer_token(&self, headers: &HeaderMap) -> Option<String> {
-        headers
-            .get("authorization")?
-            .to_str()
-            .ok()?
-            .strip_prefix("Bearer ")?
-            .to_string()
-            .into()
+        let authorization = headers.get("authorization")?.to_str().ok()?;
+        bearer_token(authorization).map(str::to_string)
     }
 
     fn validate_scopes(
@@ -72,28 +151,9 @@ impl AuthGate {
         required_scopes: &[SecurityScope],
     ) -> BrikResult<(), String> {
         for required in required_scopes {
-            let scope_string = format!("{}:{}", required.resource, required.action);
-            
-            // Check direct scope
-            if user_scopes.contains(&scope_string) {
-                continue;
-            }
-            
-            // Check wildcard scopes
-            let resource_wildcard = format!("{}:*", required.resource);
-            if user_scopes.contains(&resource_wildcard) || user_scopes.contains(&"*:*".to_string()) {
-                continue;
-            }
-            
-            // Check admin override
-            if let Some(constraints) = &required.constraints {
-                if constraints.admin_only && user_roles.contains(&"admin".to_string()) {
-                    continue;
-                }
+            if !scope_granted(user_scopes, user_roles, required) {
+                return Err(format!("{}:{}", required.resource, required.action));
             }
-            
-            // Scope not found
-            return Err(scope_string);
         }
         
         Ok(())
@@ -101,69 +161,66 @@ impl AuthGate {
 }
 
 #[async_trait::async_trait]
-impl RequestGate<&HeaderMap, AuthContext> for AuthGate {
+impl<'a> RequestGate<&'a HeaderMap, AuthContext> for AuthGate {
     fn name(&self) -> &'static str {
         "AuthGate"
     }
 
-    async fn validate(&self, headers: &HeaderMap) -> GateResult<AuthContext> {
-        let timer = GateTimer::start();
+    async fn validate(&self, headers: &'a HeaderMap) -> GateResult<AuthContext> {
+        let timer = GateTimer::start_on(self.clock.as_ref());
 
         // 1. Extract JWT token
         let token = match self.extract_bearer_token(headers) {
             Some(token) => token,
             None => {
-                return GateResult::failure(
-                    "AuthGate",
-                    "AUTH_TOKEN_MISSING",
-                    "Authorization header with Bearer token is required",
-                    401,
-                    Some(timer.elapsed()),
-                );
+                return GateResult::rejected(ErrorCode::AuthTokenMissing.gate_error("AuthGate"), Some(timer.elapsed()));
             }
         };
 
         // 2. Verify JWT signature and decode
-        let decoding_key = DecodingKey::from_secret(self.jwt_secret.as_ref());
-        let validation = Validation::new(Algorithm::HS256);
-
-        let token_data = match decode::<JwtClaims>(&token, &decoding_key, &validation) {
-            Ok(data) => data,
+        let claims = match self.decode_claims(&token) {
+            Ok(claims) => claims,
             Err(_) => {
-                return GateResult::failure(
-                    "AuthGate",
-                    "AUTH_TOKEN_INVALID",
-                    "Invalid or expired JWT token",
-                    401,
-                    Some(timer.elapsed()),
-                );
+                return GateResult::rejected(ErrorCode::AuthTokenInvalid.gate_error("AuthGate"), Some(timer.elapsed()));
             }
         };
 
-        let claims = token_data.claims;
-
         // 3. Extract auth context
-        let user_id = claims.sub
-            .or(claims.user_id)
+        let user_id = claims.sub.as_ref()
+            .or(claims.user_id.as_ref())
+            .cloned()
             .unwrap_or_else(|| "unknown".to_string());
 
-        let scopes = claims.scopes.unwrap_or_default();
-        let roles = claims.roles.unwrap_or_default();
+        // A claim that is present but not a string must not fall back to the default tenant
+        let claimed_tenant = claims
+            .other
+            .get(self.tenants.claim())
+            .map(|value| value.as_str().unwrap_or_default());
+        let Some(tenant_id) = self.tenants.resolve(claimed_tenant) else {
+            return GateResult::rejected(
+                ErrorCode::AuthTenantMissing.gate_error("AuthGate"),
+                Some(timer.elapsed()),
+            );
+        };
+        if self.tenants.config(&tenant_id).suspended {
+            return GateResult::rejected(ErrorCode::TenantSuspended.gate_error("AuthGate"), Some(timer.elapsed()));
+        }
+
+        let scopes = claims.scopes.clone().unwrap_or_default();
+        let roles = claims.roles.clone().unwrap_or_default();
 
         let auth_context = AuthContext {
             user_id,
+            tenant_id,
             scopes: scopes.clone(),
-            email: claims.email,
+            email: claims.email.clone(),
             roles: Some(roles.clone()),
         };
 
         // 4. Validate required scopes
         if let Err(missing_scope) = self.validate_scopes(&scopes, &roles, &self.required_scopes) {
-            return GateResult::failure(
-                "AuthGate",
-                "AUTH_INSUFFICIENT_SCOPES",
-                &format!("Missing required scope: {}", missing_scope),
-                403,
+            return GateResult::rejected(
+                ErrorCode::AuthInsufficientScopes.gate_error_with_params("AuthGate", &[("scope", &missing_scope)]),
                 Some(timer.elapsed()),
             );
         }
@@ -176,34 +233,6 @@ impl RequestGate<&HeaderMap, AuthContext> for AuthGate {
 pub struct UserScopes;
 
 impl UserScopes {
-    pub const CREATE: SecurityScope = SecurityScope {
-        resource: "users".to_string(),
-        action: "create".to_string(),
-        constraints: None,
-    };
-
-    pub const READ: SecurityScope = SecurityScope {
-        resource: "users".to_string(),
-        action: "read".to_string(),
-        constraints: None,
-    };
-
-    pub const UPDATE: SecurityScope = SecurityScope {
-        resource: "users".to_string(),
-        action: "update".to_string(),
-        constraints: None,
-    };
-
-    pub const DELETE: SecurityScope = SecurityScope {
-        resource: "users".to_string(),
-        action: "delete".to_string(),
-        constraints: Some(ScopeConstraints {
-            owned_only: false,
-            department_only: false,
-            admin_only: true,
-        }),
-    };
-
     pub fn create() -> SecurityScope {
         SecurityScope {
             resource: "users".to_string(),
@@ -228,6 +257,19 @@ impl UserScopes {
         }
     }
 
+    /// Acting on another user's own resources, such as their avatar
+    pub fn admin() -> SecurityScope {
+        SecurityScope {
+            resource: "users".to_string(),
+            action: "admin".to_string(),
+            constraints: Some(ScopeConstraints {
+                owned_only: false,
+                department_only: false,
+                admin_only: true,
+            }),
+        }
+    }
+
     pub fn delete() -> SecurityScope {
         SecurityScope {
             resource: "users".to_string(),
@@ -244,7 +286,12 @@ impl UserScopes {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::config::settings::{TenancySettings, TenantOverride};
+    use crate::testkit::token::{TokenBuilder, TEST_JWT_SECRET};
     use axum::http::{HeaderValue, HeaderMap};
+    use chrono::Duration;
+    use jsonwebtoken::Algorithm;
+    use proptest::prelude::*;
 
     fn create_test_headers(token: &str) -> HeaderMap {
         let mut headers = HeaderMap::new();
@@ -255,9 +302,122 @@ mod tests {
         headers
     }
 
+    fn gate(required_scopes: Vec<SecurityScope>) -> AuthGate {
+        AuthGate::new(Secret::new(TEST_JWT_SECRET.to_string()), required_scopes)
+    }
+
+    fn reader() -> TokenBuilder {
+        TokenBuilder::new().subject("user-123").scopes(&["users:read"])
+    }
+
+    #[tokio::test]
+    async fn test_valid_token_yields_auth_context() {
+        let token = reader().email("john@example.com").roles(&["support"]).tenant("acme");
+
+        let context = gate(vec![UserScopes::read()]).validate(&token.headers()).await.data.unwrap();
+
+        assert_eq!(context.user_id, "user-123");
+        assert_eq!(context.tenant_id.as_str(), "acme");
+        assert_eq!(context.email.as_deref(), Some("john@example.com"));
+        assert_eq!(context.roles, Some(vec!["support".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_expired_and_foreign_tokens() {
+        let gate = gate(vec![]);
+
+        for token in [
+            reader().expired(),
+            reader().without_claim("exp"),
+            reader().algorithm(Algorithm::HS512),
+            reader().signed_with("someone-elses-secret"),
+        ] {
+            let result = gate.validate(&token.headers()).await;
+            assert_eq!(result.error.unwrap().code, "AUTH_TOKEN_INVALID", "{:?}", token);
+        }
+
+        // Within the default 60 second leeway
+        assert!(gate.validate(&reader().expires_in(Duration::seconds(-10)).headers()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_accepts_previous_secrets_during_rotation() {
+        let gate = AuthGate::new(Secret::new("new-secret".to_string()), vec![UserScopes::read()])
+            .with_previous_secrets(vec![Secret::new("old-secret".to_string())]);
+
+        for secret in ["new-secret", "old-secret"] {
+            let result = gate.validate(&reader().signed_with(secret).headers()).await;
+            assert!(result.is_ok(), "token signed with {}", secret);
+        }
+
+        let result = gate.validate(&reader().signed_with("retired-secret").headers()).await;
+        assert_eq!(result.error.as_ref().unwrap().code, "AUTH_TOKEN_INVALID");
+    }
+
+    #[tokio::test]
+    async fn test_token_cache_holds_verified_tokens_only() {
+        let cache = Arc::new(TokenCache::new(16));
+        let gate = gate(vec![UserScopes::read()]).with_token_cache(cache.clone());
+        let token = reader().headers();
+
+        for _ in 0..3 {
+            assert_eq!(gate.validate(&token).await.data.unwrap().user_id, "user-123");
+        }
+        assert_eq!(cache.len(), 1);
+
+        let result = gate.validate(&reader().signed_with("forged").headers()).await;
+        assert_eq!(result.error.unwrap().code, "AUTH_TOKEN_INVALID");
+        assert_eq!(cache.len(), 1);
+
+        // Scopes are still checked on every request, cached or not
+        let result = AuthGate::new(Secret::new(TEST_JWT_SECRET.to_string()), vec![UserScopes::update()])
+            .with_token_cache(cache.clone())
+            .validate(&token)
+            .await;
+        assert_eq!(result.error.unwrap().code, "AUTH_INSUFFICIENT_SCOPES");
+    }
+
+    #[tokio::test]
+    async fn test_admin_role_overrides_admin_only_scopes() {
+        let gate = gate(vec![UserScopes::delete()]);
+
+        assert!(gate.validate(&TokenBuilder::new().roles(&["admin"]).headers()).await.is_ok());
+        let result = gate.validate(&TokenBuilder::new().scopes(&["users:read"]).headers()).await;
+        assert_eq!(result.error.unwrap().code, "AUTH_INSUFFICIENT_SCOPES");
+    }
+
+    #[tokio::test]
+    async fn test_tenant_comes_from_the_configured_claim() {
+        let registry = TenantRegistry::from_settings(&TenancySettings {
+            claim: "org".to_string(),
+            default_tenant: None,
+            tenants: vec![TenantOverride {
+                id: "globex".to_string(),
+                suspended: true,
+                max_page_size: None,
+            }],
+        });
+        let gate = gate(vec![]).with_tenants(Arc::new(registry));
+        let token = TokenBuilder::new().without_claim("tenant_id");
+
+        let result = gate.validate(&token.clone().claim("org", "acme").headers()).await;
+        assert_eq!(result.data.unwrap().tenant_id.as_str(), "acme");
+
+        for (token, code) in [
+            (token.clone(), "AUTH_TENANT_MISSING"),
+            (token.clone().tenant("acme"), "AUTH_TENANT_MISSING"),
+            (token.clone().claim("org", "../acme"), "AUTH_TENANT_MISSING"),
+            (token.clone().claim("org", 42), "AUTH_TENANT_MISSING"),
+            (token.claim("org", "globex"), "TENANT_SUSPENDED"),
+        ] {
+            let result = gate.validate(&token.headers()).await;
+            assert_eq!(result.error.unwrap().code, code);
+        }
+    }
+
     #[tokio::test]
     async fn test_auth_gate_missing_token() {
-        let gate = AuthGate::new("secret".to_string(), vec![]);
+        let gate = AuthGate::new(Secret::new("secret".to_string()), vec![]);
         let headers = HeaderMap::new();
         
         let result = gate.validate(&headers).await;
@@ -268,16 +428,20 @@ mod tests {
 
     #[test]
     fn test_extract_bearer_token() {
-        let gate = AuthGate::new("secret".to_string(), vec![]);
+        let gate = AuthGate::new(Secret::new("secret".to_string()), vec![]);
         let headers = create_test_headers("test_token");
         
         let token = gate.extract_bearer_token(&headers);
         assert_eq!(token, Some("test_token".to_string()));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("bearer test_token"));
+        assert_eq!(gate.extract_bearer_token(&headers), Some("test_token".to_string()));
     }
 
     #[test]
     fn test_validate_scopes() {
-        let gate = AuthGate::new("secret".to_string(), vec![UserScopes::create()]);
+        let gate = AuthGate::new(Secret::new("secret".to_string()), vec![UserScopes::create()]);
         let user_scopes = vec!["users:create".to_string()];
         let user_roles = vec![];
         let required = vec![UserScopes::create()];
@@ -286,9 +450,24 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_auth_context_has_scope() {
+        let context = AuthContext {
+            user_id: "user-123".to_string(),
+            tenant_id: TenantId::parse("acme").unwrap(),
+            scopes: vec!["users:*".to_string()],
+            email: None,
+            roles: Some(vec!["admin".to_string()]),
+        };
+
+        assert!(context.has_scope(&UserScopes::read()));
+        assert!(context.has_scope(&UserScopes::delete()));
+        assert!(!AuthContext { scopes: vec![], roles: None, ..context }.has_scope(&UserScopes::read()));
+    }
+
     #[test]
     fn test_validate_scopes_missing() {
-        let gate = AuthGate::new("secret".to_string(), vec![UserScopes::create()]);
+        let gate = AuthGate::new(Secret::new("secret".to_string()), vec![UserScopes::create()]);
         let user_scopes = vec!["users:read".to_string()];
         let user_roles = vec![];
         let required = vec![UserScopes::create()];
@@ -297,4 +476,83 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "users:create");
     }
-}
\ No newline at end of file
+
+    fn segment() -> impl Strategy<Value = String> {
+        "[a-z]{1,8}"
+    }
+
+    fn scope(resource: &str, action: &str, admin_only: bool) -> SecurityScope {
+        SecurityScope {
+            resource: resource.to_string(),
+            action: action.to_string(),
+            constraints: admin_only.then_some(ScopeConstraints {
+                owned_only: false,
+                department_only: false,
+                admin_only: true,
+            }),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_resource_wildcard_covers_every_action(
+            resource in segment(),
+            action in segment(),
+            admin_only in any::<bool>(),
+        ) {
+            let granted = vec![format!("{}:*", resource)];
+            prop_assert!(scope_granted(&granted, &[], &scope(&resource, &action, admin_only)));
+        }
+
+        #[test]
+        fn prop_global_wildcard_covers_everything(
+            resource in segment(),
+            action in segment(),
+            admin_only in any::<bool>(),
+            others in proptest::collection::vec("[a-z*]{1,8}:[a-z*]{1,8}", 0..5),
+        ) {
+            let mut granted = others;
+            granted.push("*:*".to_string());
+            prop_assert!(scope_granted(&granted, &[], &scope(&resource, &action, admin_only)));
+        }
+
+        #[test]
+        fn prop_scope_order_does_not_matter(
+            granted in proptest::collection::vec("[a-c*]{1,2}:[a-c*]{1,2}", 0..6),
+            roles in proptest::collection::vec(prop_oneof![Just("admin".to_string()), segment()], 0..3),
+            resource in "[a-c]{1,2}",
+            action in "[a-c]{1,2}",
+            admin_only in any::<bool>(),
+            rotation in 0usize..6,
+        ) {
+            let required = scope(&resource, &action, admin_only);
+            let mut shuffled = granted.clone();
+            shuffled.reverse();
+            if !shuffled.is_empty() {
+                let len = shuffled.len();
+                shuffled.rotate_left(rotation % len);
+            }
+
+            prop_assert_eq!(
+                scope_granted(&granted, &roles, &required),
+                scope_granted(&shuffled, &roles, &required)
+            );
+        }
+
+        #[test]
+        fn prop_only_matching_scopes_grant(
+            granted in proptest::collection::vec("[a-c*]{1,2}:[a-c*]{1,2}", 0..6),
+            resource in "[a-c]{1,2}",
+            action in "[a-c]{1,2}",
+        ) {
+            let expected = granted.iter().any(|candidate| {
+                let (granted_resource, granted_action) = candidate.split_once(':').unwrap();
+                (granted_resource == resource || granted_resource == "*")
+                    && (granted_action == action || granted_action == "*")
+                    && (granted_resource != "*" || granted_action == "*")
+            });
+
+            prop_assert_eq!(scope_granted(&granted, &[], &scope(&resource, &action, false)), expected);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! BRIK v5 Credentials - Authorization header parsing and JWT decoding
+//!
+//! Depends on external crates only, so the fuzz targets in `fuzz/` compile
+//! this file on its own and exercise exactly what `AuthGate` runs.
+
+use jsonwebtoken::errors::{Error, ErrorKind};
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JwtClaims {
+    pub sub: Option<String>,
+    pub user_id: Option<String>,
+    pub email: Option<String>,
+    pub scopes: Option<Vec<String>>,
+    pub roles: Option<Vec<String>>,
+    pub exp: usize,
+    /// Holds the configurable tenant claim
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
+}
+
+/// Token of an `Authorization: Bearer <token>` value. The scheme is matched
+/// case-insensitively (RFC 7235 §2.1) and may be followed by several spaces;
+/// the token itself must be non-empty and free of whitespace.
+pub fn bearer_token(authorization: &str) -> Option<&str> {
+    let (scheme, rest) = authorization.split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return None;
+    }
+
+    let token = rest.trim_start_matches(' ').trim_end();
+    let well_formed = !token.is_empty() && !token.contains(|c: char| c.is_ascii_whitespace());
+    well_formed.then_some(token)
+}
+
+/// HS256 verification with keys and validation rules built once, rather
+/// than on every request. Expiry is checked against the time the caller
+/// passes in, not the system clock.
+pub struct TokenVerifier {
+    /// Primary secret first, then secrets still honoured during a rotation
+    keys: Vec<DecodingKey>,
+    validation: Validation,
+}
+
+impl TokenVerifier {
+    pub fn new<'a>(secrets: impl IntoIterator<Item = &'a [u8]>) -> Self {
+        let mut validation = Validation::new(Algorithm::HS256);
+        // `exp` is still required, but compared with `now` in `decode`
+        validation.validate_exp = false;
+
+        Self {
+            keys: secrets.into_iter().map(DecodingKey::from_secret).collect(),
+            validation,
+        }
+    }
+
+    /// Tries each key in order; only a signature mismatch moves on to the
+    /// next one, any other failure means the token itself is bad. `now` is
+    /// in seconds since the epoch; `exp` gets the usual 60 second leeway.
+    pub fn decode(&self, token: &str, now: u64) -> Result<JwtClaims, Error> {
+        let mut last_error = Error::from(ErrorKind::InvalidSignature);
+
+        for key in &self.keys {
+            match decode::<JwtClaims>(token, key, &self.validation) {
+                Ok(data) if data.claims.exp as u64 + self.validation.leeway < now => {
+                    return Err(Error::from(ErrorKind::ExpiredSignature));
+                }
+                Ok(data) => return Ok(data.claims),
+                Err(error) if matches!(error.kind(), ErrorKind::InvalidSignature) => last_error = error,
+                Err(error) => return Err(error),
+            }
+        }
+
+        Err(last_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_bearer_scheme_is_case_insensitive() {
+        for header in ["Bearer abc", "bearer abc", "BEARER abc", "bEaReR   abc", "Bearer abc "] {
+            assert_eq!(bearer_token(header), Some("abc"), "{:?}", header);
+        }
+        for header in ["Bearer", "Bearer ", "Bearerabc", "Basic abc", "Bearer a b", " Bearer abc", "Bearer\tabc"] {
+            assert_eq!(bearer_token(header), None, "{:?}", header);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_any_scheme_casing_yields_the_token(
+            casing in proptest::collection::vec(any::<bool>(), 6),
+            spaces in 1usize..4,
+            token in "[A-Za-z0-9._~+/-]{1,64}=*",
+        ) {
+            let scheme: String = "bearer"
+                .chars()
+                .zip(casing)
+                .map(|(c, upper)| if upper { c.to_ascii_uppercase() } else { c })
+                .collect();
+            let header = format!("{}{}{}", scheme, " ".repeat(spaces), token);
+
+            prop_assert_eq!(bearer_token(&header), Some(token.as_str()));
+        }
+
+        #[test]
+        fn prop_extracted_token_is_a_clean_substring(header in "\\PC{0,80}") {
+            if let Some(token) = bearer_token(&header) {
+                prop_assert!(!token.is_empty());
+                prop_assert!(!token.contains(|c: char| c.is_ascii_whitespace()));
+                prop_assert!(header.ends_with(token) || header.trim_end().ends_with(token));
+            }
+        }
+
+        #[test]
+        fn prop_garbage_tokens_are_rejected_without_panicking(token in "\\PC{0,200}") {
+            prop_assert!(TokenVerifier::new([b"secret".as_slice()]).decode(&token, 0).is_err());
+        }
+    }
+}
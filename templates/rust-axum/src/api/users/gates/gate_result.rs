@@ -1,5 +1,8 @@
 //! BRIK v5 Gate Result Type for Rust
 
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::time::clock::{Clock, SystemClock};
+use crate::shared::types::result::BrikResult;
 use std::time::{Duration, Instant};
 use thiserror::Error;
 
@@ -31,12 +34,16 @@ impl<T> GateResult<T> {
         Self {
             is_success: false,
             data: None,
-            error: Some(GateError {
-                gate: gate.to_string(),
-                code: code.to_string(),
-                message: message.to_string(),
-                http_status,
-            }),
+            error: Some(GateError::new(gate, code, message, http_status)),
+            duration,
+        }
+    }
+
+    pub fn rejected(error: GateError, duration: Option<Duration>) -> Self {
+        Self {
+            is_success: false,
+            data: None,
+            error: Some(error),
             duration,
         }
     }
@@ -59,6 +66,15 @@ impl<T> GateResult<T> {
     pub fn is_err(&self) -> bool {
         !self.is_success
     }
+
+    /// Converts into a `BrikResult` so handlers can propagate failures with `?`
+    pub fn into_result(self) -> BrikResult<T, GateError> {
+        match (self.data, self.error) {
+            (Some(data), _) => Ok(data),
+            (None, Some(error)) => Err(error),
+            (None, None) => Err(ErrorCode::GateResultEmpty.gate_error("Unknown")),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Error)]
@@ -68,6 +84,8 @@ pub struct GateError {
     pub code: String,
     pub message: String,
     pub http_status: u16,
+    /// Values interpolated into the localized message
+    pub params: Vec<(String, String)>,
 }
 
 impl GateError {
@@ -77,8 +95,14 @@ impl GateError {
             code: code.to_string(),
             message: message.to_string(),
             http_status,
+            params: Vec::new(),
         }
     }
+
+    pub fn with_params(mut self, params: &[(&str, &str)]) -> Self {
+        self.params = params.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        self
+    }
 }
 
 /// Trait for request gates
@@ -90,29 +114,40 @@ where
 {
     fn name(&self) -> &'static str;
     
-    async fn validate(&self, input: TInput) -> GateResult<TOutput>;
+    async fn validate(&self, input: TInput) -> GateResult<TOutput>
+    where
+        TInput: 'async_trait;
 }
 
 /// Helper to time gate operations
-pub struct GateTimer {
+pub struct GateTimer<'a> {
+    clock: &'a dyn Clock,
     start: Instant,
 }
 
-impl GateTimer {
+impl GateTimer<'static> {
     pub fn start() -> Self {
+        GateTimer::start_on(&SystemClock)
+    }
+}
+
+impl<'a> GateTimer<'a> {
+    pub fn start_on(clock: &'a dyn Clock) -> Self {
         Self {
-            start: Instant::now(),
+            clock,
+            start: clock.instant(),
         }
     }
 
     pub fn elapsed(&self) -> Duration {
-        self.start.elapsed()
+        self.clock.instant().saturating_duration_since(self.start)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::time::clock::ManualClock;
 
     #[test]
     fn test_gate_result_success() {
@@ -140,6 +175,16 @@ mod tests {
         assert_eq!(result.error.as_ref().unwrap().http_status, 400);
     }
 
+    #[test]
+    fn test_gate_result_into_result() {
+        let success = GateResult::success(42, None).into_result();
+        let failure: BrikResult<i32, GateError> =
+            GateResult::failure("TestGate", "TEST_FAILED", "Test failure message", 400, None).into_result();
+
+        assert_eq!(success.unwrap(), 42);
+        assert_eq!(failure.unwrap_err().code, "TEST_FAILED");
+    }
+
     #[test]
     fn test_gate_timer() {
         let timer = GateTimer::start();
@@ -149,6 +194,16 @@ mod tests {
         assert!(duration >= Duration::from_millis(10));
     }
 
+    #[test]
+    fn test_gate_timer_reads_the_given_clock() {
+        let clock = ManualClock::starting_now();
+        let timer = GateTimer::start_on(clock.as_ref());
+
+        clock.advance(Duration::from_millis(250));
+
+        assert_eq!(timer.elapsed(), Duration::from_millis(250));
+    }
+
     #[test]
     fn test_gate_error_display() {
         let error = GateError::new("TestGate", "TEST_CODE", "Test message", 400);
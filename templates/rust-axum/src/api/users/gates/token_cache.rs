@@ -0,0 +1,159 @@
+//! BRIK v5 Token Cache - Verified tokens mapped to their claims
+//!
+//! A bounded LRU keyed by the SHA-256 of the token, so raw bearer tokens are
+//! never kept in memory. An entry is served only while the token's `exp` is
+//! in the future; after that the token goes through full verification (and
+//! its leeway) again. Like `credentials`, it depends on external crates
+//! only, so the benches compile it on its own.
+
+use super::credentials::JwtClaims;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+type TokenHash = [u8; 32];
+
+struct Entry {
+    claims: Arc<JwtClaims>,
+    expires_at: u64,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Lru {
+    entries: HashMap<TokenHash, Entry>,
+    /// Recency index: `last_used` tick to token hash, oldest first
+    recency: BTreeMap<u64, TokenHash>,
+    tick: u64,
+}
+
+impl Lru {
+    fn touch(&mut self, hash: TokenHash) {
+        self.tick += 1;
+        if let Some(entry) = self.entries.get_mut(&hash) {
+            self.recency.remove(&entry.last_used);
+            entry.last_used = self.tick;
+            self.recency.insert(self.tick, hash);
+        }
+    }
+
+    fn remove(&mut self, hash: &TokenHash) {
+        if let Some(entry) = self.entries.remove(hash) {
+            self.recency.remove(&entry.last_used);
+        }
+    }
+}
+
+pub struct TokenCache {
+    capacity: usize,
+    lru: Mutex<Lru>,
+}
+
+impl TokenCache {
+    /// Holds at most `capacity` tokens, evicting the least recently used
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            lru: Mutex::new(Lru::default()),
+        }
+    }
+
+    /// Claims of a previously verified token that has not expired at `now`
+    /// (seconds since the epoch)
+    pub fn get(&self, token: &str, now: u64) -> Option<Arc<JwtClaims>> {
+        let hash = hash(token);
+        let mut lru = self.lru.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let expires_at = lru.entries.get(&hash)?.expires_at;
+        if expires_at <= now {
+            lru.remove(&hash);
+            return None;
+        }
+
+        lru.touch(hash);
+        lru.entries.get(&hash).map(|entry| entry.claims.clone())
+    }
+
+    /// Only call with claims from a successful verification
+    pub fn insert(&self, token: &str, claims: Arc<JwtClaims>) {
+        let hash = hash(token);
+        let expires_at = claims.exp as u64;
+        let mut lru = self.lru.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        lru.remove(&hash);
+        while lru.entries.len() >= self.capacity {
+            let Some((_, oldest)) = lru.recency.pop_first() else { break };
+            lru.entries.remove(&oldest);
+        }
+
+        let entry = Entry { claims, expires_at, last_used: 0 };
+        lru.entries.insert(hash, entry);
+        lru.touch(hash);
+    }
+
+    pub fn len(&self) -> usize {
+        self.lru.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn hash(token: &str) -> TokenHash {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(sub: &str, exp: usize) -> Arc<JwtClaims> {
+        Arc::new(JwtClaims {
+            sub: Some(sub.to_string()),
+            user_id: None,
+            email: None,
+            scopes: None,
+            roles: None,
+            exp,
+            other: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_entries_expire_with_the_token() {
+        let cache = TokenCache::new(8);
+        cache.insert("token", claims("user-1", 1_000));
+
+        assert_eq!(cache.get("token", 999).unwrap().sub.as_deref(), Some("user-1"));
+        assert!(cache.get("other-token", 999).is_none());
+        assert!(cache.get("token", 1_000).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = TokenCache::new(2);
+        cache.insert("a", claims("a", 1_000));
+        cache.insert("b", claims("b", 1_000));
+        assert!(cache.get("a", 0).is_some());
+
+        cache.insert("c", claims("c", 1_000));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("a", 0).is_some());
+        assert!(cache.get("b", 0).is_none());
+        assert!(cache.get("c", 0).is_some());
+    }
+
+    #[test]
+    fn test_reinserting_a_token_replaces_its_entry() {
+        let cache = TokenCache::new(2);
+        cache.insert("a", claims("old", 1_000));
+        cache.insert("a", claims("new", 1_000));
+        cache.insert("b", claims("b", 1_000));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("a", 0).unwrap().sub.as_deref(), Some("new"));
+    }
+}
@@ -0,0 +1,152 @@
+//! BRIK v5 Create User Handler - POST /users
+
+use crate::api::app_state::AppState;
+use crate::api::users::domain::entities::user::{User, UserCreationData};
+use crate::api::users::domain::errors::domain_error::DomainError;
+use crate::api::users::dto::user_dto::{CreateUserMetadata, CreateUserRequest, CreateUserResponse};
+use crate::api::users::gates::gate_result::{GateError, RequestGate};
+use crate::shared::errors::api_error::ApiError;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::error_responses::{
+    BadRequest, Conflict, Forbidden, InternalServerError, PayloadTooLarge, ServiceUnavailable,
+    TooManyRequests, Unauthorized,
+};
+use crate::shared::http::hardening::request_too_large;
+use crate::shared::observability::correlation::{correlation_id_from, CORRELATION_ID_HEADER};
+use crate::brik_log;
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+#[utoipa::path(
+    post,
+    path = "/users",
+    operation_id = "create_user",
+    tag = "Users",
+    security(("bearerAuth" = [])),
+    params(
+        ("x-correlation-id" = Option<String>, Header, description = "Unique request correlation ID for tracing"),
+        ("idempotency-key" = String, Header, description = "Unique key to ensure idempotent operations"),
+    ),
+    request_body = CreateUserRequest,
+    responses(
+        (status = 201, description = "User created successfully", body = CreateUserResponse,
+            headers(("x-correlation-id" = String, description = "Request correlation ID"))),
+        (status = 400, response = BadRequest),
+        (status = 401, response = Unauthorized),
+        (status = 403, response = Forbidden),
+        (status = 409, response = Conflict),
+        (status = 413, response = PayloadTooLarge),
+        (status = 429, response = TooManyRequests),
+        (status = 500, response = InternalServerError),
+        (status = 503, response = ServiceUnavailable),
+    )
+)]
+pub async fn create_user(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Result<Json<CreateUserRequest>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let correlation_id = correlation_id_from(&headers);
+
+    let auth = state.create_user_gate.validate(&headers).await.into_result()?;
+    let idempotency_key = idempotency_key_from(&headers)?;
+    let Json(request) = body.map_err(|rejection| match rejection.status() {
+        StatusCode::PAYLOAD_TOO_LARGE => request_too_large(),
+        _ => ApiError::from(ErrorCode::ValidationFailed.gate_error_with("SchemaGate", &rejection.body_text())),
+    })?;
+
+    let user = User::create(
+        auth.tenant_id.clone(),
+        UserCreationData::from(request),
+        state.clock.as_ref(),
+        state.ids.as_ref(),
+    )?;
+
+    if state.user_repository.get_by_email(&user.tenant_id, &user.email).await?.is_some() {
+        return Err(user_already_exists().into());
+    }
+
+    state
+        .user_repository
+        .create(&user)
+        .await
+        .map_err(|error| {
+            // Lost a race with a concurrent request for the same email
+            if error.has_code(ErrorCode::UniqueViolation) {
+                ApiError::from(user_already_exists())
+            } else {
+                ApiError::from(error)
+            }
+        })?;
+
+    brik_log!(
+        INFO,
+        "User created",
+        correlation_id = correlation_id.as_str(),
+        user_id = auth.user_id.as_str(),
+        endpoint = "POST /users",
+        created_user_id = %user.id
+    );
+
+    let response = CreateUserResponse {
+        user: user.into(),
+        metadata: CreateUserMetadata {
+            correlation_id: correlation_id.clone(),
+            idempotency_key,
+            cached: false,
+        },
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        [(CORRELATION_ID_HEADER, correlation_id)],
+        Json(response),
+    ))
+}
+
+fn idempotency_key_from(headers: &HeaderMap) -> Result<String, GateError> {
+    headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|key| {
+            !key.is_empty()
+                && key.len() <= 255
+                && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+        .map(str::to_string)
+        .ok_or_else(|| ErrorCode::IdempotencyKeyInvalid.gate_error("IdempotencyGate"))
+}
+
+fn user_already_exists() -> DomainError {
+    ErrorCode::UserAlreadyExists.domain_error()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_idempotency_key_required() {
+        let error = idempotency_key_from(&HeaderMap::new()).unwrap_err();
+
+        assert_eq!(error.code, "IDEMPOTENCY_KEY_INVALID");
+        assert_eq!(error.http_status, 400);
+    }
+
+    #[test]
+    fn test_idempotency_key_pattern() {
+        let mut headers = HeaderMap::new();
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static("user-creation-2024-01-15-abc123"));
+        assert_eq!(idempotency_key_from(&headers).unwrap(), "user-creation-2024-01-15-abc123");
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static("not valid!"));
+        assert!(idempotency_key_from(&headers).is_err());
+    }
+}
@@ -0,0 +1,68 @@
+//! BRIK v5 Get User Handler - GET /users/{id}
+
+use crate::api::app_state::AppState;
+use crate::api::users::dto::user_dto::{GetUserResponse, ResponseMetadata};
+use crate::api::users::gates::gate_result::RequestGate;
+use crate::shared::errors::api_error::ApiError;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::error_responses::{
+    BadRequest, Forbidden, InternalServerError, NotFound, ServiceUnavailable, TooManyRequests,
+    Unauthorized,
+};
+use crate::shared::observability::correlation::{correlation_id_from, CORRELATION_ID_HEADER};
+use axum::{
+    extract::{rejection::PathRejection, Path, State},
+    http::HeaderMap,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/users/{id}",
+    operation_id = "get_user_by_id",
+    tag = "Users",
+    security(("bearerAuth" = [])),
+    params(
+        ("x-correlation-id" = Option<String>, Header, description = "Unique request correlation ID for tracing"),
+        ("id" = Uuid, Path, description = "User ID"),
+    ),
+    responses(
+        (status = 200, description = "User retrieved successfully", body = GetUserResponse),
+        (status = 400, response = BadRequest),
+        (status = 401, response = Unauthorized),
+        (status = 403, response = Forbidden),
+        (status = 404, response = NotFound),
+        (status = 429, response = TooManyRequests),
+        (status = 500, response = InternalServerError),
+        (status = 503, response = ServiceUnavailable),
+    )
+)]
+pub async fn get_user_by_id(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    id: Result<Path<Uuid>, PathRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let correlation_id = correlation_id_from(&headers);
+
+    let auth = state.read_user_gate.validate(&headers).await.into_result()?;
+    let Path(id) = id.map_err(|_| {
+        ErrorCode::ValidationFailed.gate_error_with("SchemaGate", "User ID must be a valid UUID")
+    })?;
+
+    let user = state
+        .user_repository
+        .get_by_id(&auth.tenant_id, id)
+        .await?
+        .ok_or_else(|| ErrorCode::UserNotFound.domain_error())?;
+
+    let response = GetUserResponse {
+        user: user.into(),
+        metadata: ResponseMetadata {
+            correlation_id: correlation_id.clone(),
+        },
+    };
+
+    Ok(([(CORRELATION_ID_HEADER, correlation_id)], Json(response)))
+}
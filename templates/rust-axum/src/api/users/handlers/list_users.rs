@@ -0,0 +1,72 @@
+//! BRIK v5 List Users Handler - GET /users
+
+use crate::api::app_state::AppState;
+use crate::api::users::domain::queries::list_users_query::ListUsersQuery;
+use crate::api::users::dto::user_dto::{ResponseMetadata, UserPage};
+use crate::api::users::gates::gate_result::RequestGate;
+use crate::shared::errors::api_error::ApiError;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::error_responses::{
+    BadRequest, Forbidden, InternalServerError, ServiceUnavailable, TooManyRequests, Unauthorized,
+};
+use crate::shared::observability::correlation::{correlation_id_from, CORRELATION_ID_HEADER};
+use axum::{
+    extract::{rejection::QueryRejection, Query, State},
+    http::HeaderMap,
+    response::IntoResponse,
+    Json,
+};
+use std::collections::HashMap;
+
+#[utoipa::path(
+    get,
+    path = "/users",
+    operation_id = "list_users",
+    tag = "Users",
+    security(("bearerAuth" = [])),
+    params(
+        ("x-correlation-id" = Option<String>, Header, description = "Unique request correlation ID for tracing"),
+        ("limit" = Option<u32>, Query, description = "Page size (1-100, default 20; a tenant may be capped lower)", minimum = 1, maximum = 100),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from `page.next_cursor` of the previous page"),
+        ("sort" = Option<String>, Query, description = "One of created_at, name, email, age; prefix with '-' for descending (default -created_at)"),
+        ("email_domain" = Option<String>, Query, description = "Only users whose email belongs to this domain"),
+        ("min_age" = Option<i32>, Query, description = "Minimum age (inclusive)", minimum = 13, maximum = 150),
+        ("max_age" = Option<i32>, Query, description = "Maximum age (inclusive)", minimum = 13, maximum = 150),
+        ("created_after" = Option<chrono::DateTime<chrono::Utc>>, Query, description = "Created at or after this instant"),
+        ("created_before" = Option<chrono::DateTime<chrono::Utc>>, Query, description = "Created strictly before this instant"),
+    ),
+    responses(
+        (status = 200, description = "Page of users", body = UserPage),
+        (status = 400, response = BadRequest),
+        (status = 401, response = Unauthorized),
+        (status = 403, response = Forbidden),
+        (status = 429, response = TooManyRequests),
+        (status = 500, response = InternalServerError),
+        (status = 503, response = ServiceUnavailable),
+    )
+)]
+pub async fn list_users(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    params: Result<Query<HashMap<String, String>>, QueryRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let correlation_id = correlation_id_from(&headers);
+
+    let auth = state.read_user_gate.validate(&headers).await.into_result()?;
+    let Query(params) = params.map_err(|_| {
+        ErrorCode::InvalidQuery.gate_error_with("QueryGate", "Query string is malformed")
+    })?;
+    let mut query = ListUsersQuery::from_params(&params)?;
+    query.page.limit = query.page.limit.min(state.tenants.config(&auth.tenant_id).max_page_size);
+
+    let users = state.user_repository.list(&auth.tenant_id, &query).await?;
+
+    let response = UserPage::new(
+        query.into_page(users),
+        ResponseMetadata {
+            correlation_id: correlation_id.clone(),
+        },
+    );
+
+    Ok(([(CORRELATION_ID_HEADER, correlation_id)], Json(response)))
+}
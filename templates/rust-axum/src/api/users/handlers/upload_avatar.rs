@@ -0,0 +1,190 @@
+//! BRIK v5 Upload Avatar Handler - PUT /users/{id}/avatar
+//!
+//! Users change their own avatar; changing someone else's takes the
+//! `users:admin` scope or the admin role. The image type is sniffed from
+//! its bytes and metadata is stripped before anything is stored; the
+//! client's content type and file name are ignored.
+
+use crate::api::app_state::AppState;
+use crate::api::users::dto::user_dto::{GetUserResponse, ResponseMetadata};
+use crate::api::users::gates::auth_gate::UserScopes;
+use crate::api::users::gates::gate_result::{GateError, RequestGate};
+use crate::shared::errors::api_error::ApiError;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::error_responses::{
+    BadRequest, Conflict, Forbidden, InternalServerError, NotFound, PayloadTooLarge,
+    ServiceUnavailable, TooManyRequests, Unauthorized, UnsupportedMediaType,
+};
+use crate::shared::media::image_sanitizer::{sanitize_image, ImageError};
+use crate::shared::observability::correlation::{correlation_id_from, CORRELATION_ID_HEADER};
+use crate::brik_log;
+use axum::{
+    extract::{
+        multipart::{MultipartError, MultipartRejection},
+        rejection::PathRejection,
+        Multipart, Path, State,
+    },
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+pub const AVATAR_FIELD: &str = "avatar";
+/// Room for multipart framing around the avatar
+const MULTIPART_OVERHEAD_BYTES: usize = 16 * 1024;
+
+const GATE_NAME: &str = "UploadGate";
+
+#[utoipa::path(
+    put,
+    path = "/users/{id}/avatar",
+    operation_id = "upload_user_avatar",
+    tag = "Users",
+    security(("bearerAuth" = [])),
+    params(
+        ("x-correlation-id" = Option<String>, Header, description = "Unique request correlation ID for tracing"),
+        ("id" = Uuid, Path, description = "User ID"),
+    ),
+    request_body(content = AvatarUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Avatar stored and profile updated", body = GetUserResponse),
+        (status = 400, response = BadRequest),
+        (status = 401, response = Unauthorized),
+        (status = 403, response = Forbidden),
+        (status = 404, response = NotFound),
+        (status = 409, response = Conflict),
+        (status = 413, response = PayloadTooLarge),
+        (status = 415, response = UnsupportedMediaType),
+        (status = 429, response = TooManyRequests),
+        (status = 500, response = InternalServerError),
+        (status = 503, response = ServiceUnavailable),
+    )
+)]
+pub async fn upload_avatar(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    id: Result<Path<Uuid>, PathRejection>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let correlation_id = correlation_id_from(&headers);
+
+    let auth = state.update_user_gate.validate(&headers).await.into_result()?;
+    let Path(id) = id.map_err(|_| {
+        ErrorCode::ValidationFailed.gate_error_with("SchemaGate", "User ID must be a valid UUID")
+    })?;
+    if auth.user_id != id.to_string() && !auth.has_scope(&UserScopes::admin()) {
+        return Err(ErrorCode::AvatarNotOwned.gate_error(GATE_NAME).into());
+    }
+    let multipart = multipart.map_err(|rejection| {
+        ErrorCode::AvatarMissing.gate_error_with(GATE_NAME, &rejection.body_text())
+    })?;
+
+    let upload = read_avatar(multipart, state.max_avatar_bytes).await?;
+    let (format, image) = sanitize_image(&upload).map_err(|error| match error {
+        ImageError::Unsupported => ErrorCode::AvatarUnsupportedType.gate_error_with(GATE_NAME, &error.to_string()),
+        ImageError::Malformed { .. } => ErrorCode::AvatarInvalidImage.gate_error_with(GATE_NAME, &error.to_string()),
+    })?;
+
+    let user = state
+        .user_repository
+        .get_by_id(&auth.tenant_id, id)
+        .await?
+        .ok_or_else(|| ErrorCode::UserNotFound.domain_error())?;
+
+    let key = format!("avatars/{}/{}/{}.{}", auth.tenant_id, id, Uuid::new_v4().simple(), format.extension());
+    let avatar_url = state.blob_storage.put(&key, format.mime_type(), image).await?;
+
+    let updated = match user.change_avatar(avatar_url, state.clock.as_ref()) {
+        Ok(updated) => updated,
+        Err(error) => {
+            discard_blob(&state, &key, &correlation_id).await;
+            return Err(error.into());
+        }
+    };
+    if let Err(error) = state.user_repository.update(&updated).await {
+        discard_blob(&state, &key, &correlation_id).await;
+        return Err(if error.has_code(ErrorCode::VersionConflict) {
+            ErrorCode::UserVersionConflict
+                .domain_error_with("User was modified by another request, retry the upload")
+                .into()
+        } else {
+            error.into()
+        });
+    }
+
+    // The previous avatar is only removed once nothing references it anymore
+    if let Some(previous) = user.profile.avatar_url.as_deref().and_then(|url| state.blob_storage.key_for_url(url)) {
+        discard_blob(&state, &previous, &correlation_id).await;
+    }
+
+    brik_log!(
+        INFO,
+        "User avatar updated",
+        correlation_id = correlation_id.as_str(),
+        user_id = auth.user_id.as_str(),
+        endpoint = "PUT /users/{id}/avatar",
+        updated_user_id = %id,
+        content_type = format.mime_type()
+    );
+
+    let response = GetUserResponse {
+        user: updated.into(),
+        metadata: ResponseMetadata {
+            correlation_id: correlation_id.clone(),
+        },
+    };
+
+    Ok((StatusCode::OK, [(CORRELATION_ID_HEADER, correlation_id)], Json(response)))
+}
+
+/// Request body limit of the upload route for `http.max_avatar_bytes`
+pub fn avatar_body_limit(max_avatar_bytes: usize) -> usize {
+    max_avatar_bytes + MULTIPART_OVERHEAD_BYTES
+}
+
+/// Reads the `avatar` field, failing as soon as it exceeds `max_bytes`
+async fn read_avatar(mut multipart: Multipart, max_bytes: usize) -> Result<Vec<u8>, GateError> {
+    let multipart_error = |error: MultipartError| {
+        if error.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            too_large(max_bytes)
+        } else {
+            ErrorCode::AvatarMissing.gate_error_with(GATE_NAME, &error.body_text())
+        }
+    };
+
+    while let Some(mut field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some(AVATAR_FIELD) {
+            continue;
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+            if bytes.len() + chunk.len() > max_bytes {
+                return Err(too_large(max_bytes));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        return Ok(bytes);
+    }
+
+    Err(ErrorCode::AvatarMissing.gate_error_with(GATE_NAME, &format!("Multipart field '{}' is required", AVATAR_FIELD)))
+}
+
+fn too_large(max_bytes: usize) -> GateError {
+    ErrorCode::AvatarTooLarge.gate_error_with_params(GATE_NAME, &[("max_bytes", &max_bytes.to_string())])
+}
+
+/// Best-effort cleanup; an orphaned file is preferable to a failed request
+async fn discard_blob(state: &AppState, key: &str, correlation_id: &str) {
+    if let Err(error) = state.blob_storage.delete(key).await {
+        brik_log!(
+            WARN,
+            "Failed to delete avatar blob",
+            correlation_id = correlation_id,
+            port = error.port.as_str(),
+            key = key,
+            error = error.message.as_str()
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! BRIK v5 User Events Handler - GET /users/events (WebSocket)
+//!
+//! Pushes user events as JSON text frames. Any valid token may subscribe;
+//! events about other users are only delivered with the `users:read` scope,
+//! and never across tenants.
+//! Browsers cannot set headers on WebSocket handshakes, so the token may also
+//! be passed as `access_token` in the query string.
+//!
+//! - Heartbeat: the server pings every `heartbeat_interval` and closes the
+//!   socket when the client has been silent for `idle_timeout`.
+//! - Backpressure: a consumer that falls behind the broadcast buffer, or
+//!   whose frames cannot be written within `send_timeout`, is disconnected
+//!   (close code 1013) instead of slowing down everyone else.
+//! - Resume: reconnect with `last_event_id` (or the `Last-Event-ID` header)
+//!   to replay retained events; a `resync_required` message is sent first
+//!   when the history no longer covers that id.
+
+use crate::api::app_state::AppState;
+use crate::api::users::domain::events::user_event::UserEvent;
+use crate::api::users::dto::user_event_dto::UserEventMessage;
+use crate::api::users::gates::auth_gate::{AuthContext, UserScopes};
+use crate::api::users::gates::gate_result::RequestGate;
+use crate::shared::errors::api_error::ApiError;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::error_responses::{
+    BadRequest, InternalServerError, TooManyRequests, Unauthorized,
+};
+use crate::shared::events::event_stream::{Sequenced, Subscription};
+use axum::{
+    extract::{
+        rejection::QueryRejection,
+        ws::{close_code, rejection::WebSocketUpgradeRejection, CloseFrame, Message, WebSocketUpgrade},
+        Query, State,
+    },
+    http::{HeaderMap, HeaderValue},
+    response::Response,
+};
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::Instant;
+
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UserEventsParams {
+    pub last_event_id: Option<u64>,
+    pub access_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SessionConfig {
+    pub heartbeat_interval: Duration,
+    pub idle_timeout: Duration,
+    pub send_timeout: Duration,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            heartbeat_interval: Duration::from_secs(30),
+            idle_timeout: Duration::from_secs(75),
+            send_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/events",
+    operation_id = "stream_user_events",
+    tag = "Users",
+    security(("bearerAuth" = [])),
+    params(
+        ("last_event_id" = Option<u64>, Query, description = "Resume after this event id"),
+        ("access_token" = Option<String>, Query, description = "JWT for clients that cannot set the Authorization header"),
+        ("last-event-id" = Option<u64>, Header, description = "Resume after this event id (query parameter wins)"),
+    ),
+    responses(
+        (status = 101, description = "Switching to the WebSocket protocol; frames carry user event messages"),
+        (status = 400, response = BadRequest),
+        (status = 401, response = Unauthorized),
+        (status = 429, response = TooManyRequests),
+        (status = 500, response = InternalServerError),
+    )
+)]
+pub async fn user_events(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    params: Result<Query<UserEventsParams>, QueryRejection>,
+    upgrade: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+) -> Result<Response, ApiError> {
+    let Query(params) = params.map_err(|rejection| {
+        ErrorCode::InvalidQuery.gate_error_with("QueryGate", &rejection.body_text())
+    })?;
+
+    let auth = state
+        .user_events_gate
+        .validate(&with_query_token(&headers, params.access_token.as_deref()))
+        .await
+        .into_result()?;
+    let upgrade = upgrade.map_err(|rejection| {
+        ErrorCode::WebsocketUpgradeRequired.gate_error_with("SchemaGate", &rejection.body_text())
+    })?;
+
+    let last_event_id = params.last_event_id.or_else(|| {
+        headers
+            .get(LAST_EVENT_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+    });
+    // Subscribe before the handshake completes so nothing published in
+    // between is lost
+    let subscription = state.user_events.subscribe(last_event_id);
+
+    Ok(upgrade.on_upgrade(move |socket| {
+        let (sink, incoming) = socket.split();
+        run_session(sink, incoming, auth, subscription, last_event_id, SessionConfig::default())
+    }))
+}
+
+fn with_query_token(headers: &HeaderMap, access_token: Option<&str>) -> HeaderMap {
+    let mut headers = headers.clone();
+    if !headers.contains_key("authorization") {
+        if let Some(value) = access_token.and_then(|token| HeaderValue::from_str(&format!("Bearer {}", token)).ok()) {
+            headers.insert("authorization", value);
+        }
+    }
+    headers
+}
+
+/// Callers always see events about themselves; everything else in their
+/// tenant needs `users:read`
+pub fn visible_to(auth: &AuthContext, event: &UserEvent) -> bool {
+    event.tenant_id == auth.tenant_id
+        && (event.user_id.to_string() == auth.user_id || auth.has_scope(&UserScopes::read()))
+}
+
+/// Drives one subscriber until either side goes away
+pub async fn run_session<Si, St, E>(
+    mut sink: Si,
+    mut incoming: St,
+    auth: AuthContext,
+    mut subscription: Subscription<UserEvent>,
+    last_event_id: Option<u64>,
+    config: SessionConfig,
+) where
+    Si: Sink<Message> + Unpin,
+    St: Stream<Item = Result<Message, E>> + Unpin,
+{
+    if subscription.missed {
+        let resync = UserEventMessage::ResyncRequired {
+            last_event_id: last_event_id.unwrap_or_default(),
+        };
+        if !send_json(&mut sink, &resync, config.send_timeout).await {
+            return;
+        }
+    }
+
+    for event in std::mem::take(&mut subscription.replay) {
+        if !deliver(&mut sink, &auth, &event, config.send_timeout).await {
+            return;
+        }
+    }
+
+    let mut heartbeat = tokio::time::interval_at(Instant::now() + config.heartbeat_interval, config.heartbeat_interval);
+    let mut last_seen = Instant::now();
+
+    loop {
+        tokio::select! {
+            _ = heartbeat.tick() => {
+                if last_seen.elapsed() >= config.idle_timeout {
+                    close(&mut sink, close_code::AWAY, "heartbeat timeout").await;
+                    return;
+                }
+                if !send(&mut sink, Message::Ping(Vec::new()), config.send_timeout).await {
+                    return;
+                }
+            }
+            message = incoming.next() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                // Pongs and anything else the client sends prove it is alive
+                Some(Ok(_)) => last_seen = Instant::now(),
+            },
+            received = subscription.live.recv() => match received {
+                Ok(event) => {
+                    if !deliver(&mut sink, &auth, &event, config.send_timeout).await {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    let reason = format!("lagged behind by {} events, reconnect with last_event_id", skipped);
+                    close(&mut sink, close_code::AGAIN, &reason).await;
+                    return;
+                }
+                Err(RecvError::Closed) => {
+                    close(&mut sink, close_code::AWAY, "server shutting down").await;
+                    return;
+                }
+            },
+        }
+    }
+}
+
+async fn deliver<Si>(sink: &mut Si, auth: &AuthContext, event: &Sequenced<UserEvent>, timeout: Duration) -> bool
+where
+    Si: Sink<Message> + Unpin,
+{
+    if !visible_to(auth, &event.event) {
+        return true;
+    }
+    send_json(sink, &UserEventMessage::event(event.id, &event.event), timeout).await
+}
+
+async fn send_json<Si>(sink: &mut Si, message: &UserEventMessage, timeout: Duration) -> bool
+where
+    Si: Sink<Message> + Unpin,
+{
+    match serde_json::to_string(message) {
+        Ok(text) => send(sink, Message::Text(text), timeout).await,
+        Err(_) => false,
+    }
+}
+
+/// A frame that cannot be written in time means the client stopped reading
+async fn send<Si>(sink: &mut Si, message: Message, timeout: Duration) -> bool
+where
+    Si: Sink<Message> + Unpin,
+{
+    matches!(tokio::time::timeout(timeout, sink.send(message)).await, Ok(Ok(())))
+}
+
+async fn close<Si>(sink: &mut Si, code: u16, reason: &str)
+where
+    Si: Sink<Message> + Unpin,
+{
+    let frame = CloseFrame {
+        code,
+        reason: Cow::Owned(reason.to_string()),
+    };
+    let _ = tokio::time::timeout(Duration::from_secs(1), sink.send(Message::Close(Some(frame)))).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::time::clock::SystemClock;
+    use crate::api::users::adapters::user_event_stream::UserEventStream;
+    use crate::shared::tenancy::tenant::TenantId;
+    use futures::channel::mpsc;
+    use std::convert::Infallible;
+    use uuid::Uuid;
+
+    fn tenant() -> TenantId {
+        TenantId::parse("acme").unwrap()
+    }
+
+    fn auth(user_id: &str, scopes: &[&str]) -> AuthContext {
+        AuthContext {
+            user_id: user_id.to_string(),
+            tenant_id: tenant(),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+            email: None,
+            roles: None,
+        }
+    }
+
+    fn parse(message: Message) -> serde_json::Value {
+        match message {
+            Message::Text(text) => serde_json::from_str(&text).unwrap(),
+            other => panic!("expected a text frame, got {:?}", other),
+        }
+    }
+
+    /// Session wired to in-memory channels: (outgoing frames, client sender)
+    fn start(
+        auth: AuthContext,
+        subscription: Subscription<UserEvent>,
+        last_event_id: Option<u64>,
+        config: SessionConfig,
+    ) -> (mpsc::UnboundedReceiver<Message>, mpsc::UnboundedSender<Result<Message, Infallible>>) {
+        let (sink, outgoing) = mpsc::unbounded();
+        let (client, incoming) = mpsc::unbounded();
+        tokio::spawn(run_session(sink, incoming, auth, subscription, last_event_id, config));
+        (outgoing, client)
+    }
+
+    #[test]
+    fn test_visibility_follows_scopes() {
+        let own = Uuid::new_v4();
+        let event = UserEvent::deleted(&tenant(), own, &SystemClock);
+
+        assert!(visible_to(&auth(&own.to_string(), &[]), &event));
+        assert!(!visible_to(&auth("someone-else", &[]), &event));
+        assert!(visible_to(&auth("someone-else", &["users:read"]), &event));
+
+        let other_tenant = UserEvent::deleted(&TenantId::parse("globex").unwrap(), own, &SystemClock);
+        assert!(!visible_to(&auth(&own.to_string(), &["users:read"]), &other_tenant));
+    }
+
+    #[test]
+    fn test_query_token_does_not_override_header() {
+        let mut headers = HeaderMap::new();
+
+        assert_eq!(with_query_token(&headers, Some("abc"))["authorization"], "Bearer abc");
+
+        headers.insert("authorization", HeaderValue::from_static("Bearer header"));
+        assert_eq!(with_query_token(&headers, Some("abc"))["authorization"], "Bearer header");
+    }
+
+    #[tokio::test]
+    async fn test_resume_replays_visible_events_then_streams_live() {
+        let events = UserEventStream::default();
+        let caller = Uuid::new_v4();
+        events.publish(UserEvent::deleted(&tenant(), caller, &SystemClock));
+        events.publish(UserEvent::deleted(&tenant(), Uuid::new_v4(), &SystemClock));
+        events.publish(UserEvent::deleted(&tenant(), caller, &SystemClock));
+
+        let subscription = events.subscribe(Some(1));
+        let (mut outgoing, _client) = start(auth(&caller.to_string(), &[]), subscription, Some(1), SessionConfig::default());
+        events.publish(UserEvent::deleted(&tenant(), caller, &SystemClock));
+
+        assert_eq!(parse(outgoing.next().await.unwrap())["id"], 3);
+        assert_eq!(parse(outgoing.next().await.unwrap())["id"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_missed_history_requests_resync() {
+        let events = UserEventStream::new(1, 8);
+        events.publish(UserEvent::deleted(&tenant(), Uuid::new_v4(), &SystemClock));
+        events.publish(UserEvent::deleted(&tenant(), Uuid::new_v4(), &SystemClock));
+
+        let subscription = events.subscribe(Some(0));
+        let (mut outgoing, _client) = start(auth("admin", &["users:read"]), subscription, Some(0), SessionConfig::default());
+
+        assert_eq!(parse(outgoing.next().await.unwrap())["type"], "resync_required");
+        assert_eq!(parse(outgoing.next().await.unwrap())["id"], 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_silent_client_is_disconnected() {
+        let events = UserEventStream::default();
+        let config = SessionConfig {
+            heartbeat_interval: Duration::from_secs(1),
+            idle_timeout: Duration::from_secs(2),
+            send_timeout: Duration::from_secs(1),
+        };
+        let (mut outgoing, _client) = start(auth("admin", &[]), events.subscribe(None), None, config);
+
+        assert!(matches!(outgoing.next().await, Some(Message::Ping(_))));
+        assert!(matches!(outgoing.next().await, Some(Message::Close(Some(frame))) if frame.code == close_code::AWAY));
+    }
+
+    #[tokio::test]
+    async fn test_lagging_client_is_disconnected() {
+        let events = UserEventStream::new(0, 1);
+        let subscription = events.subscribe(None);
+        events.publish(UserEvent::deleted(&tenant(), Uuid::new_v4(), &SystemClock));
+        events.publish(UserEvent::deleted(&tenant(), Uuid::new_v4(), &SystemClock));
+
+        let (mut outgoing, _client) = start(auth("admin", &["users:read"]), subscription, None, SessionConfig::default());
+
+        assert!(matches!(outgoing.next().await, Some(Message::Close(Some(frame))) if frame.code == close_code::AGAIN));
+    }
+}
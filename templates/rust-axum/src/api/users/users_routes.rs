@@ -0,0 +1,412 @@
+//! BRIK v5 Users Routes
+
+use super::handlers::create_user::create_user;
+use super::handlers::get_user::get_user_by_id;
+use super::handlers::list_users::list_users;
+use super::handlers::upload_avatar::{avatar_body_limit, upload_avatar};
+use super::handlers::user_events::user_events;
+use crate::api::app_state::AppState;
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{get, post, put},
+    Router,
+};
+
+pub fn users_routes(state: AppState) -> Router {
+    let avatar_body_limit = avatar_body_limit(state.max_avatar_bytes);
+
+    Router::new()
+        .route("/users", post(create_user).get(list_users))
+        .route("/users/events", get(user_events))
+        .route("/users/:id", get(get_user_by_id))
+        .route(
+            "/users/:id/avatar",
+            put(upload_avatar).layer(DefaultBodyLimit::max(avatar_body_limit)),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::health::health_checker::HealthChecker;
+    use crate::api::users::adapters::in_memory_user_repository::InMemoryUserRepository;
+    use crate::api::users::adapters::user_event_stream::UserEventStream;
+    use crate::shared::config::settings::{Profile, Settings};
+    use crate::shared::i18n::locale::Locale;
+    use crate::shared::security::secret::Secret;
+    use crate::shared::storage::blob_storage::InMemoryBlobStorage;
+    use crate::shared::tenancy::tenant::TenantRegistry;
+    use crate::shared::time::clock::SystemClock;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "test-secret";
+
+    fn token(scopes: &[&str]) -> String {
+        tenant_token("default", scopes)
+    }
+
+    fn tenant_token(tenant_id: &str, scopes: &[&str]) -> String {
+        user_token("user-123", tenant_id, scopes)
+    }
+
+    fn user_token(sub: &str, tenant_id: &str, scopes: &[&str]) -> String {
+        let claims = serde_json::json!({
+            "sub": sub,
+            "tenant_id": tenant_id,
+            "scopes": scopes,
+            "exp": chrono::Utc::now().timestamp() + 3600,
+        });
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap()
+    }
+
+    fn settings() -> Settings {
+        let mut settings = Settings::defaults(Profile::Testing);
+        settings.auth.jwt_secret = Secret::new(SECRET.to_string());
+        settings.auth.token_cache_capacity = 100;
+        settings
+    }
+
+    fn router() -> Router {
+        router_with(&settings())
+    }
+
+    fn router_with(settings: &Settings) -> Router {
+        users_routes(AppState::new(
+            Arc::new(InMemoryUserRepository::new()),
+            Arc::new(InMemoryBlobStorage::new("http://localhost:3000/uploads")),
+            Arc::new(UserEventStream::default()),
+            settings,
+            Arc::new(TenantRegistry::default()),
+            Arc::new(HealthChecker::new()),
+            SystemClock::shared(),
+        ))
+    }
+
+    fn create_request(token: &str, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/users")
+            .header("authorization", format!("Bearer {}", token))
+            .header("idempotency-key", "user-creation-abc123")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn json_body(response: axum::response::Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_then_get_user() {
+        let router = router();
+        let body = serde_json::json!({ "email": "john.doe@example.com", "name": "John Doe", "age": 30 });
+
+        let created = router
+            .clone()
+            .oneshot(create_request(&token(&["users:create"]), body))
+            .await
+            .unwrap();
+        assert_eq!(created.status(), StatusCode::CREATED);
+        let created = json_body(created).await;
+        assert_eq!(created["metadata"]["idempotency_key"], "user-creation-abc123");
+
+        let id = created["user"]["id"].as_str().unwrap();
+        let fetched = router
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/users/{}", id))
+                    .header("authorization", format!("Bearer {}", token(&["users:read"])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(fetched.status(), StatusCode::OK);
+        assert_eq!(json_body(fetched).await["user"]["email"], "john.doe@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_other_tenants_cannot_read_a_user() {
+        let router = router();
+        let body = serde_json::json!({ "email": "john.doe@example.com", "name": "John Doe", "age": 30 });
+        let created = router
+            .clone()
+            .oneshot(create_request(&tenant_token("acme", &["users:create"]), body.clone()))
+            .await
+            .unwrap();
+        let id = json_body(created).await["user"]["id"].as_str().unwrap().to_string();
+        let get = |tenant_id: &str, uri: String| {
+            Request::builder()
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", tenant_token(tenant_id, &["users:read"])))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = router.clone().oneshot(get("acme", format!("/users/{}", id))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = router.clone().oneshot(get("globex", format!("/users/{}", id))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(json_body(response).await["error"]["code"], "USER_NOT_FOUND");
+
+        let listed = json_body(router.clone().oneshot(get("globex", "/users".to_string())).await.unwrap()).await;
+        assert_eq!(listed["data"].as_array().unwrap().len(), 0);
+
+        // The same email is free in another tenant
+        let response = router
+            .oneshot(create_request(&tenant_token("globex", &["users:create"]), body))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_create_user_requires_scope() {
+        let body = serde_json::json!({ "email": "john.doe@example.com", "name": "John Doe", "age": 30 });
+
+        let response = router()
+            .oneshot(create_request(&token(&["users:read"]), body))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = json_body(response).await;
+        assert_eq!(body["error"]["type"], "GATE_ERROR");
+        assert_eq!(body["error"]["code"], "AUTH_INSUFFICIENT_SCOPES");
+    }
+
+    #[tokio::test]
+    async fn test_create_user_reports_every_invalid_field() {
+        let body = serde_json::json!({ "email": "john.doe@example.com", "name": "J", "age": 12 });
+
+        let response = router()
+            .oneshot(create_request(&token(&["users:create"]), body))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = json_body(response).await;
+        assert_eq!(body["error"]["type"], "DOMAIN_ERROR");
+        assert_eq!(body["error"]["code"], "INVALID_USER");
+        let codes: Vec<_> = body["error"]["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["code"].as_str().unwrap())
+            .collect();
+        assert_eq!(codes, ["INVALID_USER_NAME", "INVALID_USER_AGE"]);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_email_conflicts() {
+        let router = router();
+        let body = serde_json::json!({ "email": "john.doe@example.com", "name": "John Doe", "age": 30 });
+        let token = token(&["users:create"]);
+
+        router.clone().oneshot(create_request(&token, body.clone())).await.unwrap();
+        let response = router.oneshot(create_request(&token, body)).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(json_body(response).await["error"]["code"], "USER_ALREADY_EXISTS");
+    }
+
+    #[tokio::test]
+    async fn test_list_users_paginates() {
+        let router = router();
+        let token = token(&["users:create", "users:read"]);
+        for (i, email) in ["a@example.com", "b@example.com", "c@example.com"].iter().enumerate() {
+            let body = serde_json::json!({ "email": email, "name": "John Doe", "age": 30 + i });
+            let request = Request::builder()
+                .method("POST")
+                .uri("/users")
+                .header("authorization", format!("Bearer {}", token))
+                .header("idempotency-key", format!("user-creation-{}", i))
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            router.clone().oneshot(request).await.unwrap();
+        }
+        let list = |uri: String| {
+            Request::builder()
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let first = router.clone().oneshot(list("/users?sort=-age&limit=2".to_string())).await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let first = json_body(first).await;
+        assert_eq!(first["data"][0]["email"], "c@example.com");
+        assert_eq!(first["page"]["has_more"], true);
+
+        let cursor = first["page"]["next_cursor"].as_str().unwrap();
+        let second = router
+            .oneshot(list(format!("/users?sort=-age&limit=2&cursor={}", cursor)))
+            .await
+            .unwrap();
+        let second = json_body(second).await;
+        assert_eq!(second["data"].as_array().unwrap().len(), 1);
+        assert_eq!(second["data"][0]["email"], "a@example.com");
+        assert_eq!(second["page"]["has_more"], false);
+    }
+
+    #[tokio::test]
+    async fn test_list_users_rejects_unknown_sort() {
+        let response = router()
+            .oneshot(
+                Request::builder()
+                    .uri("/users?sort=password")
+                    .header("authorization", format!("Bearer {}", token(&["users:read"])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(json_body(response).await["error"]["code"], "INVALID_QUERY");
+    }
+
+    fn avatar_request(token: &str, id: &str, image: &[u8]) -> Request<Body> {
+        let boundary = "brik-boundary";
+        let body = [
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"avatar\"; filename=\"me.png\"\r\nContent-Type: image/png\r\n\r\n",
+                boundary
+            )
+            .into_bytes(),
+            image.to_vec(),
+            format!("\r\n--{}--\r\n", boundary).into_bytes(),
+        ]
+        .concat();
+
+        Request::builder()
+            .method("PUT")
+            .uri(format!("/users/{}/avatar", id))
+            .header("authorization", format!("Bearer {}", token))
+            .header("content-type", format!("multipart/form-data; boundary={}", boundary))
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_upload_avatar() {
+        let router = router();
+        let body = serde_json::json!({ "email": "john.doe@example.com", "name": "John Doe", "age": 30 });
+        let created = json_body(router.clone().oneshot(create_request(&token(&["users:create"]), body)).await.unwrap()).await;
+        let id = created["user"]["id"].as_str().unwrap();
+        let token = user_token(id, "default", &["users:update"]);
+        // Signature, empty IEND chunk
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\0IEND\xaeB`\x82";
+
+        let response = router.clone().oneshot(avatar_request(&token, id, png)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let avatar_url = json_body(response).await["user"]["profile"]["avatar_url"].clone();
+        assert!(avatar_url.as_str().unwrap().ends_with(".png"));
+
+        let svg = b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>";
+        let response = router.clone().oneshot(avatar_request(&token, id, svg)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let oversized = vec![0u8; avatar_body_limit(settings().http.max_avatar_bytes)];
+        let response = router.oneshot(avatar_request(&token, id, &oversized)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(json_body(response).await["error"]["code"], "AVATAR_TOO_LARGE");
+    }
+
+    #[tokio::test]
+    async fn test_avatar_limit_follows_settings() {
+        let mut settings = settings();
+        settings.http.max_avatar_bytes = 16;
+        let router = router_with(&settings);
+        let body = serde_json::json!({ "email": "john.doe@example.com", "name": "John Doe", "age": 30 });
+        let created = json_body(router.clone().oneshot(create_request(&token(&["users:create"]), body)).await.unwrap()).await;
+        let id = created["user"]["id"].as_str().unwrap();
+        let token = user_token(id, "default", &["users:update"]);
+        // 20 bytes: signature and an empty IEND chunk
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\0IEND\xaeB`\x82";
+
+        let response = router.clone().oneshot(avatar_request(&token, id, png)).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body = json_body(response).await;
+        assert_eq!(body["error"]["code"], "AVATAR_TOO_LARGE");
+        assert_eq!(body["error"]["message"], "The avatar exceeds the upload size limit of 16 bytes");
+
+        let response = Locale::Es.scope(router.oneshot(avatar_request(&token, id, png))).await.unwrap();
+        assert_eq!(
+            json_body(response).await["error"]["message"],
+            "El avatar supera el tamaño máximo permitido de 16 bytes"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_only_the_owner_or_an_admin_changes_an_avatar() {
+        let router = router();
+        let body = serde_json::json!({ "email": "john.doe@example.com", "name": "John Doe", "age": 30 });
+        let created = json_body(router.clone().oneshot(create_request(&token(&["users:create"]), body)).await.unwrap()).await;
+        let id = created["user"]["id"].as_str().unwrap();
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\0IEND\xaeB`\x82";
+
+        let other = token(&["users:update"]);
+        let response = router.clone().oneshot(avatar_request(&other, id, png)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(json_body(response).await["error"]["code"], "AVATAR_NOT_OWNED");
+
+        let admin = token(&["users:update", "users:admin"]);
+        let response = router.oneshot(avatar_request(&admin, id, png)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_user_events_require_token_and_upgrade() {
+        let router = router();
+
+        let anonymous = router
+            .clone()
+            .oneshot(Request::builder().uri("/users/events").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+
+        let plain_http = router
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/users/events?access_token={}", token(&[])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(plain_http.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(json_body(plain_http).await["error"]["code"], "WEBSOCKET_UPGRADE_REQUIRED");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_user_is_not_found() {
+        let response = router()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/users/{}", uuid::Uuid::new_v4()))
+                    .header("authorization", format!("Bearer {}", token(&["users:read"])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(json_body(response).await["error"]["type"], "DOMAIN_ERROR");
+    }
+}
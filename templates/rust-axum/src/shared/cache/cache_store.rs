@@ -0,0 +1,146 @@
+//! BRIK v5 Cache Store Port - Key/value cache used by caching decorators
+//!
+//! Values are opaque strings (decorators decide the encoding). Every failure
+//! is a `PortError` with code `CACHE_UNAVAILABLE`, which callers are expected
+//! to treat as a miss rather than a request failure.
+
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::time::clock::{SharedClock, SystemClock};
+use crate::shared::types::result::BrikResult;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+const PORT_NAME: &str = "CacheStore";
+
+#[async_trait::async_trait]
+pub trait CacheStore: Send + Sync {
+    async fn get(&self, key: &str) -> BrikResult<Option<String>, PortError>;
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> BrikResult<(), PortError>;
+
+    async fn delete(&self, key: &str) -> BrikResult<(), PortError>;
+}
+
+fn unavailable(error: redis::RedisError) -> PortError {
+    ErrorCode::CacheUnavailable.port_error(PORT_NAME, &error.to_string())
+}
+
+/// Redis adapter over the shared connection manager
+#[derive(Clone)]
+pub struct RedisCacheStore {
+    connection: ConnectionManager,
+}
+
+impl RedisCacheStore {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheStore for RedisCacheStore {
+    async fn get(&self, key: &str) -> BrikResult<Option<String>, PortError> {
+        self.connection.clone().get(key).await.map_err(unavailable)
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> BrikResult<(), PortError> {
+        // Redis rejects a zero expiry
+        let seconds = ttl.as_secs().max(1);
+        self.connection
+            .clone()
+            .set_ex(key, value, seconds)
+            .await
+            .map_err(unavailable)
+    }
+
+    async fn delete(&self, key: &str) -> BrikResult<(), PortError> {
+        self.connection.clone().del(key).await.map_err(unavailable)
+    }
+}
+
+/// In-process adapter for tests and local runs without Redis
+pub struct InMemoryCacheStore {
+    entries: RwLock<HashMap<String, (String, Instant)>>,
+    /// Entry expiry
+    clock: SharedClock,
+}
+
+impl InMemoryCacheStore {
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            clock: SystemClock::shared(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+impl Default for InMemoryCacheStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheStore for InMemoryCacheStore {
+    async fn get(&self, key: &str) -> BrikResult<Option<String>, PortError> {
+        Ok(self
+            .entries
+            .read()
+            .await
+            .get(key)
+            .filter(|(_, expires_at)| *expires_at > self.clock.instant())
+            .map(|(value, _)| value.clone()))
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> BrikResult<(), PortError> {
+        self.entries
+            .write()
+            .await
+            .insert(key.to_string(), (value.to_string(), self.clock.instant() + ttl));
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> BrikResult<(), PortError> {
+        self.entries.write().await.remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::time::clock::ManualClock;
+
+    #[tokio::test]
+    async fn test_in_memory_store_round_trip() {
+        let store = InMemoryCacheStore::new();
+
+        store.set("user:1", "cached", Duration::from_secs(60)).await.unwrap();
+        assert_eq!(store.get("user:1").await.unwrap().as_deref(), Some("cached"));
+
+        store.delete("user:1").await.unwrap();
+        assert_eq!(store.get("user:1").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_entry_expires_after_ttl() {
+        let clock = ManualClock::starting_now();
+        let store = InMemoryCacheStore::new().with_clock(clock.clone());
+        store.set("user:1", "cached", Duration::from_secs(60)).await.unwrap();
+
+        clock.advance(Duration::from_secs(59));
+        assert_eq!(store.get("user:1").await.unwrap().as_deref(), Some("cached"));
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(store.get("user:1").await.unwrap(), None);
+    }
+}
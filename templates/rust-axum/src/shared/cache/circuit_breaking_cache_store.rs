@@ -0,0 +1,56 @@
+//! BRIK v5 Circuit Breaking Cache Store - Stops waiting on a Redis that is down
+//!
+//! Callers already treat cache errors as misses; with the circuit open they
+//! get that miss immediately instead of after a connection timeout.
+
+use super::cache_store::CacheStore;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::resilience::circuit_breaker::CircuitBreaker;
+use crate::shared::types::result::BrikResult;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct CircuitBreakingCacheStore {
+    inner: Arc<dyn CacheStore>,
+    breaker: CircuitBreaker,
+}
+
+impl CircuitBreakingCacheStore {
+    pub fn new(inner: Arc<dyn CacheStore>, breaker: CircuitBreaker) -> Self {
+        Self { inner, breaker }
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheStore for CircuitBreakingCacheStore {
+    async fn get(&self, key: &str) -> BrikResult<Option<String>, PortError> {
+        self.breaker.call(self.inner.get(key)).await
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> BrikResult<(), PortError> {
+        self.breaker.call(self.inner.set(key, value, ttl)).await
+    }
+
+    async fn delete(&self, key: &str) -> BrikResult<(), PortError> {
+        self.breaker.call(self.inner.delete(key)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::cache::cache_store::InMemoryCacheStore;
+    use crate::shared::resilience::circuit_breaker::{CircuitBreakerConfig, CircuitState};
+    use crate::shared::time::clock::SystemClock;
+
+    #[tokio::test]
+    async fn test_delegates_while_closed() {
+        let breaker = CircuitBreaker::new("CacheStore", CircuitBreakerConfig::default(), SystemClock::shared());
+        let store = CircuitBreakingCacheStore::new(Arc::new(InMemoryCacheStore::new()), breaker.clone());
+
+        store.set("user:1", "cached", Duration::from_secs(60)).await.unwrap();
+
+        assert_eq!(store.get("user:1").await.unwrap(), Some("cached".to_string()));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
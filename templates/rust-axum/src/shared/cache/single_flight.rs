@@ -0,0 +1,110 @@
+//! BRIK v5 Single Flight - Coalesces concurrent loads of the same key
+//!
+//! While a load for a key is in flight, later callers for that key await the
+//! same future instead of starting their own, so a cache miss under load
+//! reaches the backing store once.
+
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, PoisonError};
+
+pub struct SingleFlight<T>
+where
+    T: Clone,
+{
+    in_flight: Mutex<HashMap<String, Shared<BoxFuture<'static, T>>>>,
+}
+
+impl<T> Default for SingleFlight<T>
+where
+    T: Clone,
+{
+    fn default() -> Self {
+        Self {
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T> SingleFlight<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `load` unless a load for `key` is already in flight, in which
+    /// case its result is shared. The load keeps running if its first caller
+    /// is cancelled while others still wait on it.
+    pub async fn run<F>(&self, key: &str, load: F) -> T
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        let flight = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key.to_string())
+            .or_insert_with(|| load.boxed().shared())
+            .clone();
+
+        let result = flight.clone().await;
+
+        // Whoever finishes first retires the flight; a newer flight that
+        // replaced it under the same key is left alone
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(PoisonError::into_inner);
+        if in_flight.get(key).is_some_and(|current| current.ptr_eq(&flight)) {
+            in_flight.remove(key);
+        }
+
+        result
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_callers_share_one_load() {
+        let flights = Arc::new(SingleFlight::new());
+        let loads = Arc::new(AtomicUsize::new(0));
+
+        let callers = (0..10).map(|_| {
+            let flights = flights.clone();
+            let loads = loads.clone();
+            tokio::spawn(async move {
+                flights
+                    .run("user:1", async move {
+                        loads.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        42
+                    })
+                    .await
+            })
+        });
+
+        let results = futures::future::join_all(callers).await;
+
+        assert!(results.into_iter().all(|result| result.unwrap() == 42));
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert_eq!(flights.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sequential_calls_load_again() {
+        let flights = SingleFlight::new();
+
+        assert_eq!(flights.run("user:1", async { 1 }).await, 1);
+        assert_eq!(flights.run("user:1", async { 2 }).await, 2);
+    }
+}
@@ -0,0 +1,1191 @@
+//! BRIK v5 Settings - Typed configuration with environment profiles
+//!
+//! Layers, lowest to highest precedence:
+//! 1. Built-in defaults for the active profile
+//! 2. `config/{profile}.toml`
+//! 3. `.env` file
+//! 4. Process environment variables
+//!
+//! Environment keys use the `APP__` prefix and `__` as the nesting separator,
+//! e.g. `APP__DATABASE__URL` or `APP__AUTH__JWT_SECRET`. The profile itself is
+//! selected with `APP_PROFILE` (development, production or testing).
+//!
+//! Any key can instead be read from a file by appending `_FILE`, e.g.
+//! `APP__AUTH__JWT_SECRET_FILE=/run/secrets/jwt_secret` for Docker and
+//! Kubernetes secret mounts; the file wins over the plain key of its layer.
+
+use crate::shared::pagination::list_query::MAX_PAGE_LIMIT;
+use crate::shared::security::password_hasher::{
+    DEFAULT_ARGON2_ITERATIONS, DEFAULT_ARGON2_MEMORY_KIB, DEFAULT_ARGON2_PARALLELISM, DEFAULT_BCRYPT_COST,
+};
+use crate::shared::security::secret::{Secret, FILE_SUFFIX, REDACTED};
+use crate::shared::tenancy::tenant::{TenantId, DEFAULT_TENANT, DEFAULT_TENANT_CLAIM};
+use argon2::Params;
+use config::{Config, Environment, File};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use thiserror::Error;
+
+pub const PROFILE_ENV_VAR: &str = "APP_PROFILE";
+const ENV_PREFIX: &str = "APP";
+const ENV_SEPARATOR: &str = "__";
+const DEVELOPMENT_JWT_SECRET: &str = "development-secret-change-me";
+const MIN_PRODUCTION_SECRET_LENGTH: usize = 32;
+
+/// Deployment profiles, matching `deployment.environment_configs` in
+/// `architecture-classification.json`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    Development,
+    Production,
+    Testing,
+}
+
+impl Profile {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "development" | "dev" => Some(Profile::Development),
+            "production" | "prod" => Some(Profile::Production),
+            "testing" | "test" => Some(Profile::Testing),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Profile::Development => "development",
+            Profile::Production => "production",
+            Profile::Testing => "testing",
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerSettings {
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseSettings {
+    pub url: String,
+    pub pool_size: u32,
+    pub connect_timeout_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisSettings {
+    pub url: String,
+    /// TTL of cached `GET /users/{id}` lookups
+    pub user_cache_ttl_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageSettings {
+    /// Directory used by the local filesystem blob storage
+    pub local_root: String,
+    /// URL the `local_root` directory is served from
+    pub public_base_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxSettings {
+    /// `redis` (pub/sub) or `log`
+    pub publisher: String,
+    /// Events go to `{channel_prefix}{aggregate_type}`
+    pub channel_prefix: String,
+    pub poll_interval_ms: u64,
+    pub batch_size: i64,
+    /// Attempts before an event is parked as dead
+    pub max_attempts: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthSettings {
+    /// Signs and verifies tokens
+    pub jwt_secret: Secret<String>,
+    /// Still accepted for verification while a rotation is in progress;
+    /// comma-separated in `APP__AUTH__PREVIOUS_JWT_SECRETS`
+    #[serde(default)]
+    pub previous_jwt_secrets: Vec<Secret<String>>,
+    /// Verified tokens kept until they expire, so repeat requests skip
+    /// signature checks; 0 disables the cache
+    pub token_cache_capacity: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordSettings {
+    /// `argon2id` or `bcrypt`; hashes made with the other one are upgraded on login
+    pub algorithm: String,
+    pub bcrypt_cost: u32,
+    pub argon2_memory_kib: u32,
+    pub argon2_iterations: u32,
+    pub argon2_parallelism: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdSettings {
+    /// `v7` (time-ordered) or `v4` (random) for new entity ids
+    pub version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteTimeout {
+    /// Route path as registered, e.g. `/api/v1/users/:id/avatar`
+    pub route: String,
+    pub timeout_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpSettings {
+    pub request_timeout_secs: u64,
+    /// Per-route overrides, as `[[http.route_timeouts]]` tables
+    #[serde(default)]
+    pub route_timeouts: Vec<RouteTimeout>,
+    /// Avatar uploads are limited by `max_avatar_bytes` instead
+    pub max_body_bytes: usize,
+    /// Largest avatar image; its route allows this plus multipart framing
+    pub max_avatar_bytes: usize,
+    /// Exact origins (`https://app.example.com`); empty disables CORS;
+    /// comma-separated in `APP__HTTP__CORS_ALLOWED_ORIGINS`
+    #[serde(default)]
+    pub cors_allowed_origins: Vec<String>,
+    /// gzip/br, negotiated with Accept-Encoding
+    pub compression: bool,
+    /// Strict-Transport-Security max-age; 0 disables the header
+    pub hsts_max_age_secs: u64,
+    /// `DENY` or `SAMEORIGIN`
+    pub frame_options: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerSettings {
+    /// Share of failed calls (0.0-1.0) that opens the circuit
+    pub failure_rate_threshold: f64,
+    /// Share of calls slower than `slow_call_duration_ms` that opens it
+    pub slow_call_rate_threshold: f64,
+    pub slow_call_duration_ms: u64,
+    /// Most recent calls the rates are computed over
+    pub window_size: usize,
+    pub minimum_calls: usize,
+    pub open_duration_ms: u64,
+    /// Trial calls let through while half-open
+    pub half_open_calls: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetrySettings {
+    /// Attempts including the first; idempotent operations only
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+    /// Budget for all attempts and sleeps of one operation
+    pub deadline_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkheadSettings {
+    /// Route path as registered, e.g. `/api/v1/users`
+    pub route: String,
+    /// Requests served at once; with load shedding, the adaptive limit's ceiling
+    pub max_concurrent: usize,
+    /// Longest wait for a slot before answering 503
+    pub queue_timeout_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadSheddingSettings {
+    /// Adapts bulkhead limits (AIMD) and sheds requests queued past the target
+    pub enabled: bool,
+    pub target_queue_ms: u64,
+    /// Floor of the adaptive limit
+    pub min_limit: usize,
+    /// Sent as `Retry-After` on every bulkhead rejection
+    pub retry_after_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResilienceSettings {
+    /// Shared by every port; each port gets its own circuit
+    pub circuit_breaker: CircuitBreakerSettings,
+    pub retry: RetrySettings,
+    /// One per route, as `[[resilience.bulkheads]]` tables; other routes are unlimited
+    #[serde(default)]
+    pub bulkheads: Vec<BulkheadSettings>,
+    pub load_shedding: LoadSheddingSettings,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantOverride {
+    pub id: String,
+    #[serde(default)]
+    pub suspended: bool,
+    /// Caps `limit` on list endpoints below the global maximum
+    pub max_page_size: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenancySettings {
+    /// JWT claim holding the tenant id
+    pub claim: String,
+    /// Tenant for tokens without the claim; unset rejects them
+    pub default_tenant: Option<String>,
+    /// Per-tenant overrides, as `[[tenancy.tenants]]` tables
+    #[serde(default)]
+    pub tenants: Vec<TenantOverride>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingSettings {
+    pub level: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub profile: Profile,
+    pub server: ServerSettings,
+    pub database: DatabaseSettings,
+    pub redis: RedisSettings,
+    pub storage: StorageSettings,
+    pub outbox: OutboxSettings,
+    pub auth: AuthSettings,
+    pub password: PasswordSettings,
+    pub ids: IdSettings,
+    pub http: HttpSettings,
+    pub resilience: ResilienceSettings,
+    pub tenancy: TenancySettings,
+    pub logging: LoggingSettings,
+}
+
+#[derive(Debug, Error)]
+pub enum SettingsError {
+    #[error("Unknown profile '{0}' (expected development, production or testing)")]
+    UnknownProfile(String),
+
+    #[error("Failed to read {variable} from '{path}': {source}")]
+    SecretFile {
+        variable: String,
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to load configuration: {0}")]
+    Load(#[from] config::ConfigError),
+
+    #[error("Invalid configuration:\n  - {}", .0.join("\n  - "))]
+    Invalid(Vec<String>),
+}
+
+impl Settings {
+    /// Built-in defaults per profile (log level and pool size mirror the
+    /// environment configs generated by BRIK)
+    pub fn defaults(profile: Profile) -> Self {
+        // Production connects only where the environment says; a missing
+        // variable fails validation instead of reaching for localhost
+        let local = |url: &str| match profile {
+            Profile::Production => String::new(),
+            _ => url.to_string(),
+        };
+        let (log_level, pool_size) = match profile {
+            Profile::Development => ("debug", 5),
+            Profile::Production => ("info", 20),
+            Profile::Testing => ("warn", 1),
+        };
+
+        Self {
+            profile,
+            server: ServerSettings {
+                host: "0.0.0.0".to_string(),
+                port: 3000,
+            },
+            database: DatabaseSettings {
+                url: local("postgres://localhost:5432/app"),
+                pool_size,
+                connect_timeout_secs: 5,
+            },
+            redis: RedisSettings {
+                url: local("redis://localhost:6379"),
+                user_cache_ttl_secs: 300,
+            },
+            storage: StorageSettings {
+                local_root: "./var/uploads".to_string(),
+                public_base_url: "http://localhost:3000/uploads".to_string(),
+            },
+            outbox: OutboxSettings {
+                publisher: "log".to_string(),
+                channel_prefix: "events.".to_string(),
+                poll_interval_ms: 500,
+                batch_size: 100,
+                max_attempts: 10,
+            },
+            auth: AuthSettings {
+                // Production has no usable default: validation rejects it
+                jwt_secret: Secret::new(match profile {
+                    Profile::Production => String::new(),
+                    _ => DEVELOPMENT_JWT_SECRET.to_string(),
+                }),
+                previous_jwt_secrets: Vec::new(),
+                token_cache_capacity: 10_000,
+            },
+            password: match profile {
+                // Cheapest accepted parameters keep test suites fast
+                Profile::Testing => PasswordSettings {
+                    algorithm: "argon2id".to_string(),
+                    bcrypt_cost: 4,
+                    argon2_memory_kib: 64,
+                    argon2_iterations: 1,
+                    argon2_parallelism: 1,
+                },
+                _ => PasswordSettings {
+                    algorithm: "argon2id".to_string(),
+                    bcrypt_cost: DEFAULT_BCRYPT_COST,
+                    argon2_memory_kib: DEFAULT_ARGON2_MEMORY_KIB,
+                    argon2_iterations: DEFAULT_ARGON2_ITERATIONS,
+                    argon2_parallelism: DEFAULT_ARGON2_PARALLELISM,
+                },
+            },
+            ids: IdSettings {
+                version: "v7".to_string(),
+            },
+            http: HttpSettings {
+                request_timeout_secs: 30,
+                route_timeouts: vec![RouteTimeout {
+                    route: "/api/v1/users/:id/avatar".to_string(),
+                    timeout_secs: 60,
+                }],
+                max_body_bytes: 1024 * 1024,
+                max_avatar_bytes: 2 * 1024 * 1024,
+                cors_allowed_origins: Vec::new(),
+                compression: true,
+                // HSTS on localhost outlives the dev server; production turns it on
+                hsts_max_age_secs: match profile {
+                    Profile::Production => 31_536_000,
+                    _ => 0,
+                },
+                frame_options: "DENY".to_string(),
+            },
+            resilience: ResilienceSettings {
+                circuit_breaker: CircuitBreakerSettings {
+                    failure_rate_threshold: 0.5,
+                    slow_call_rate_threshold: 1.0,
+                    slow_call_duration_ms: 2_000,
+                    window_size: 20,
+                    minimum_calls: 10,
+                    open_duration_ms: 30_000,
+                    half_open_calls: 3,
+                },
+                retry: RetrySettings {
+                    max_attempts: 3,
+                    base_delay_ms: 50,
+                    max_delay_ms: 1_000,
+                    deadline_ms: 3_000,
+                },
+                bulkheads: vec![BulkheadSettings {
+                    route: "/api/v1/users".to_string(),
+                    max_concurrent: 64,
+                    queue_timeout_ms: 1_000,
+                }],
+                load_shedding: LoadSheddingSettings {
+                    enabled: true,
+                    target_queue_ms: 200,
+                    min_limit: 4,
+                    retry_after_secs: 1,
+                },
+            },
+            tenancy: TenancySettings {
+                claim: DEFAULT_TENANT_CLAIM.to_string(),
+                // One shared tenant is convenient locally; production tokens must name theirs
+                default_tenant: match profile {
+                    Profile::Production => None,
+                    _ => Some(DEFAULT_TENANT.to_string()),
+                },
+                tenants: Vec::new(),
+            },
+            logging: LoggingSettings {
+                level: log_level.to_string(),
+            },
+        }
+    }
+
+    /// Loads and validates settings from `config/`, `.env` and the process environment
+    pub fn load() -> Result<Self, SettingsError> {
+        Self::load_from(Path::new("config"), Path::new(".env"), std::env::vars())
+    }
+
+    pub fn load_from<I>(config_dir: &Path, dotenv_path: &Path, env: I) -> Result<Self, SettingsError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let (env, env_secrets) = resolve_secret_files(env.into_iter().collect())?;
+        let (dotenv, dotenv_secrets) = resolve_secret_files(
+            dotenvy::from_path_iter(dotenv_path)
+                .map(|entries| entries.filter_map(Result::ok).collect())
+                .unwrap_or_default(),
+        )?;
+
+        let profile_name = env
+            .get(PROFILE_ENV_VAR)
+            .or_else(|| dotenv.get(PROFILE_ENV_VAR))
+            .map(String::as_str)
+            .unwrap_or("development");
+        let profile = Profile::parse(profile_name)
+            .ok_or_else(|| SettingsError::UnknownProfile(profile_name.to_string()))?;
+
+        // Same precedence as the layers: a `.env` secret file loses to
+        // anything the process environment sets for that key
+        let dotenv_secrets: SecretFiles = dotenv_secrets
+            .into_iter()
+            .filter(|(key, _)| !env.contains_key(key) && !env_secrets.iter().any(|(env_key, _)| env_key == key))
+            .collect();
+
+        let defaults = Self::defaults(profile);
+        let mut settings: Settings = Config::builder()
+            .add_source(Config::try_from(&defaults)?)
+            .add_source(File::from(config_dir.join(format!("{}.toml", profile))).required(false))
+            .add_source(env_source(dotenv))
+            .add_source(env_source(env))
+            .build()?
+            .try_deserialize()?;
+
+        // Secrets serialize redacted, so one no layer overrode is restored here
+        if settings.auth.jwt_secret.expose() == REDACTED {
+            settings.auth.jwt_secret = defaults.auth.jwt_secret;
+        }
+        for (key, secret) in dotenv_secrets.into_iter().chain(env_secrets) {
+            apply_secret_file(&mut settings, &key, secret);
+        }
+
+        // The profile comes from APP_PROFILE only, never from the layers
+        settings.profile = profile;
+        settings.validate()?;
+
+        Ok(settings)
+    }
+
+    /// Checks every setting and reports all problems at once
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        let mut problems = Vec::new();
+
+        if self.server.port == 0 {
+            problems.push("server.port must be between 1 and 65535".to_string());
+        }
+
+        if self.database.url.trim().is_empty() {
+            problems.push("database.url is required (set APP__DATABASE__URL or APP__DATABASE__URL_FILE)".to_string());
+        } else if !has_scheme(&self.database.url, &["postgres", "postgresql"]) {
+            problems.push(format!(
+                "database.url must be a postgres:// or postgresql:// URL (got '{}')",
+                redact_url(&self.database.url)
+            ));
+        }
+
+        if !(1..=100).contains(&self.database.pool_size) {
+            problems.push(format!(
+                "database.pool_size must be between 1 and 100 (got {})",
+                self.database.pool_size
+            ));
+        }
+
+        if !(1..=60).contains(&self.database.connect_timeout_secs) {
+            problems.push(format!(
+                "database.connect_timeout_secs must be between 1 and 60 (got {})",
+                self.database.connect_timeout_secs
+            ));
+        }
+
+        if self.redis.url.trim().is_empty() {
+            problems.push("redis.url is required (set APP__REDIS__URL or APP__REDIS__URL_FILE)".to_string());
+        } else if !has_scheme(&self.redis.url, &["redis", "rediss"]) {
+            problems.push(format!(
+                "redis.url must be a redis:// or rediss:// URL (got '{}')",
+                redact_url(&self.redis.url)
+            ));
+        }
+
+        if !(1..=86_400).contains(&self.redis.user_cache_ttl_secs) {
+            problems.push(format!(
+                "redis.user_cache_ttl_secs must be between 1 and 86400 (got {})",
+                self.redis.user_cache_ttl_secs
+            ));
+        }
+
+        if self.storage.local_root.trim().is_empty() {
+            problems.push("storage.local_root must not be empty".to_string());
+        }
+
+        if !has_scheme(&self.storage.public_base_url, &["http", "https"]) {
+            problems.push(format!(
+                "storage.public_base_url must be an http:// or https:// URL (got '{}')",
+                self.storage.public_base_url
+            ));
+        }
+
+        if !["redis", "log"].contains(&self.outbox.publisher.as_str()) {
+            problems.push(format!(
+                "outbox.publisher must be one of redis, log (got '{}')",
+                self.outbox.publisher
+            ));
+        }
+
+        if !(10..=60_000).contains(&self.outbox.poll_interval_ms) {
+            problems.push(format!(
+                "outbox.poll_interval_ms must be between 10 and 60000 (got {})",
+                self.outbox.poll_interval_ms
+            ));
+        }
+
+        if !(1..=1_000).contains(&self.outbox.batch_size) {
+            problems.push(format!(
+                "outbox.batch_size must be between 1 and 1000 (got {})",
+                self.outbox.batch_size
+            ));
+        }
+
+        if self.outbox.max_attempts < 1 {
+            problems.push(format!(
+                "outbox.max_attempts must be at least 1 (got {})",
+                self.outbox.max_attempts
+            ));
+        }
+
+        let jwt_secret = self.auth.jwt_secret.expose();
+        if jwt_secret.trim().is_empty() {
+            problems.push(
+                "auth.jwt_secret is required (set APP__AUTH__JWT_SECRET or APP__AUTH__JWT_SECRET_FILE)".to_string(),
+            );
+        } else if self.profile == Profile::Production {
+            if jwt_secret == DEVELOPMENT_JWT_SECRET {
+                problems.push("auth.jwt_secret must not use the development default in production".to_string());
+            } else if jwt_secret.len() < MIN_PRODUCTION_SECRET_LENGTH {
+                problems.push(format!(
+                    "auth.jwt_secret must be at least {} characters in production",
+                    MIN_PRODUCTION_SECRET_LENGTH
+                ));
+            }
+        }
+
+        if self.auth.previous_jwt_secrets.iter().any(|secret| secret.expose().trim().is_empty()) {
+            problems.push("auth.previous_jwt_secrets must not contain empty secrets".to_string());
+        }
+
+        if !["argon2id", "bcrypt"].contains(&self.password.algorithm.as_str()) {
+            problems.push(format!(
+                "password.algorithm must be one of argon2id, bcrypt (got '{}')",
+                self.password.algorithm
+            ));
+        }
+
+        if !["v4", "v7"].contains(&self.ids.version.as_str()) {
+            problems.push(format!("ids.version must be one of v4, v7 (got '{}')", self.ids.version));
+        }
+
+        if !(4..=31).contains(&self.password.bcrypt_cost) {
+            problems.push(format!(
+                "password.bcrypt_cost must be between 4 and 31 (got {})",
+                self.password.bcrypt_cost
+            ));
+        }
+
+        if let Err(error) = Params::new(
+            self.password.argon2_memory_kib,
+            self.password.argon2_iterations,
+            self.password.argon2_parallelism,
+            None,
+        ) {
+            problems.push(format!("password.argon2_* parameters are invalid: {}", error));
+        }
+
+        if !(1..=300).contains(&self.http.request_timeout_secs) {
+            problems.push(format!(
+                "http.request_timeout_secs must be between 1 and 300 (got {})",
+                self.http.request_timeout_secs
+            ));
+        }
+
+        for RouteTimeout { route, timeout_secs } in &self.http.route_timeouts {
+            if !route.starts_with('/') || !(1..=3_600).contains(timeout_secs) {
+                problems.push(format!(
+                    "http.route_timeouts entries need a /path and 1..=3600 seconds (got '{}' = {})",
+                    route, timeout_secs
+                ));
+            }
+        }
+
+        if !(1..=100 * 1024 * 1024).contains(&self.http.max_body_bytes) {
+            problems.push(format!(
+                "http.max_body_bytes must be between 1 and 104857600 (got {})",
+                self.http.max_body_bytes
+            ));
+        }
+
+        if !(1..=100 * 1024 * 1024).contains(&self.http.max_avatar_bytes) {
+            problems.push(format!(
+                "http.max_avatar_bytes must be between 1 and 104857600 (got {})",
+                self.http.max_avatar_bytes
+            ));
+        }
+
+        for origin in &self.http.cors_allowed_origins {
+            if !is_origin(origin) {
+                problems.push(format!(
+                    "http.cors_allowed_origins entries must be scheme://host[:port] without a path (got '{}')",
+                    origin
+                ));
+            }
+        }
+
+        if !["DENY", "SAMEORIGIN"].contains(&self.http.frame_options.as_str()) {
+            problems.push(format!(
+                "http.frame_options must be one of DENY, SAMEORIGIN (got '{}')",
+                self.http.frame_options
+            ));
+        }
+
+        let breaker = &self.resilience.circuit_breaker;
+        for (name, rate) in [
+            ("failure_rate_threshold", breaker.failure_rate_threshold),
+            ("slow_call_rate_threshold", breaker.slow_call_rate_threshold),
+        ] {
+            if !(rate > 0.0 && rate <= 1.0) {
+                problems.push(format!(
+                    "resilience.circuit_breaker.{} must be above 0 and at most 1 (got {})",
+                    name, rate
+                ));
+            }
+        }
+
+        if breaker.window_size == 0 || !(1..=breaker.window_size).contains(&breaker.minimum_calls) {
+            problems.push(format!(
+                "resilience.circuit_breaker.minimum_calls must be between 1 and window_size (got {} of {})",
+                breaker.minimum_calls, breaker.window_size
+            ));
+        }
+
+        for (name, value) in [
+            ("slow_call_duration_ms", breaker.slow_call_duration_ms),
+            ("open_duration_ms", breaker.open_duration_ms),
+            ("half_open_calls", breaker.half_open_calls as u64),
+        ] {
+            if value == 0 {
+                problems.push(format!("resilience.circuit_breaker.{} must be greater than 0", name));
+            }
+        }
+
+        let retry = &self.resilience.retry;
+        if !(1..=10).contains(&retry.max_attempts) {
+            problems.push(format!(
+                "resilience.retry.max_attempts must be between 1 and 10 (got {})",
+                retry.max_attempts
+            ));
+        }
+
+        if retry.base_delay_ms == 0 || retry.base_delay_ms > retry.max_delay_ms {
+            problems.push(format!(
+                "resilience.retry.base_delay_ms must be between 1 and max_delay_ms (got {} of {})",
+                retry.base_delay_ms, retry.max_delay_ms
+            ));
+        }
+
+        if retry.deadline_ms == 0 {
+            problems.push("resilience.retry.deadline_ms must be greater than 0".to_string());
+        }
+
+        for BulkheadSettings { route, max_concurrent, queue_timeout_ms } in &self.resilience.bulkheads {
+            if !route.starts_with('/') || *max_concurrent == 0 || *queue_timeout_ms == 0 {
+                problems.push(format!(
+                    "resilience.bulkheads entries need a /path, max_concurrent and queue_timeout_ms above 0 (got '{}' = {}, {}ms)",
+                    route, max_concurrent, queue_timeout_ms
+                ));
+            }
+        }
+
+        let shedding = &self.resilience.load_shedding;
+        if shedding.target_queue_ms == 0 || shedding.min_limit == 0 {
+            problems.push(format!(
+                "resilience.load_shedding.target_queue_ms and min_limit must be greater than 0 (got {}ms, {})",
+                shedding.target_queue_ms, shedding.min_limit
+            ));
+        }
+
+        if self.tenancy.claim.trim().is_empty() {
+            problems.push("tenancy.claim must not be empty".to_string());
+        }
+
+        if let Some(default_tenant) = &self.tenancy.default_tenant {
+            if TenantId::parse(default_tenant).is_none() {
+                problems.push(format!(
+                    "tenancy.default_tenant must be 1-64 characters of [a-zA-Z0-9_-] (got '{}')",
+                    default_tenant
+                ));
+            }
+        }
+
+        let mut seen_tenants = HashSet::new();
+        for TenantOverride { id, max_page_size, .. } in &self.tenancy.tenants {
+            let valid_limit = max_page_size.is_none_or(|limit| (1..=MAX_PAGE_LIMIT).contains(&limit));
+            if TenantId::parse(id).is_none() || !valid_limit || !seen_tenants.insert(id) {
+                problems.push(format!(
+                    "tenancy.tenants entries need a unique [a-zA-Z0-9_-] id and max_page_size in 1..={} (got '{}' = {:?})",
+                    MAX_PAGE_LIMIT, id, max_page_size
+                ));
+            }
+        }
+
+        if !["trace", "debug", "info", "warn", "error"].contains(&self.logging.level.as_str()) {
+            problems.push(format!(
+                "logging.level must be one of trace, debug, info, warn, error (got '{}')",
+                self.logging.level
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(SettingsError::Invalid(problems))
+        }
+    }
+}
+
+fn env_source(vars: HashMap<String, String>) -> Environment {
+    Environment::with_prefix(ENV_PREFIX)
+        .prefix_separator(ENV_SEPARATOR)
+        .separator(ENV_SEPARATOR)
+        .try_parsing(true)
+        .list_separator(",")
+        .with_list_parse_key("auth.previous_jwt_secrets")
+        .with_list_parse_key("http.cors_allowed_origins")
+        .source(Some(vars))
+}
+
+/// Environment keys of `Secret` settings. Read from a `_FILE`, their value
+/// goes straight into `Settings` instead of through the config builder,
+/// which would keep plain copies that are never zeroized.
+const SECRET_KEYS: [&str; 2] = ["APP__AUTH__JWT_SECRET", "APP__AUTH__PREVIOUS_JWT_SECRETS"];
+
+/// Secrets read from `{KEY}_FILE` variables, keyed by `{KEY}`
+type SecretFiles = Vec<(String, Secret<String>)>;
+
+/// Replaces every `{KEY}_FILE` variable with `{KEY}` set to the file content;
+/// files of `SECRET_KEYS` are returned separately for `apply_secret_file`
+fn resolve_secret_files(mut vars: HashMap<String, String>) -> Result<(HashMap<String, String>, SecretFiles), SettingsError> {
+    // `APP__` only: `APPDATA_FILE` and the like belong to other programs
+    let prefix = format!("{}{}", ENV_PREFIX, ENV_SEPARATOR);
+    let file_keys: Vec<String> = vars
+        .keys()
+        .filter(|key| key.starts_with(&prefix) && key.ends_with(FILE_SUFFIX))
+        .cloned()
+        .collect();
+    let mut secrets = Vec::new();
+
+    for file_key in file_keys {
+        let path = vars.remove(&file_key).unwrap_or_default();
+        let secret = Secret::from_file(&path).map_err(|source| SettingsError::SecretFile {
+            variable: file_key.clone(),
+            path: path.clone(),
+            source,
+        })?;
+        let key = file_key.trim_end_matches(FILE_SUFFIX).to_string();
+        if SECRET_KEYS.contains(&key.as_str()) {
+            secrets.push((key, secret));
+        } else {
+            vars.insert(key, secret.expose().clone());
+        }
+    }
+
+    Ok((vars, secrets))
+}
+
+fn apply_secret_file(settings: &mut Settings, key: &str, secret: Secret<String>) {
+    if key == SECRET_KEYS[0] {
+        settings.auth.jwt_secret = secret;
+    } else {
+        settings.auth.previous_jwt_secrets = secret
+            .expose()
+            .split(',')
+            .map(|previous| Secret::new(previous.to_string()))
+            .collect();
+    }
+}
+
+/// `scheme://host...` with one of the accepted schemes and a non-empty host
+fn has_scheme(url: &str, schemes: &[&str]) -> bool {
+    match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let host = rest.rsplit('@').next().unwrap_or_default();
+            schemes.contains(&scheme) && !host.is_empty() && !host.starts_with('/')
+        }
+        None => false,
+    }
+}
+
+/// An exact CORS origin: `http(s)://host[:port]`, no path or trailing slash
+fn is_origin(origin: &str) -> bool {
+    has_scheme(origin, &["http", "https"])
+        && origin.split_once("://").is_some_and(|(_, host)| !host.contains(['/', '@', ' ']))
+}
+
+/// Hides credentials embedded in connection URLs before they reach error messages
+fn redact_url(url: &str) -> String {
+    match (url.split_once("://"), url.rfind('@')) {
+        (Some((scheme, _)), Some(at)) => format!("{}://***{}", scheme, &url[at..]),
+        _ => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_development_defaults() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        let settings = Settings::load_from(dir, &dir.join(".env"), env(&[])).unwrap();
+
+        assert_eq!(settings.profile, Profile::Development);
+        assert_eq!(settings.database.pool_size, 5);
+        assert_eq!(settings.logging.level, "debug");
+        assert_eq!(settings.auth.jwt_secret.expose(), DEVELOPMENT_JWT_SECRET);
+    }
+
+    #[test]
+    fn test_serialized_settings_redact_secrets() {
+        let mut settings = Settings::defaults(Profile::Development);
+        settings.auth.previous_jwt_secrets = vec![Secret::new("old-secret".to_string())];
+
+        let dump = serde_json::to_value(&settings).unwrap();
+
+        assert_eq!(dump["auth"]["jwt_secret"], REDACTED);
+        assert_eq!(dump["auth"]["previous_jwt_secrets"], serde_json::json!([REDACTED]));
+        assert!(!dump.to_string().contains(DEVELOPMENT_JWT_SECRET));
+    }
+
+    #[test]
+    fn test_layers_override_in_order() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("testing.toml"),
+            "[database]\npool_size = 3\nurl = \"postgres://file-host/app\"\n\n[server]\nport = 8080\n",
+        )
+        .unwrap();
+        let mut dotenv = std::fs::File::create(dir.join(".env")).unwrap();
+        writeln!(dotenv, "APP__DATABASE__POOL_SIZE=4").unwrap();
+        writeln!(dotenv, "APP__SERVER__PORT=9090").unwrap();
+
+        let settings = Settings::load_from(
+            dir,
+            &dir.join(".env"),
+            env(&[("APP_PROFILE", "testing"), ("APP__DATABASE__POOL_SIZE", "7")]),
+        )
+        .unwrap();
+
+        assert_eq!(settings.profile, Profile::Testing);
+        assert_eq!(settings.logging.level, "warn"); // profile default
+        assert_eq!(settings.database.url, "postgres://file-host/app"); // toml
+        assert_eq!(settings.server.port, 9090); // .env over toml
+        assert_eq!(settings.database.pool_size, 7); // environment over .env
+    }
+
+    #[test]
+    fn test_production_requires_secret() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        let error = Settings::load_from(dir, &dir.join(".env"), env(&[("APP_PROFILE", "production")]))
+            .unwrap_err();
+
+        assert!(error.to_string().contains("auth.jwt_secret is required"));
+    }
+
+    #[test]
+    fn test_production_requires_connection_urls() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        let error = Settings::load_from(
+            dir,
+            &dir.join(".env"),
+            env(&[("APP_PROFILE", "production"), ("APP__AUTH__JWT_SECRET", &"s".repeat(32))]),
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(error.contains("database.url is required"), "{}", error);
+        assert!(error.contains("redis.url is required"), "{}", error);
+
+        let settings = Settings::load_from(
+            dir,
+            &dir.join(".env"),
+            env(&[
+                ("APP_PROFILE", "production"),
+                ("APP__AUTH__JWT_SECRET", &"s".repeat(32)),
+                ("APP__DATABASE__URL", "postgres://db.internal:5432/app"),
+                ("APP__REDIS__URL", "rediss://cache.internal:6379"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(settings.database.url, "postgres://db.internal:5432/app");
+    }
+
+    #[test]
+    fn test_validation_lists_every_problem() {
+        let mut settings = Settings::defaults(Profile::Production);
+        settings.database.url = "mysql://localhost/app".to_string();
+        settings.database.pool_size = 0;
+        settings.redis.url = "localhost:6379".to_string();
+        settings.redis.user_cache_ttl_secs = 0;
+        settings.storage.public_base_url = "/uploads".to_string();
+
+        match settings.validate() {
+            Err(SettingsError::Invalid(problems)) => {
+                assert_eq!(problems.len(), 6);
+                assert!(problems.iter().any(|p| p.starts_with("database.url")));
+                assert!(problems.iter().any(|p| p.starts_with("database.pool_size")));
+                assert!(problems.iter().any(|p| p.starts_with("redis.url")));
+                assert!(problems.iter().any(|p| p.starts_with("redis.user_cache_ttl_secs")));
+                assert!(problems.iter().any(|p| p.starts_with("storage.public_base_url")));
+                assert!(problems.iter().any(|p| p.starts_with("auth.jwt_secret")));
+            }
+            other => panic!("expected validation failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_secrets_from_files_and_rotation_list() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        let secret_path = dir.join("jwt_secret");
+        std::fs::write(&secret_path, "file-secret\n").unwrap();
+
+        let settings = Settings::load_from(
+            dir,
+            &dir.join(".env"),
+            env(&[
+                ("APP__AUTH__JWT_SECRET", "plain-secret"),
+                ("APP__AUTH__JWT_SECRET_FILE", secret_path.to_str().unwrap()),
+                ("APP__AUTH__PREVIOUS_JWT_SECRETS", "old-one,old-two"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(settings.auth.jwt_secret.expose(), "file-secret");
+        assert_eq!(
+            settings.auth.previous_jwt_secrets.iter().map(|s| s.expose().as_str()).collect::<Vec<_>>(),
+            vec!["old-one", "old-two"]
+        );
+        assert!(!format!("{:?}", settings).contains("file-secret"));
+
+        let error = Settings::load_from(
+            dir,
+            &dir.join(".env"),
+            env(&[("APP__AUTH__JWT_SECRET_FILE", "/nonexistent/jwt_secret")]),
+        )
+        .unwrap_err();
+        assert!(matches!(error, SettingsError::SecretFile { .. }));
+    }
+
+    #[test]
+    fn test_file_variables_outside_the_app_prefix_are_ignored() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+
+        let settings = Settings::load_from(
+            dir,
+            &dir.join(".env"),
+            env(&[("APPX_FILE", "/nonexistent/appx"), ("APPLICATION_LOG_FILE", "/nonexistent/log")]),
+        )
+        .unwrap();
+
+        assert_eq!(settings.profile, Profile::Development);
+    }
+
+    #[test]
+    fn test_secret_files_bypass_the_config_layers() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        std::fs::write(dir.join("jwt_secret"), "file-secret\n").unwrap();
+        std::fs::write(dir.join("previous"), "old-one,old-two\n").unwrap();
+        let vars = env(&[
+            ("APP__AUTH__JWT_SECRET_FILE", dir.join("jwt_secret").to_str().unwrap()),
+            ("APP__AUTH__PREVIOUS_JWT_SECRETS_FILE", dir.join("previous").to_str().unwrap()),
+        ]);
+
+        let (plain, secrets) = resolve_secret_files(vars.iter().cloned().collect()).unwrap();
+        assert!(plain.values().all(|value| !value.contains("secret") && !value.contains("old-")));
+        assert_eq!(secrets.len(), 2);
+
+        let mut dotenv = std::fs::File::create(dir.join(".env")).unwrap();
+        writeln!(dotenv, "APP__AUTH__JWT_SECRET_FILE={}", dir.join("jwt_secret").display()).unwrap();
+        let settings = Settings::load_from(dir, &dir.join(".env"), vars).unwrap();
+        assert_eq!(settings.auth.jwt_secret.expose(), "file-secret");
+        assert_eq!(settings.auth.previous_jwt_secrets.len(), 2);
+
+        // The environment still wins over a `.env` secret file
+        let settings =
+            Settings::load_from(dir, &dir.join(".env"), env(&[("APP__AUTH__JWT_SECRET", "env-secret")])).unwrap();
+        assert_eq!(settings.auth.jwt_secret.expose(), "env-secret");
+    }
+
+    #[test]
+    fn test_password_settings_validation() {
+        let mut settings = Settings::defaults(Profile::Development);
+        settings.password.algorithm = "md5".to_string();
+        settings.password.bcrypt_cost = 3;
+        settings.password.argon2_memory_kib = 1;
+
+        match settings.validate() {
+            Err(SettingsError::Invalid(problems)) => {
+                assert_eq!(problems.len(), 3);
+                assert!(problems.iter().all(|p| p.starts_with("password.")));
+            }
+            other => panic!("expected validation failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_id_version_validation() {
+        let mut settings = Settings::defaults(Profile::Development);
+        assert_eq!(settings.ids.version, "v7");
+        settings.ids.version = "v1".to_string();
+
+        match settings.validate() {
+            Err(SettingsError::Invalid(problems)) => {
+                assert_eq!(problems, vec!["ids.version must be one of v4, v7 (got 'v1')".to_string()]);
+            }
+            other => panic!("expected validation failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_http_settings_from_toml_and_env() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("development.toml"),
+            "[[http.route_timeouts]]\nroute = \"/api/v1/reports/:id\"\ntimeout_secs = 120\n",
+        )
+        .unwrap();
+
+        let settings = Settings::load_from(
+            dir,
+            &dir.join(".env"),
+            env(&[("APP__HTTP__CORS_ALLOWED_ORIGINS", "https://app.example.com,http://localhost:5173")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            settings.http.route_timeouts,
+            vec![RouteTimeout {
+                route: "/api/v1/reports/:id".to_string(),
+                timeout_secs: 120,
+            }]
+        );
+        assert_eq!(
+            settings.http.cors_allowed_origins,
+            vec!["https://app.example.com", "http://localhost:5173"]
+        );
+    }
+
+    #[test]
+    fn test_http_settings_validation() {
+        let mut settings = Settings::defaults(Profile::Development);
+        settings.http.request_timeout_secs = 0;
+        settings.http.route_timeouts.push(RouteTimeout {
+            route: "reports".to_string(),
+            timeout_secs: 10,
+        });
+        settings.http.max_body_bytes = 0;
+        settings.http.max_avatar_bytes = 200 * 1024 * 1024;
+        settings.http.cors_allowed_origins = vec!["https://app.example.com/".to_string(), "*".to_string()];
+        settings.http.frame_options = "ALLOW".to_string();
+
+        match settings.validate() {
+            Err(SettingsError::Invalid(problems)) => {
+                assert_eq!(problems.len(), 7);
+                assert!(problems.iter().all(|p| p.starts_with("http.")));
+            }
+            other => panic!("expected validation failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_tenancy_settings() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("development.toml"),
+            "[[tenancy.tenants]]\nid = \"acme\"\nmax_page_size = 25\n\n[[tenancy.tenants]]\nid = \"globex\"\nsuspended = true\n",
+        )
+        .unwrap();
+
+        let settings = Settings::load_from(dir, &dir.join(".env"), env(&[("APP__TENANCY__CLAIM", "org_id")])).unwrap();
+        assert_eq!(settings.tenancy.claim, "org_id");
+        assert_eq!(settings.tenancy.default_tenant.as_deref(), Some("default"));
+        assert_eq!(settings.tenancy.tenants[0].max_page_size, Some(25));
+        assert!(settings.tenancy.tenants[1].suspended);
+        assert_eq!(Settings::defaults(Profile::Production).tenancy.default_tenant, None);
+
+        let mut settings = settings;
+        settings.tenancy.default_tenant = Some("not valid".to_string());
+        settings.tenancy.tenants[1].id = "acme".to_string();
+        match settings.validate() {
+            Err(SettingsError::Invalid(problems)) => {
+                assert_eq!(problems.len(), 2);
+                assert!(problems.iter().all(|p| p.starts_with("tenancy.")));
+            }
+            other => panic!("expected validation failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_resilience_settings_validation() {
+        let mut settings = Settings::defaults(Profile::Development);
+        settings.resilience.circuit_breaker.failure_rate_threshold = 1.5;
+        settings.resilience.circuit_breaker.minimum_calls = 50;
+        settings.resilience.circuit_breaker.open_duration_ms = 0;
+        settings.resilience.retry.max_attempts = 0;
+        settings.resilience.retry.base_delay_ms = 5_000;
+        settings.resilience.bulkheads[0].max_concurrent = 0;
+        settings.resilience.load_shedding.min_limit = 0;
+
+        match settings.validate() {
+            Err(SettingsError::Invalid(problems)) => {
+                assert_eq!(problems.len(), 7);
+                assert!(problems.iter().all(|p| p.starts_with("resilience.")));
+            }
+            other => panic!("expected validation failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown_profile() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        let error = Settings::load_from(dir, &dir.join(".env"), env(&[("APP_PROFILE", "staging")]))
+            .unwrap_err();
+
+        assert!(matches!(error, SettingsError::UnknownProfile(name) if name == "staging"));
+    }
+
+    #[test]
+    fn test_redact_url_hides_credentials() {
+        assert_eq!(redact_url("postgres://user:pass@db:5432/app"), "postgres://***@db:5432/app");
+        assert_eq!(redact_url("redis://localhost:6379"), "redis://localhost:6379");
+    }
+}
@@ -0,0 +1,221 @@
+//! BRIK v5 API Error - Maps gate, domain and port failures to the error envelope
+
+use super::error_code::ErrorCode;
+use super::port_error::PortError;
+use crate::api::users::domain::errors::domain_error::DomainError;
+use crate::api::users::gates::gate_result::GateError;
+use crate::shared::i18n::locale::Locale;
+use crate::shared::i18n::messages::{localize, template};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorType {
+    GateError,
+    DomainError,
+    PortError,
+    InternalError,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErrorBody {
+    #[serde(rename = "type")]
+    #[schema(inline)]
+    pub error_type: ErrorType,
+    #[schema(example = "VALIDATION_FAILED")]
+    pub code: String,
+    #[schema(example = "Email is required and must be a valid email address")]
+    pub message: String,
+    /// Present when type is GATE_ERROR
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gate: Option<String>,
+    /// Present when type is PORT_ERROR
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    /// Present when several checks failed at once, one entry per check
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schema(inline)]
+    pub errors: Vec<ErrorDetail>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ErrorDetail {
+    #[schema(example = "INVALID_USER_AGE")]
+    pub code: String,
+    #[schema(example = "Users must be at least 13 years old (COPPA compliance)")]
+    pub message: String,
+}
+
+/// Error envelope shared by every endpoint
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = Error)]
+pub struct ErrorResponse {
+    #[schema(inline)]
+    pub error: ErrorBody,
+}
+
+#[derive(Debug)]
+pub enum ApiError {
+    Gate(GateError),
+    Domain(DomainError),
+    Port(PortError),
+    Internal(String),
+}
+
+impl ApiError {
+    pub fn status_code(&self) -> StatusCode {
+        let status = match self {
+            ApiError::Gate(error) => error.http_status,
+            ApiError::Domain(error) => error.http_status,
+            ApiError::Port(_) => 503,
+            ApiError::Internal(_) => 500,
+        };
+
+        StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// Envelope with the message in the locale of the current request
+    pub fn body(&self) -> ErrorResponse {
+        let locale = Locale::current();
+        let error = match self {
+            ApiError::Gate(error) => ErrorBody {
+                error_type: ErrorType::GateError,
+                code: error.code.clone(),
+                message: localize(&error.code, &error.message, &error.params, locale),
+                gate: Some(error.gate.clone()),
+                port: None,
+                errors: Vec::new(),
+            },
+            ApiError::Domain(error) => ErrorBody {
+                error_type: ErrorType::DomainError,
+                code: error.code.clone(),
+                message: localize(&error.code, &error.message, &error.params, locale),
+                gate: None,
+                port: None,
+                errors: error
+                    .errors
+                    .iter()
+                    .map(|detail| ErrorDetail {
+                        code: detail.code.clone(),
+                        message: localize(&detail.code, &detail.message, &detail.params, locale),
+                    })
+                    .collect(),
+            },
+            ApiError::Port(error) => ErrorBody {
+                error_type: ErrorType::PortError,
+                code: error.code.clone(),
+                message: localize(&error.code, &error.message, &[], locale),
+                gate: None,
+                port: Some(error.port.clone()),
+                errors: Vec::new(),
+            },
+            // Internal details are logged, never returned
+            ApiError::Internal(_) => ErrorBody {
+                error_type: ErrorType::InternalError,
+                code: ErrorCode::InternalError.as_str().to_string(),
+                message: template(ErrorCode::InternalError, locale).to_string(),
+                gate: None,
+                port: None,
+                errors: Vec::new(),
+            },
+        };
+
+        ErrorResponse { error }
+    }
+}
+
+impl From<GateError> for ApiError {
+    fn from(error: GateError) -> Self {
+        ApiError::Gate(error)
+    }
+}
+
+impl From<DomainError> for ApiError {
+    fn from(error: DomainError) -> Self {
+        ApiError::Domain(error)
+    }
+}
+
+impl From<PortError> for ApiError {
+    fn from(error: PortError) -> Self {
+        ApiError::Port(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status_code(), Json(self.body())).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gate_error_envelope() {
+        let error = ApiError::from(GateError::new("AuthGate", "AUTH_TOKEN_MISSING", "Token required", 401));
+        let body = serde_json::to_value(error.body()).unwrap();
+
+        assert_eq!(error.status_code(), StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["type"], "GATE_ERROR");
+        assert_eq!(body["error"]["code"], "AUTH_TOKEN_MISSING");
+        assert_eq!(body["error"]["gate"], "AuthGate");
+        assert!(body["error"].get("port").is_none());
+    }
+
+    #[test]
+    fn test_domain_error_envelope_lists_every_failed_check() {
+        let error = ApiError::from(ErrorCode::InvalidUser.domain_error().with_errors(vec![
+            ErrorCode::InvalidUserName.domain_error(),
+            ErrorCode::InvalidUserAge.domain_error(),
+        ]));
+        let body = serde_json::to_value(error.body()).unwrap();
+
+        assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "INVALID_USER");
+        assert_eq!(body["error"]["errors"][0]["code"], "INVALID_USER_NAME");
+        assert_eq!(body["error"]["errors"][1]["code"], "INVALID_USER_AGE");
+        let single = serde_json::to_value(ApiError::from(ErrorCode::InvalidUserAge.domain_error()).body()).unwrap();
+        assert!(single["error"].get("errors").is_none());
+    }
+
+    #[test]
+    fn test_port_error_envelope() {
+        let error = ApiError::from(PortError::new("UserRepository", "DATABASE_UNAVAILABLE", "down"));
+        let body = serde_json::to_value(error.body()).unwrap();
+
+        assert_eq!(error.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["error"]["type"], "PORT_ERROR");
+        assert_eq!(body["error"]["port"], "UserRepository");
+    }
+
+    #[test]
+    fn test_internal_error_hides_details() {
+        let error = ApiError::Internal("connection string leaked".to_string());
+        let body = serde_json::to_value(error.body()).unwrap();
+
+        assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["error"]["code"], "INTERNAL_ERROR");
+        assert!(!body.to_string().contains("leaked"));
+    }
+
+    #[tokio::test]
+    async fn test_message_follows_request_locale() {
+        let error = ApiError::from(ErrorCode::AuthInsufficientScopes.gate_error_with_params("AuthGate", &[("scope", "users:read")]));
+
+        let body = Locale::Es.scope(async { serde_json::to_value(error.body()).unwrap() }).await;
+
+        assert_eq!(body["error"]["message"], "Falta el scope requerido: users:read");
+        assert_eq!(
+            serde_json::to_value(error.body()).unwrap()["error"]["message"],
+            "Missing required scope: users:read"
+        );
+    }
+}
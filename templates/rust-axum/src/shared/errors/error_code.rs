@@ -0,0 +1,358 @@
+//! BRIK v5 Error Code Catalog - Every code the API can return
+//!
+//! Codes are a public contract: clients branch on them and support links to
+//! their documentation, so a code is never renamed or reused. Add new ones
+//! at the end of their group.
+//!
+//! The catalog is exported with `server export-error-catalog [--format markdown|json]`,
+//! which `bootstrap::run` hands to `export_command` before loading settings.
+
+use super::api_error::ErrorType;
+use super::port_error::PortError;
+use crate::api::users::domain::errors::domain_error::DomainError;
+use crate::api::users::gates::gate_result::GateError;
+use crate::shared::i18n::locale::Locale;
+use crate::shared::i18n::messages::render;
+use serde::Serialize;
+
+pub const EXPORT_COMMAND: &str = "export-error-catalog";
+
+macro_rules! error_codes {
+    ($($variant:ident => ($code:literal, $error_type:ident, $status:literal, $message:literal)),+ $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $($variant,)+
+        }
+
+        impl ErrorCode {
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$variant,)+];
+
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $code,)+
+                }
+            }
+
+            pub fn error_type(self) -> ErrorType {
+                match self {
+                    $(ErrorCode::$variant => ErrorType::$error_type,)+
+                }
+            }
+
+            /// Status the API answers with; port failures always surface as 503
+            pub fn http_status(self) -> u16 {
+                match self {
+                    $(ErrorCode::$variant => $status,)+
+                }
+            }
+
+            pub fn default_message(self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $message,)+
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    // Gates
+    AuthTokenMissing => ("AUTH_TOKEN_MISSING", GateError, 401, "Authorization header with Bearer token is required"),
+    AuthTokenInvalid => ("AUTH_TOKEN_INVALID", GateError, 401, "Invalid or expired JWT token"),
+    AuthInsufficientScopes => ("AUTH_INSUFFICIENT_SCOPES", GateError, 403, "Missing required scope: {scope}"),
+    AuthTenantMissing => ("AUTH_TENANT_MISSING", GateError, 403, "The token does not name a valid tenant"),
+    TenantSuspended => ("TENANT_SUSPENDED", GateError, 403, "This tenant is suspended"),
+    ValidationFailed => ("VALIDATION_FAILED", GateError, 400, "The request does not match the expected schema"),
+    InvalidQuery => ("INVALID_QUERY", GateError, 400, "One or more query parameters are invalid"),
+    IdempotencyKeyInvalid => ("IDEMPOTENCY_KEY_INVALID", GateError, 400, "idempotency-key header is required (1-255 chars, [a-zA-Z0-9_-])"),
+    WebsocketUpgradeRequired => ("WEBSOCKET_UPGRADE_REQUIRED", GateError, 400, "This endpoint only accepts WebSocket upgrades"),
+    AvatarMissing => ("AVATAR_MISSING", GateError, 400, "The multipart body has no avatar field"),
+    AvatarTooLarge => ("AVATAR_TOO_LARGE", GateError, 413, "The avatar exceeds the upload size limit of {max_bytes} bytes"),
+    AvatarUnsupportedType => ("AVATAR_UNSUPPORTED_TYPE", GateError, 415, "The avatar is not a JPEG, PNG or WebP image"),
+    AvatarInvalidImage => ("AVATAR_INVALID_IMAGE", GateError, 400, "The avatar image is corrupt or truncated"),
+    GateResultEmpty => ("GATE_RESULT_EMPTY", GateError, 500, "Gate returned neither data nor error"),
+    RequestTimeout => ("REQUEST_TIMEOUT", GateError, 408, "The request took too long to process"),
+    RequestTooLarge => ("REQUEST_TOO_LARGE", GateError, 413, "The request body exceeds the size limit"),
+    BulkheadFull => ("BULKHEAD_FULL", GateError, 503, "Too many concurrent requests for this endpoint, try again later"),
+    LoadShed => ("LOAD_SHED", GateError, 503, "The server is overloaded, try again later"),
+    AvatarNotOwned => ("AVATAR_NOT_OWNED", GateError, 403, "Only the user or an admin can change this avatar"),
+
+    // Domain rules
+    InvalidUserName => ("INVALID_USER_NAME", DomainError, 400, "Name must be 2-100 characters without control characters"),
+    InvalidUserAge => ("INVALID_USER_AGE", DomainError, 400, "Age must be between 13 and 150"),
+    InvalidUserEmail => ("INVALID_USER_EMAIL", DomainError, 400, "Email is required and must be a valid email address"),
+    InvalidUserProfile => ("INVALID_USER_PROFILE", DomainError, 400, "Profile fields are too long or not valid URLs"),
+    UserNotFound => ("USER_NOT_FOUND", DomainError, 404, "User does not exist"),
+    UserAlreadyExists => ("USER_ALREADY_EXISTS", DomainError, 409, "A user with this email already exists"),
+    UserVersionConflict => ("USER_VERSION_CONFLICT", DomainError, 409, "User was modified by another request, retry the operation"),
+    UserDeletionTooEarly => ("USER_DELETION_TOO_EARLY", DomainError, 422, "Users cannot be deleted within 1 hour of creation"),
+    InvalidUser => ("INVALID_USER", DomainError, 400, "Several user fields are invalid, see errors"),
+
+    // Ports
+    DatabaseUnavailable => ("DATABASE_UNAVAILABLE", PortError, 503, "The database could not be reached"),
+    DatabaseError => ("DATABASE_ERROR", PortError, 503, "The database rejected the operation"),
+    RowMappingFailed => ("ROW_MAPPING_FAILED", PortError, 503, "A stored row could not be read"),
+    UniqueViolation => ("UNIQUE_VIOLATION", PortError, 503, "A unique constraint was violated"),
+    VersionConflict => ("VERSION_CONFLICT", PortError, 503, "The record was modified concurrently"),
+    NotFound => ("NOT_FOUND", PortError, 503, "The record to change does not exist"),
+    CacheUnavailable => ("CACHE_UNAVAILABLE", PortError, 503, "The cache could not be reached"),
+    StorageUnavailable => ("STORAGE_UNAVAILABLE", PortError, 503, "Blob storage could not be reached"),
+    InvalidKey => ("INVALID_KEY", PortError, 503, "The blob key is not valid"),
+    PublishFailed => ("PUBLISH_FAILED", PortError, 503, "An event could not be published"),
+    HashFailed => ("HASH_FAILED", PortError, 503, "The password could not be hashed"),
+    HasherUnavailable => ("HASHER_UNAVAILABLE", PortError, 503, "The password hashing pool is unavailable"),
+    InvalidHash => ("INVALID_HASH", PortError, 503, "The stored password hash is corrupt"),
+    UnsupportedHash => ("UNSUPPORTED_HASH", PortError, 503, "The stored password hash has an unknown format"),
+    CircuitOpen => ("CIRCUIT_OPEN", PortError, 503, "The service is temporarily unavailable, try again later"),
+    DeadlineExceeded => ("DEADLINE_EXCEEDED", PortError, 503, "A dependency did not answer in time"),
+
+    // Internal
+    InternalError => ("INTERNAL_ERROR", InternalError, 500, "An unexpected error occurred"),
+}
+
+impl ErrorCode {
+    pub fn parse(code: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|candidate| candidate.as_str() == code)
+    }
+
+    /// Heading anchor of the code in the exported Markdown catalog
+    pub fn docs_anchor(self) -> String {
+        self.as_str().to_lowercase()
+    }
+
+    pub fn gate_error(self, gate: &str) -> GateError {
+        self.gate_error_with(gate, self.default_message())
+    }
+
+    pub fn gate_error_with(self, gate: &str, message: &str) -> GateError {
+        GateError::new(gate, self.as_str(), message, self.http_status())
+    }
+
+    /// Error whose message is the English template filled with `params`,
+    /// translated per request by the i18n catalogs
+    pub fn gate_error_with_params(self, gate: &str, params: &[(&str, &str)]) -> GateError {
+        GateError::new(gate, self.as_str(), &render(self, Locale::En, params), self.http_status())
+            .with_params(params)
+    }
+
+    pub fn domain_error(self) -> DomainError {
+        self.domain_error_with(self.default_message())
+    }
+
+    pub fn domain_error_with(self, message: &str) -> DomainError {
+        DomainError::new(self.as_str(), message, self.http_status())
+    }
+
+    pub fn port_error(self, port: &str, message: &str) -> PortError {
+        PortError::new(port, self.as_str(), message)
+    }
+
+    pub fn domain_error_with_params(self, params: &[(&str, &str)]) -> DomainError {
+        DomainError::new(self.as_str(), &render(self, Locale::En, params), self.http_status())
+            .with_params(params)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogEntry {
+    pub code: &'static str,
+    #[serde(rename = "type")]
+    pub error_type: ErrorType,
+    pub http_status: u16,
+    pub message: &'static str,
+    pub docs_anchor: String,
+}
+
+pub fn catalog() -> Vec<CatalogEntry> {
+    ErrorCode::ALL
+        .iter()
+        .map(|code| CatalogEntry {
+            code: code.as_str(),
+            error_type: code.error_type(),
+            http_status: code.http_status(),
+            message: code.default_message(),
+            docs_anchor: code.docs_anchor(),
+        })
+        .collect()
+}
+
+/// Same spelling as the `type` field of error responses
+fn type_label(error_type: ErrorType) -> String {
+    match serde_json::to_value(error_type) {
+        Ok(serde_json::Value::String(label)) => label,
+        _ => format!("{:?}", error_type),
+    }
+}
+
+pub fn catalog_json() -> String {
+    serde_json::to_string_pretty(&catalog()).expect("catalog serializes")
+}
+
+pub fn catalog_markdown() -> String {
+    let entries = catalog();
+    let mut markdown = String::from(
+        "# Error codes\n\nEvery error response carries one of these codes in `error.code`.\n\n\
+         | Code | Type | HTTP status |\n|------|------|-------------|\n",
+    );
+
+    for entry in &entries {
+        markdown.push_str(&format!(
+            "| [`{}`](#{}) | {} | {} |\n",
+            entry.code,
+            entry.docs_anchor,
+            type_label(entry.error_type),
+            entry.http_status
+        ));
+    }
+
+    for entry in &entries {
+        markdown.push_str(&format!(
+            "\n## {}\n\n- Type: `{}`\n- HTTP status: {}\n\n{}\n",
+            entry.code,
+            type_label(entry.error_type),
+            entry.http_status,
+            entry.message
+        ));
+    }
+
+    markdown
+}
+
+/// Handles `export-error-catalog [--format markdown|json]`; `None` when the
+/// arguments (program name excluded) are not this command
+pub fn export_command<I>(args: I) -> Option<Result<String, String>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    if args.next().as_deref() != Some(EXPORT_COMMAND) {
+        return None;
+    }
+
+    let format = match (args.next().as_deref(), args.next()) {
+        (None, None) => "markdown".to_string(),
+        (Some("--format"), Some(format)) => format,
+        _ => return Some(Err(format!("usage: {} [--format markdown|json]", EXPORT_COMMAND))),
+    };
+
+    Some(match format.as_str() {
+        "markdown" | "md" => Ok(catalog_markdown()),
+        "json" => Ok(catalog_json()),
+        other => Err(format!("unknown format '{}', expected markdown or json", other)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    #[test]
+    fn test_codes_are_unique() {
+        let mut seen = HashSet::new();
+
+        for code in ErrorCode::ALL {
+            assert!(seen.insert(code.as_str()), "duplicate code {}", code.as_str());
+            assert_eq!(ErrorCode::parse(code.as_str()), Some(*code));
+        }
+    }
+
+    #[test]
+    fn test_codes_are_screaming_snake_case() {
+        for code in ErrorCode::ALL {
+            assert!(
+                code.as_str().chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'),
+                "{} is not SCREAMING_SNAKE_CASE",
+                code.as_str()
+            );
+        }
+    }
+
+    #[test]
+    fn test_port_codes_surface_as_service_unavailable() {
+        for code in ErrorCode::ALL.iter().filter(|code| code.error_type() == ErrorType::PortError) {
+            assert_eq!(code.http_status(), 503, "{}", code.as_str());
+        }
+    }
+
+    fn code_literals(source: &str) -> Vec<String> {
+        // Production code only; string literals sit at odd positions
+        let production = source.split("#[cfg(test)]").next().unwrap_or_default();
+        production
+            .split('"')
+            .skip(1)
+            .step_by(2)
+            .filter(|literal| {
+                literal.len() > 3
+                    && literal.contains('_')
+                    && literal.starts_with(|c: char| c.is_ascii_uppercase())
+                    && literal.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            })
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn visit(dir: &Path, literals: &mut Vec<(String, String)>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                visit(&path, literals);
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                let source = std::fs::read_to_string(&path).unwrap();
+                for literal in code_literals(&source) {
+                    literals.push((path.display().to_string(), literal));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_code_in_source_is_catalogued() {
+        // Literals that look like codes but are not
+        let not_codes = [
+            "APP_PROFILE",
+            "APP__AUTH__JWT_SECRET",
+            "APP__AUTH__PREVIOUS_JWT_SECRETS",
+            "CARGO_MANIFEST_DIR",
+            "CARGO_PKG_VERSION",
+            "SCREAMING_SNAKE_CASE",
+        ];
+        let mut literals = Vec::new();
+        visit(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut literals);
+
+        let missing: Vec<_> = literals
+            .iter()
+            .filter(|(_, literal)| ErrorCode::parse(literal).is_none() && !not_codes.contains(&literal.as_str()))
+            .collect();
+
+        assert!(missing.is_empty(), "codes missing from the catalog: {:?}", missing);
+    }
+
+    #[test]
+    fn test_markdown_export_links_every_code() {
+        let markdown = catalog_markdown();
+
+        for code in ErrorCode::ALL {
+            assert!(markdown.contains(&format!("(#{})", code.docs_anchor())));
+            assert!(markdown.contains(&format!("\n## {}\n", code.as_str())));
+        }
+    }
+
+    #[test]
+    fn test_export_command() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert!(export_command(args(&["serve"])).is_none());
+        assert!(export_command(args(&[EXPORT_COMMAND])).unwrap().unwrap().starts_with("# Error codes"));
+
+        let json = export_command(args(&[EXPORT_COMMAND, "--format", "json"])).unwrap().unwrap();
+        let entries: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(entries.as_array().unwrap().len(), ErrorCode::ALL.len());
+        assert_eq!(entries[0]["code"], "AUTH_TOKEN_MISSING");
+        assert_eq!(entries[0]["type"], "GATE_ERROR");
+        assert_eq!(entries[0]["docs_anchor"], "auth_token_missing");
+
+        assert!(export_command(args(&[EXPORT_COMMAND, "--format", "yaml"])).unwrap().is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! BRIK v5 Error Responses - Reusable OpenAPI responses for the error envelope
+//! Mirrors `components.responses` in `openapi.yaml`; every one of them carries
+//! the `Error` schema.
+
+use utoipa::openapi::{
+    header::HeaderBuilder, ContentBuilder, Object, ObjectBuilder, Ref, RefOr, Response,
+    ResponseBuilder, SchemaType,
+};
+use utoipa::ToResponse;
+
+fn error_response(description: &str) -> ResponseBuilder {
+    ResponseBuilder::new().description(description).content(
+        "application/json",
+        ContentBuilder::new()
+            .schema(Ref::from_schema_name("Error"))
+            .build(),
+    )
+}
+
+fn integer_schema() -> Object {
+    ObjectBuilder::new().schema_type(SchemaType::Integer).build()
+}
+
+macro_rules! error_responses {
+    ($($name:ident => $description:literal),+ $(,)?) => {
+        $(
+            pub struct $name;
+
+            impl<'r> ToResponse<'r> for $name {
+                fn response() -> (&'r str, RefOr<Response>) {
+                    (stringify!($name), error_response($description).build().into())
+                }
+            }
+        )+
+    };
+}
+
+error_responses! {
+    BadRequest => "Bad Request - Invalid input or validation failed",
+    Unauthorized => "Unauthorized - Invalid or missing authentication",
+    Forbidden => "Forbidden - Insufficient permissions",
+    NotFound => "Not Found - Resource does not exist",
+    Conflict => "Conflict - Resource already exists or idempotency conflict",
+    PayloadTooLarge => "Payload Too Large - Upload exceeds the size limit",
+    UnsupportedMediaType => "Unsupported Media Type - Content is not an accepted format",
+    InternalServerError => "Internal Server Error",
+    ServiceUnavailable => "Service Unavailable - A port (database, cache, external service) failed",
+}
+
+pub struct TooManyRequests;
+
+impl<'r> ToResponse<'r> for TooManyRequests {
+    fn response() -> (&'r str, RefOr<Response>) {
+        let response = error_response("Too Many Requests - Rate limit exceeded")
+            .header(
+                "x-ratelimit-limit",
+                HeaderBuilder::new()
+                    .schema(integer_schema())
+                    .description(Some("Request limit per time window"))
+                    .build(),
+            )
+            .header(
+                "x-ratelimit-remaining",
+                HeaderBuilder::new()
+                    .schema(integer_schema())
+                    .description(Some("Remaining requests in current window"))
+                    .build(),
+            )
+            .header(
+                "x-ratelimit-reset",
+                HeaderBuilder::new()
+                    .schema(integer_schema())
+                    .description(Some("Time when the rate limit resets (Unix timestamp)"))
+                    .build(),
+            );
+
+        ("TooManyRequests", response.build().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_response_references_error_schema() {
+        let (name, response) = NotFound::response();
+        let json = serde_json::to_value(response).unwrap();
+
+        assert_eq!(name, "NotFound");
+        assert_eq!(
+            json["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Error"
+        );
+    }
+
+    #[test]
+    fn test_too_many_requests_declares_rate_limit_headers() {
+        let (_, response) = TooManyRequests::response();
+        let json = serde_json::to_value(response).unwrap();
+
+        assert!(json["headers"].get("x-ratelimit-limit").is_some());
+        assert!(json["headers"].get("x-ratelimit-remaining").is_some());
+        assert!(json["headers"].get("x-ratelimit-reset").is_some());
+    }
+}
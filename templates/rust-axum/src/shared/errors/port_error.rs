@@ -0,0 +1,43 @@
+//! BRIK v5 Port Error - Failures raised by infrastructure adapters
+
+use super::error_code::ErrorCode;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error)]
+#[error("Port {port} failed with code {code}: {message}")]
+pub struct PortError {
+    pub port: String,
+    pub code: String,
+    pub message: String,
+}
+
+impl PortError {
+    pub fn new(port: &str, code: &str, message: &str) -> Self {
+        Self {
+            port: port.to_string(),
+            code: code.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn has_code(&self, code: ErrorCode) -> bool {
+        self.code == code.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_port_error_display() {
+        let error = PortError::new("UserRepository", "DATABASE_UNAVAILABLE", "Connection refused");
+        let error_string = format!("{}", error);
+
+        assert!(error_string.contains("UserRepository"));
+        assert!(error_string.contains("DATABASE_UNAVAILABLE"));
+        assert!(error_string.contains("Connection refused"));
+        assert!(error.has_code(ErrorCode::DatabaseUnavailable));
+        assert!(!error.has_code(ErrorCode::DatabaseError));
+    }
+}
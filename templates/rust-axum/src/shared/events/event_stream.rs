@@ -0,0 +1,181 @@
+//! BRIK v5 Event Stream - Sequenced in-process broadcast with replay
+//!
+//! Every published event gets a monotonically increasing id. Subscribers get
+//! a live receiver plus the retained events after the id they last saw, so a
+//! reconnecting client can resume without gaps as long as the history still
+//! covers it. Ids are local to the process.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::broadcast;
+
+pub const DEFAULT_HISTORY_CAPACITY: usize = 1024;
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Debug, PartialEq)]
+pub struct Sequenced<T> {
+    pub id: u64,
+    pub event: T,
+}
+
+pub struct Subscription<T> {
+    /// Retained events after the requested id, oldest first
+    pub replay: Vec<Arc<Sequenced<T>>>,
+    /// Events published after `replay` was taken; lags when the consumer
+    /// falls more than the channel capacity behind
+    pub live: broadcast::Receiver<Arc<Sequenced<T>>>,
+    /// The requested id is no longer (or not yet) covered by the history,
+    /// so the subscriber has to resynchronise from the source of truth
+    pub missed: bool,
+}
+
+struct History<T> {
+    last_id: u64,
+    events: VecDeque<Arc<Sequenced<T>>>,
+}
+
+pub struct EventStream<T> {
+    history: Mutex<History<T>>,
+    history_capacity: usize,
+    sender: broadcast::Sender<Arc<Sequenced<T>>>,
+}
+
+impl<T> Default for EventStream<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY, DEFAULT_CHANNEL_CAPACITY)
+    }
+}
+
+impl<T> EventStream<T>
+where
+    T: Send + Sync + 'static,
+{
+    pub fn new(history_capacity: usize, channel_capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(channel_capacity.max(1));
+        Self {
+            history: Mutex::new(History {
+                last_id: 0,
+                events: VecDeque::with_capacity(history_capacity),
+            }),
+            history_capacity,
+            sender,
+        }
+    }
+
+    /// Publishes to current subscribers and the history; returns the event id
+    pub fn publish(&self, event: T) -> u64 {
+        let mut history = self.history.lock().unwrap_or_else(PoisonError::into_inner);
+        history.last_id += 1;
+        let event = Arc::new(Sequenced {
+            id: history.last_id,
+            event,
+        });
+
+        if history.events.len() == self.history_capacity {
+            history.events.pop_front();
+        }
+        if self.history_capacity > 0 {
+            history.events.push_back(event.clone());
+        }
+        // No subscribers is not an error
+        let _ = self.sender.send(event);
+
+        history.last_id
+    }
+
+    /// Subscribes after `last_event_id` (`None` for live events only)
+    pub fn subscribe(&self, last_event_id: Option<u64>) -> Subscription<T> {
+        // Holding the lock keeps publish from slipping between the snapshot
+        // and the receiver, so replay and live neither overlap nor gap
+        let history = self.history.lock().unwrap_or_else(PoisonError::into_inner);
+        let live = self.sender.subscribe();
+
+        let Some(after) = last_event_id else {
+            return Subscription {
+                replay: Vec::new(),
+                live,
+                missed: false,
+            };
+        };
+
+        let oldest_retained = history.events.front().map_or(history.last_id + 1, |event| event.id);
+        Subscription {
+            replay: history.events.iter().filter(|event| event.id > after).cloned().collect(),
+            live,
+            missed: after > history.last_id || after + 1 < oldest_retained,
+        }
+    }
+
+    pub fn last_event_id(&self) -> u64 {
+        self.history.lock().unwrap_or_else(PoisonError::into_inner).last_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(events: &[Arc<Sequenced<&'static str>>]) -> Vec<u64> {
+        events.iter().map(|event| event.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_live_subscription() {
+        let stream = EventStream::new(8, 8);
+        stream.publish("before");
+        let mut subscription = stream.subscribe(None);
+
+        stream.publish("after");
+
+        assert!(subscription.replay.is_empty());
+        assert_eq!(subscription.live.recv().await.unwrap().event, "after");
+    }
+
+    #[tokio::test]
+    async fn test_resume_replays_then_continues_live() {
+        let stream = EventStream::new(8, 8);
+        for event in ["a", "b", "c"] {
+            stream.publish(event);
+        }
+
+        let mut subscription = stream.subscribe(Some(1));
+        stream.publish("d");
+
+        assert_eq!(ids(&subscription.replay), vec![2, 3]);
+        assert!(!subscription.missed);
+        assert_eq!(subscription.live.recv().await.unwrap().id, 4);
+    }
+
+    #[test]
+    fn test_resume_beyond_history_is_missed() {
+        let stream = EventStream::new(2, 8);
+        for event in ["a", "b", "c", "d"] {
+            stream.publish(event);
+        }
+
+        let evicted = stream.subscribe(Some(1));
+        assert!(evicted.missed);
+        assert_eq!(ids(&evicted.replay), vec![3, 4]);
+
+        assert!(!stream.subscribe(Some(2)).missed);
+        // An id from before a restart
+        assert!(stream.subscribe(Some(99)).missed);
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_lags() {
+        let stream = EventStream::new(0, 2);
+        let mut subscription = stream.subscribe(None);
+        for event in ["a", "b", "c"] {
+            stream.publish(event);
+        }
+
+        assert!(matches!(
+            subscription.live.recv().await,
+            Err(broadcast::error::RecvError::Lagged(1))
+        ));
+    }
+}
@@ -0,0 +1,255 @@
+//! BRIK v5 HTTP Hardening - Timeouts, body limits, CORS, compression and security headers
+//!
+//! Every value comes from `HttpSettings`. Timeouts and body limits reject
+//! with the usual error envelope; the per-route timeout is looked up by the
+//! matched route path (`/api/v1/users/:id/avatar`), so it must be applied
+//! with `Router::layer` after the routes are registered.
+
+use crate::shared::config::settings::HttpSettings;
+use crate::shared::errors::api_error::ApiError;
+use crate::shared::errors::error_code::ErrorCode;
+use axum::{
+    extract::{DefaultBodyLimit, MatchedPath, Request, State},
+    http::{header, HeaderName, HeaderValue, Method},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::set_header::SetResponseHeaderLayer;
+
+const GATE_NAME: &str = "HttpGate";
+
+/// Headers browsers may send cross-origin; the rest of the API contract
+const CORS_ALLOWED_HEADERS: [&str; 5] = [
+    "authorization",
+    "content-type",
+    "accept-language",
+    "idempotency-key",
+    "x-correlation-id",
+];
+
+#[derive(Debug, Clone)]
+struct Timeouts {
+    default: Duration,
+    routes: Arc<HashMap<String, Duration>>,
+}
+
+impl Timeouts {
+    fn from_settings(settings: &HttpSettings) -> Self {
+        Self {
+            default: Duration::from_secs(settings.request_timeout_secs),
+            routes: Arc::new(
+                settings
+                    .route_timeouts
+                    .iter()
+                    .map(|entry| (entry.route.clone(), Duration::from_secs(entry.timeout_secs)))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn for_route(&self, route: Option<&str>) -> Duration {
+        route.and_then(|route| self.routes.get(route)).copied().unwrap_or(self.default)
+    }
+}
+
+async fn enforce_timeout(State(timeouts): State<Timeouts>, request: Request, next: Next) -> Response {
+    let timeout = timeouts.for_route(request.extensions().get::<MatchedPath>().map(MatchedPath::as_str));
+
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => ApiError::from(ErrorCode::RequestTimeout.gate_error(GATE_NAME)).into_response(),
+    }
+}
+
+/// Rejection for extractors that hit the body limit
+pub fn request_too_large() -> ApiError {
+    ApiError::from(ErrorCode::RequestTooLarge.gate_error(GATE_NAME))
+}
+
+fn cors_layer(origins: &[String]) -> CorsLayer {
+    // Origins are checked by Settings::validate
+    let origins: Vec<HeaderValue> = origins.iter().filter_map(|origin| origin.parse().ok()).collect();
+
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_headers(CORS_ALLOWED_HEADERS.map(HeaderName::from_static))
+        .expose_headers([HeaderName::from_static("x-correlation-id")])
+        .max_age(Duration::from_secs(3600))
+}
+
+/// Wraps every route registered so far in the hardening stack; routes may
+/// still set a larger `DefaultBodyLimit` of their own
+pub fn harden(router: Router, settings: &HttpSettings) -> Router {
+    let mut router = router
+        .layer(DefaultBodyLimit::max(settings.max_body_bytes))
+        .layer(middleware::from_fn_with_state(Timeouts::from_settings(settings), enforce_timeout))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::X_CONTENT_TYPE_OPTIONS,
+            HeaderValue::from_static("nosniff"),
+        ))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::X_FRAME_OPTIONS,
+            HeaderValue::from_str(&settings.frame_options).unwrap_or(HeaderValue::from_static("DENY")),
+        ));
+
+    if settings.hsts_max_age_secs > 0 {
+        let hsts = format!("max-age={}; includeSubDomains", settings.hsts_max_age_secs);
+        router = router.layer(SetResponseHeaderLayer::if_not_present(
+            header::STRICT_TRANSPORT_SECURITY,
+            HeaderValue::from_str(&hsts).expect("max-age directive is a valid header value"),
+        ));
+    }
+
+    if settings.compression {
+        router = router.layer(CompressionLayer::new());
+    }
+
+    if !settings.cors_allowed_origins.is_empty() {
+        router = router.layer(cors_layer(&settings.cors_allowed_origins));
+    }
+
+    router
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::config::settings::{Profile, RouteTimeout, Settings};
+    use axum::{body::Body, extract::Json, http::StatusCode, routing::{get, post}};
+    use tower::ServiceExt;
+
+    fn settings() -> HttpSettings {
+        HttpSettings {
+            request_timeout_secs: 1,
+            route_timeouts: vec![RouteTimeout {
+                route: "/slow/:id".to_string(),
+                timeout_secs: 3,
+            }],
+            max_body_bytes: 64,
+            max_avatar_bytes: 64,
+            cors_allowed_origins: vec!["https://app.example.com".to_string()],
+            compression: true,
+            hsts_max_age_secs: 600,
+            frame_options: "SAMEORIGIN".to_string(),
+        }
+    }
+
+    async fn sleep_then_ok() -> &'static str {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        "done"
+    }
+
+    async fn echo(body: Result<Json<serde_json::Value>, axum::extract::rejection::JsonRejection>) -> Response {
+        match body {
+            Ok(Json(value)) => Json(value).into_response(),
+            Err(rejection) if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => request_too_large().into_response(),
+            Err(rejection) => rejection.into_response(),
+        }
+    }
+
+    fn app(settings: &HttpSettings) -> Router {
+        let router = Router::new()
+            .route("/fast", get(sleep_then_ok))
+            .route("/slow/:id", get(sleep_then_ok))
+            .route("/echo", post(echo))
+            .route("/large", get(|| async { "brik ".repeat(200) }));
+        harden(router, settings)
+    }
+
+    async fn send(settings: &HttpSettings, request: axum::http::Request<Body>) -> Response {
+        app(settings).oneshot(request).await.unwrap()
+    }
+
+    fn get_request(uri: &str) -> axum::http::Request<Body> {
+        axum::http::Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_default_and_per_route_timeouts() {
+        let timed_out = send(&settings(), get_request("/fast")).await;
+        assert_eq!(timed_out.status(), StatusCode::REQUEST_TIMEOUT);
+
+        let overridden = send(&settings(), get_request("/slow/1")).await;
+        assert_eq!(overridden.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_body_limit() {
+        let post = |body: String| {
+            axum::http::Request::builder()
+                .method("POST")
+                .uri("/echo")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        assert_eq!(send(&settings(), post(r#"{"a":1}"#.to_string())).await.status(), StatusCode::OK);
+        let too_large = send(&settings(), post(format!(r#"{{"a":"{}"}}"#, "x".repeat(100)))).await;
+        assert_eq!(too_large.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_security_headers() {
+        let response = send(&settings(), get_request("/large")).await;
+
+        assert_eq!(response.headers()["x-content-type-options"], "nosniff");
+        assert_eq!(response.headers()["x-frame-options"], "SAMEORIGIN");
+        assert_eq!(response.headers()["strict-transport-security"], "max-age=600; includeSubDomains");
+    }
+
+    #[tokio::test]
+    async fn test_hsts_disabled_by_default_outside_production() {
+        let response = send(&Settings::defaults(Profile::Development).http, get_request("/large")).await;
+
+        assert!(response.headers().get("strict-transport-security").is_none());
+        assert_eq!(response.headers()["x-frame-options"], "DENY");
+    }
+
+    #[tokio::test]
+    async fn test_cors_allow_list() {
+        let preflight = |origin: &str| {
+            axum::http::Request::builder()
+                .method("OPTIONS")
+                .uri("/echo")
+                .header("origin", origin)
+                .header("access-control-request-method", "POST")
+                .header("access-control-request-headers", "authorization,content-type")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let allowed = send(&settings(), preflight("https://app.example.com")).await;
+        assert_eq!(allowed.headers()["access-control-allow-origin"], "https://app.example.com");
+
+        let denied = send(&settings(), preflight("https://evil.example.com")).await;
+        assert!(denied.headers().get("access-control-allow-origin").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_compression() {
+        let request = axum::http::Request::builder()
+            .uri("/large")
+            .header("accept-encoding", "br, gzip")
+            .body(Body::empty())
+            .unwrap();
+
+        let compressed = send(&settings(), request).await;
+        assert_eq!(compressed.headers()["content-encoding"], "br");
+
+        let disabled = HttpSettings { compression: false, ..settings() };
+        let request = axum::http::Request::builder()
+            .uri("/large")
+            .header("accept-encoding", "gzip")
+            .body(Body::empty())
+            .unwrap();
+        assert!(send(&disabled, request).await.headers().get("content-encoding").is_none());
+    }
+}
@@ -0,0 +1,124 @@
+//! BRIK v5 Locale - Language negotiated from Accept-Language
+//!
+//! `negotiate_locale` runs around every request: it picks the best supported
+//! language, makes it available to error responses through `Locale::current`,
+//! records it in the `LogContext` and echoes it in `Content-Language`.
+
+use crate::shared::observability::logger::LogContext;
+use axum::{
+    extract::Request,
+    http::{header, HeaderMap, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use serde::Serialize;
+
+tokio::task_local! {
+    static CURRENT_LOCALE: Locale;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    En,
+    Es,
+}
+
+/// Used when the client sends no Accept-Language or none we support; API
+/// messages were English-only before localization
+pub const DEFAULT_LOCALE: Locale = Locale::En;
+
+impl Locale {
+    pub const ALL: &'static [Locale] = &[Locale::En, Locale::Es];
+
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+        }
+    }
+
+    /// Matches on the primary subtag, so `es-MX` selects `es`
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split(['-', '_']).next().unwrap_or_default();
+        Self::ALL.iter().copied().find(|locale| locale.tag().eq_ignore_ascii_case(primary))
+    }
+
+    /// Highest-weighted supported language of an Accept-Language value;
+    /// earlier entries win ties and `*` stands for the default
+    pub fn negotiate(accept_language: Option<&str>) -> Self {
+        let Some(accept_language) = accept_language else {
+            return DEFAULT_LOCALE;
+        };
+
+        let mut best: Option<(Locale, f32)> = None;
+        for entry in accept_language.split(',') {
+            let mut parts = entry.split(';').map(str::trim);
+            let tag = parts.next().unwrap_or_default();
+            let weight = parts
+                .find_map(|param| param.strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse::<f32>().ok())
+                .unwrap_or(0.0);
+
+            let locale = if tag == "*" { Some(DEFAULT_LOCALE) } else { Self::from_tag(tag) };
+            if let Some(locale) = locale {
+                if weight > 0.0 && best.is_none_or(|(_, best_weight)| weight > best_weight) {
+                    best = Some((locale, weight));
+                }
+            }
+        }
+
+        best.map_or(DEFAULT_LOCALE, |(locale, _)| locale)
+    }
+
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self::negotiate(headers.get(header::ACCEPT_LANGUAGE).and_then(|value| value.to_str().ok()))
+    }
+
+    /// Locale of the request being served, or the default outside one
+    pub fn current() -> Self {
+        CURRENT_LOCALE.try_with(|locale| *locale).unwrap_or(DEFAULT_LOCALE)
+    }
+
+    pub async fn scope<F: std::future::Future>(self, future: F) -> F::Output {
+        CURRENT_LOCALE.scope(self, future).await
+    }
+}
+
+/// Middleware selecting the response language; see the module docs
+pub async fn negotiate_locale(request: Request, next: Next) -> Response {
+    let locale = Locale::from_headers(request.headers());
+    let context = LogContext::current().unwrap_or_default().with_extra("locale", locale);
+
+    let mut response = locale.scope(context.scope(next.run(request))).await;
+    response
+        .headers_mut()
+        .insert(header::CONTENT_LANGUAGE, HeaderValue::from_static(locale.tag()));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_picks_highest_weight() {
+        assert_eq!(Locale::negotiate(Some("en;q=0.5, es-MX;q=0.9, fr")), Locale::Es);
+        assert_eq!(Locale::negotiate(Some("es, en")), Locale::Es);
+        assert_eq!(Locale::negotiate(Some("en-GB,es;q=0.8")), Locale::En);
+    }
+
+    #[test]
+    fn test_negotiate_falls_back_to_default() {
+        assert_eq!(Locale::negotiate(None), DEFAULT_LOCALE);
+        assert_eq!(Locale::negotiate(Some("fr-FR, de;q=0.9")), DEFAULT_LOCALE);
+        assert_eq!(Locale::negotiate(Some("es;q=0, *;q=0.1")), DEFAULT_LOCALE);
+        assert_eq!(Locale::negotiate(Some("es;q=abc")), DEFAULT_LOCALE);
+    }
+
+    #[tokio::test]
+    async fn test_current_inside_scope() {
+        assert_eq!(Locale::current(), DEFAULT_LOCALE);
+        assert_eq!(Locale::Es.scope(async { Locale::current() }).await, Locale::Es);
+    }
+}
@@ -0,0 +1,137 @@
+//! BRIK v5 Message Catalogs - Error messages per locale, keyed by error code
+//!
+//! English templates are the catalog defaults (`ErrorCode::default_message`).
+//! Templates may contain `{name}` placeholders filled from the error's params.
+//!
+//! Only messages built from the catalog are translated. An error whose
+//! message differs from its English template carries a specific detail
+//! (a schema rejection, a validation reason) and is returned as written.
+
+use super::locale::Locale;
+use crate::shared::errors::error_code::ErrorCode;
+
+fn spanish(code: ErrorCode) -> &'static str {
+    match code {
+        ErrorCode::AuthTokenMissing => "Se requiere la cabecera Authorization con un token Bearer",
+        ErrorCode::AuthTokenInvalid => "El token JWT no es válido o ha expirado",
+        ErrorCode::AuthInsufficientScopes => "Falta el scope requerido: {scope}",
+        ErrorCode::AuthTenantMissing => "El token no indica un tenant válido",
+        ErrorCode::TenantSuspended => "Este tenant está suspendido",
+        ErrorCode::ValidationFailed => "La petición no cumple el esquema esperado",
+        ErrorCode::InvalidQuery => "Uno o más parámetros de consulta no son válidos",
+        ErrorCode::IdempotencyKeyInvalid => {
+            "La cabecera idempotency-key es obligatoria (1-255 caracteres, [a-zA-Z0-9_-])"
+        }
+        ErrorCode::WebsocketUpgradeRequired => "Este endpoint solo acepta conexiones WebSocket",
+        ErrorCode::AvatarMissing => "El cuerpo multipart no contiene el campo avatar",
+        ErrorCode::AvatarTooLarge => "El avatar supera el tamaño máximo permitido de {max_bytes} bytes",
+        ErrorCode::AvatarUnsupportedType => "El avatar no es una imagen JPEG, PNG o WebP",
+        ErrorCode::AvatarInvalidImage => "La imagen del avatar está dañada o incompleta",
+        ErrorCode::GateResultEmpty => "El gate no devolvió ni datos ni error",
+        ErrorCode::RequestTimeout => "La petición tardó demasiado en procesarse",
+        ErrorCode::RequestTooLarge => "El cuerpo de la petición supera el tamaño máximo",
+        ErrorCode::BulkheadFull => "Demasiadas peticiones simultáneas a este endpoint, inténtalo más tarde",
+        ErrorCode::LoadShed => "El servidor está sobrecargado, inténtalo más tarde",
+        ErrorCode::AvatarNotOwned => "Solo el propio usuario o un administrador puede cambiar este avatar",
+        ErrorCode::InvalidUserName => "El nombre debe tener entre 2 y 100 caracteres sin caracteres de control",
+        ErrorCode::InvalidUserAge => "La edad debe estar entre 13 y 150 años",
+        ErrorCode::InvalidUserEmail => "El email es obligatorio y debe ser una dirección válida",
+        ErrorCode::InvalidUserProfile => "Los campos del perfil son demasiado largos o no son URLs válidas",
+        ErrorCode::UserNotFound => "El usuario no existe",
+        ErrorCode::UserAlreadyExists => "Ya existe un usuario con este email",
+        ErrorCode::UserVersionConflict => "Otra petición modificó el usuario, reintenta la operación",
+        ErrorCode::UserDeletionTooEarly => "No se puede eliminar un usuario durante la primera hora tras su creación",
+        ErrorCode::InvalidUser => "Varios campos del usuario no son válidos, ver errors",
+        ErrorCode::DatabaseUnavailable => "No se pudo conectar con la base de datos",
+        ErrorCode::DatabaseError => "La base de datos rechazó la operación",
+        ErrorCode::RowMappingFailed => "No se pudo leer un registro almacenado",
+        ErrorCode::UniqueViolation => "Se violó una restricción de unicidad",
+        ErrorCode::VersionConflict => "El registro fue modificado de forma concurrente",
+        ErrorCode::NotFound => "El registro a modificar no existe",
+        ErrorCode::CacheUnavailable => "No se pudo conectar con la caché",
+        ErrorCode::StorageUnavailable => "No se pudo conectar con el almacenamiento de ficheros",
+        ErrorCode::InvalidKey => "La clave del fichero no es válida",
+        ErrorCode::PublishFailed => "No se pudo publicar un evento",
+        ErrorCode::HashFailed => "No se pudo calcular el hash de la contraseña",
+        ErrorCode::HasherUnavailable => "El servicio de hash de contraseñas no está disponible",
+        ErrorCode::InvalidHash => "El hash de contraseña almacenado está dañado",
+        ErrorCode::UnsupportedHash => "El hash de contraseña almacenado tiene un formato desconocido",
+        ErrorCode::CircuitOpen => "El servicio no está disponible temporalmente, inténtalo más tarde",
+        ErrorCode::DeadlineExceeded => "Una dependencia no respondió a tiempo",
+        ErrorCode::InternalError => "Se produjo un error inesperado",
+    }
+}
+
+pub fn template(code: ErrorCode, locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => code.default_message(),
+        Locale::Es => spanish(code),
+    }
+}
+
+/// Fills `{name}` placeholders; unknown placeholders are left as they are
+pub fn render<K, V>(code: ErrorCode, locale: Locale, params: &[(K, V)]) -> String
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    params.iter().fold(template(code, locale).to_string(), |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name.as_ref()), value.as_ref())
+    })
+}
+
+/// Message of an error response in `locale`; see the module docs for which
+/// messages are translated
+pub fn localize(code: &str, message: &str, params: &[(String, String)], locale: Locale) -> String {
+    match ErrorCode::parse(code) {
+        Some(code) if render(code, Locale::En, params) == message => render(code, locale, params),
+        _ => message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(template: &str) -> Vec<&str> {
+        let mut names: Vec<_> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_every_locale_uses_the_same_placeholders() {
+        for code in ErrorCode::ALL {
+            let english = placeholders(template(*code, Locale::En));
+            for locale in Locale::ALL {
+                assert_eq!(placeholders(template(*code, *locale)), english, "{} in {:?}", code.as_str(), locale);
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_interpolates_params() {
+        let message = render(ErrorCode::AuthInsufficientScopes, Locale::Es, &[("scope", "users:create")]);
+
+        assert_eq!(message, "Falta el scope requerido: users:create");
+    }
+
+    #[test]
+    fn test_localize_translates_catalog_messages_only() {
+        let params = vec![("scope".to_string(), "users:read".to_string())];
+
+        assert_eq!(
+            localize("AUTH_INSUFFICIENT_SCOPES", "Missing required scope: users:read", &params, Locale::Es),
+            "Falta el scope requerido: users:read"
+        );
+        assert_eq!(
+            localize("INVALID_USER_NAME", "Name must not exceed 100 characters", &[], Locale::Es),
+            "Name must not exceed 100 characters"
+        );
+        assert_eq!(localize("CUSTOM_CODE", "Something", &[], Locale::Es), "Something");
+    }
+}
@@ -0,0 +1,165 @@
+//! BRIK v5 Id Generator - Where entity ids come from
+//!
+//! Entity constructors ask an `IdGenerator` instead of calling
+//! `Uuid::new_v4()`. The default is UUIDv7: ids start with their creation
+//! time in milliseconds, so they sort by age, append to the right of B-tree
+//! indexes and make a stable tiebreaker for cursor pagination. `ids.version
+//! = "v4"` keeps fully random ids; `SequentialIdGenerator` gives tests the
+//! same ids on every run.
+
+use crate::shared::config::settings::IdSettings;
+use crate::shared::time::clock::{SharedClock, SystemClock};
+use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use uuid::{timestamp::context::ContextV7, Builder, Timestamp, Uuid};
+
+pub trait IdGenerator: Send + Sync {
+    fn generate(&self) -> Uuid;
+}
+
+pub type SharedIdGenerator = Arc<dyn IdGenerator>;
+
+/// Time-ordered ids; ids made within the same millisecond still increase
+pub struct UuidV7Generator {
+    clock: SharedClock,
+    context: Mutex<ContextV7>,
+}
+
+impl UuidV7Generator {
+    pub fn new(clock: SharedClock) -> Self {
+        Self {
+            clock,
+            context: Mutex::new(ContextV7::new()),
+        }
+    }
+}
+
+impl Default for UuidV7Generator {
+    fn default() -> Self {
+        Self::new(SystemClock::shared())
+    }
+}
+
+impl IdGenerator for UuidV7Generator {
+    fn generate(&self) -> Uuid {
+        let now = self.clock.now();
+        // Before the epoch only happens with a badly set manual clock
+        let seconds = u64::try_from(now.timestamp()).unwrap_or(0);
+        let context = self.context.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        Uuid::new_v7(Timestamp::from_unix(&*context, seconds, now.timestamp_subsec_nanos()))
+    }
+}
+
+/// Random ids with no embedded time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidV4Generator;
+
+impl IdGenerator for UuidV4Generator {
+    fn generate(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+/// Deterministic UUIDv7 ids for tests: the clock's millisecond followed by
+/// a counter, so the same clock readings always give the same ids
+pub struct SequentialIdGenerator {
+    clock: SharedClock,
+    next: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    pub fn new(clock: SharedClock) -> Self {
+        Self {
+            clock,
+            next: AtomicU64::new(1),
+        }
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn generate(&self) -> Uuid {
+        let millis = u64::try_from(self.clock.now().timestamp_millis()).unwrap_or(0);
+        let mut counter = [0u8; 10];
+        counter[2..].copy_from_slice(&self.next.fetch_add(1, Ordering::Relaxed).to_be_bytes());
+        Builder::from_unix_timestamp_millis(millis, &counter).into_uuid()
+    }
+}
+
+/// When a time-ordered id was generated, to the millisecond; `None` for
+/// random (v4) ids
+pub fn id_created_at(id: &Uuid) -> Option<DateTime<Utc>> {
+    let (seconds, nanos) = id.get_timestamp()?.to_unix();
+    DateTime::from_timestamp(i64::try_from(seconds).ok()?, nanos)
+}
+
+/// Generator selected by `ids.version`; settings are validated at load
+pub fn id_generator_from_settings(settings: &IdSettings, clock: SharedClock) -> SharedIdGenerator {
+    match settings.version.as_str() {
+        "v4" => Arc::new(UuidV4Generator),
+        _ => Arc::new(UuidV7Generator::new(clock)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::time::clock::ManualClock;
+    use chrono::TimeZone;
+    use std::time::Duration;
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_v7_ids_are_ordered_and_carry_their_creation_time() {
+        let clock = ManualClock::new(start());
+        let ids = UuidV7Generator::new(clock.clone());
+
+        let first = ids.generate();
+        let second = ids.generate();
+        clock.advance(Duration::from_secs(5));
+        let third = ids.generate();
+
+        assert_eq!(first.get_version_num(), 7);
+        assert!(first < second && second < third);
+        assert_eq!(id_created_at(&first), Some(start()));
+        assert_eq!(id_created_at(&third), Some(start() + chrono::Duration::seconds(5)));
+    }
+
+    #[test]
+    fn test_v4_ids_have_no_creation_time() {
+        let id = UuidV4Generator.generate();
+
+        assert_eq!(id.get_version_num(), 4);
+        assert_eq!(id_created_at(&id), None);
+    }
+
+    #[test]
+    fn test_sequential_ids_repeat_for_the_same_clock() {
+        let generate = || {
+            let clock = ManualClock::new(start());
+            let ids = SequentialIdGenerator::new(clock.clone());
+            let first = ids.generate();
+            clock.advance(Duration::from_millis(1));
+            [first, ids.generate()]
+        };
+
+        let [first, second] = generate();
+        assert_eq!([first, second], generate());
+        assert!(first < second);
+        assert_eq!(first.get_version_num(), 7);
+        assert_eq!(id_created_at(&first), Some(start()));
+    }
+
+    #[test]
+    fn test_settings_select_the_version() {
+        let clock = ManualClock::new(start());
+        let v4 = id_generator_from_settings(&IdSettings { version: "v4".to_string() }, clock.clone());
+        let v7 = id_generator_from_settings(&IdSettings { version: "v7".to_string() }, clock);
+
+        assert_eq!(v4.generate().get_version_num(), 4);
+        assert_eq!(v7.generate().get_version_num(), 7);
+    }
+}
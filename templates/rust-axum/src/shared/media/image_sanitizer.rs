@@ -0,0 +1,369 @@
+//! BRIK v5 Image Sanitizer - Magic-byte sniffing and metadata stripping
+//!
+//! Uploaded images are identified by their leading bytes, never by the
+//! client's content type or file name. Metadata (EXIF, XMP, IPTC, comments,
+//! text chunks) is removed by rewriting the container without re-encoding, so
+//! pixels and colour profiles are kept intact.
+
+use crate::shared::types::result::BrikResult;
+use thiserror::Error;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Jpeg,
+    Png,
+    WebP,
+}
+
+impl ImageFormat {
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(ImageFormat::Jpeg)
+        } else if bytes.starts_with(PNG_SIGNATURE) {
+            Some(ImageFormat::Png)
+        } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            Some(ImageFormat::WebP)
+        } else {
+            None
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ImageFormat::Jpeg => "image/jpeg",
+            ImageFormat::Png => "image/png",
+            ImageFormat::WebP => "image/webp",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Png => "png",
+            ImageFormat::WebP => "webp",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ImageError {
+    #[error("Unsupported image format (expected JPEG, PNG or WebP)")]
+    Unsupported,
+
+    #[error("Malformed {format} image: {reason}")]
+    Malformed { format: &'static str, reason: &'static str },
+}
+
+/// Sniffs the format and returns it with a metadata-free copy of the image
+pub fn sanitize_image(bytes: &[u8]) -> BrikResult<(ImageFormat, Vec<u8>), ImageError> {
+    let format = ImageFormat::sniff(bytes).ok_or(ImageError::Unsupported)?;
+
+    let stripped = match format {
+        ImageFormat::Jpeg => strip_jpeg(bytes),
+        ImageFormat::Png => strip_png(bytes),
+        ImageFormat::WebP => strip_webp(bytes),
+    }?;
+
+    Ok((format, stripped))
+}
+
+fn malformed(format: &'static str, reason: &'static str) -> ImageError {
+    ImageError::Malformed { format, reason }
+}
+
+fn read_u16_be(bytes: &[u8], at: usize) -> Option<usize> {
+    bytes.get(at..at + 2).map(|b| u16::from_be_bytes([b[0], b[1]]) as usize)
+}
+
+fn read_u32_be(bytes: &[u8], at: usize) -> Option<usize> {
+    bytes.get(at..at + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize)
+}
+
+fn read_u32_le(bytes: &[u8], at: usize) -> Option<usize> {
+    bytes.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+}
+
+/// Offset of the first marker after the entropy-coded data at `from`;
+/// stuffed `FF 00` bytes and restart markers belong to the data
+fn entropy_coded_end(bytes: &[u8], from: usize) -> Option<usize> {
+    let mut position = from;
+    loop {
+        position += bytes.get(position..)?.iter().position(|byte| *byte == 0xFF)?;
+        match *bytes.get(position + 1)? {
+            0x00 | 0xD0..=0xD7 => position += 2,
+            _ => return Some(position),
+        }
+    }
+}
+
+/// Drops APP1-APP15 and COM segments, keeping JFIF (APP0), ICC profiles
+/// (APP2) and the Adobe colour transform (APP14); everything after EOI is
+/// discarded
+fn strip_jpeg(bytes: &[u8]) -> BrikResult<Vec<u8>, ImageError> {
+    const FORMAT: &str = "JPEG";
+    let mut output = Vec::with_capacity(bytes.len());
+    output.extend_from_slice(&bytes[..2]);
+    let mut position = 2;
+
+    loop {
+        if bytes.get(position) != Some(&0xFF) {
+            return Err(malformed(FORMAT, "expected a segment marker"));
+        }
+        let marker = *bytes.get(position + 1).ok_or_else(|| malformed(FORMAT, "truncated marker"))?;
+
+        match marker {
+            // Fill byte before the actual marker
+            0xFF => {
+                position += 1;
+                continue;
+            }
+            0xD9 => {
+                output.extend_from_slice(&bytes[position..position + 2]);
+                return Ok(output);
+            }
+            0x01 | 0xD0..=0xD7 => {
+                output.extend_from_slice(&bytes[position..position + 2]);
+                position += 2;
+                continue;
+            }
+            _ => {}
+        }
+
+        let length = read_u16_be(bytes, position + 2).ok_or_else(|| malformed(FORMAT, "truncated segment"))?;
+        let end = position + 2 + length;
+        if length < 2 || end > bytes.len() {
+            return Err(malformed(FORMAT, "segment exceeds file size"));
+        }
+
+        let payload = &bytes[position + 4..end];
+        let keep = match marker {
+            0xE2 => payload.starts_with(b"ICC_PROFILE\0"),
+            0xEE => payload.starts_with(b"Adobe"),
+            0xE1..=0xEF | 0xFE => false,
+            _ => true,
+        };
+        if keep {
+            output.extend_from_slice(&bytes[position..end]);
+        }
+        position = end;
+
+        // Start of scan: entropy-coded data runs up to the next marker
+        if marker == 0xDA {
+            position = entropy_coded_end(bytes, end).ok_or_else(|| malformed(FORMAT, "missing EOI marker"))?;
+            output.extend_from_slice(&bytes[end..position]);
+        }
+    }
+}
+
+/// Drops textual, EXIF and timestamp chunks; everything after IEND is discarded
+fn strip_png(bytes: &[u8]) -> BrikResult<Vec<u8>, ImageError> {
+    const FORMAT: &str = "PNG";
+    const METADATA_CHUNKS: [&[u8]; 5] = [b"tEXt", b"zTXt", b"iTXt", b"eXIf", b"tIME"];
+
+    let mut output = Vec::with_capacity(bytes.len());
+    output.extend_from_slice(PNG_SIGNATURE);
+    let mut position = PNG_SIGNATURE.len();
+
+    loop {
+        let length = read_u32_be(bytes, position).ok_or_else(|| malformed(FORMAT, "missing IEND chunk"))?;
+        let chunk_type = bytes
+            .get(position + 4..position + 8)
+            .ok_or_else(|| malformed(FORMAT, "truncated chunk"))?;
+        // Length, type, data and CRC
+        let end = position
+            .checked_add(12 + length)
+            .filter(|end| *end <= bytes.len())
+            .ok_or_else(|| malformed(FORMAT, "chunk exceeds file size"))?;
+
+        if !METADATA_CHUNKS.contains(&chunk_type) {
+            output.extend_from_slice(&bytes[position..end]);
+        }
+        if chunk_type == b"IEND" {
+            return Ok(output);
+        }
+        position = end;
+    }
+}
+
+/// Drops EXIF and XMP chunks and clears their flags in the VP8X header
+fn strip_webp(bytes: &[u8]) -> BrikResult<Vec<u8>, ImageError> {
+    const FORMAT: &str = "WebP";
+    const VP8X_EXIF_FLAG: u8 = 0x08;
+    const VP8X_XMP_FLAG: u8 = 0x04;
+
+    let riff_end = read_u32_le(bytes, 4)
+        .and_then(|size| size.checked_add(8))
+        .filter(|end| *end <= bytes.len())
+        .ok_or_else(|| malformed(FORMAT, "RIFF size exceeds file size"))?;
+
+    let mut output = Vec::with_capacity(riff_end);
+    output.extend_from_slice(&bytes[..12]);
+    let mut position = 12;
+
+    while position < riff_end {
+        let fourcc = bytes
+            .get(position..position + 4)
+            .ok_or_else(|| malformed(FORMAT, "truncated chunk"))?;
+        let size = read_u32_le(bytes, position + 4).ok_or_else(|| malformed(FORMAT, "truncated chunk"))?;
+        // Chunks are padded to an even size
+        let end = position
+            .checked_add(8 + size + (size & 1))
+            .filter(|end| *end <= riff_end)
+            .ok_or_else(|| malformed(FORMAT, "chunk exceeds RIFF size"))?;
+
+        match fourcc {
+            b"EXIF" | b"XMP " => {}
+            b"VP8X" => {
+                let start = output.len();
+                output.extend_from_slice(&bytes[position..end]);
+                if let Some(flags) = output.get_mut(start + 8) {
+                    *flags &= !(VP8X_EXIF_FLAG | VP8X_XMP_FLAG);
+                }
+            }
+            _ => output.extend_from_slice(&bytes[position..end]),
+        }
+        position = end;
+    }
+
+    let riff_size = u32::try_from(output.len() - 8).map_err(|_| malformed(FORMAT, "file too large"))?;
+    output[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jpeg_segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+        let mut segment = vec![0xFF, marker];
+        segment.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        segment.extend_from_slice(payload);
+        segment
+    }
+
+    fn png_chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(chunk_type);
+        chunk.extend_from_slice(data);
+        // CRC is carried over untouched, its value does not matter here
+        chunk.extend_from_slice(&[0, 0, 0, 0]);
+        chunk
+    }
+
+    fn webp_chunk(fourcc: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut chunk = fourcc.to_vec();
+        chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        chunk.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            chunk.push(0);
+        }
+        chunk
+    }
+
+    fn webp(chunks: &[Vec<u8>]) -> Vec<u8> {
+        let body: Vec<u8> = chunks.concat();
+        let mut file = b"RIFF".to_vec();
+        file.extend_from_slice(&((body.len() + 4) as u32).to_le_bytes());
+        file.extend_from_slice(b"WEBP");
+        file.extend_from_slice(&body);
+        file
+    }
+
+    #[test]
+    fn test_sniff_ignores_client_claims() {
+        assert_eq!(ImageFormat::sniff(&[0xFF, 0xD8, 0xFF, 0xE0]), Some(ImageFormat::Jpeg));
+        assert_eq!(ImageFormat::sniff(PNG_SIGNATURE), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::sniff(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>"), None);
+        assert_eq!(sanitize_image(b"GIF89a"), Err(ImageError::Unsupported));
+    }
+
+    #[test]
+    fn test_strip_jpeg_metadata() {
+        let jfif = jpeg_segment(0xE0, b"JFIF\0\x01\x01");
+        let exif = jpeg_segment(0xE1, b"Exif\0\0GPS");
+        let icc = jpeg_segment(0xE2, b"ICC_PROFILE\0\x01\x01");
+        let comment = jpeg_segment(0xFE, b"shot on my phone");
+        let quantization = jpeg_segment(0xDB, &[0; 4]);
+        let scan = [jpeg_segment(0xDA, &[1, 2, 3]), vec![0xAB, 0xCD, 0xFF, 0xD9]].concat();
+        let image = [vec![0xFF, 0xD8], jfif.clone(), exif, icc.clone(), comment, quantization.clone(), scan.clone()].concat();
+
+        let (format, stripped) = sanitize_image(&image).unwrap();
+
+        assert_eq!(format, ImageFormat::Jpeg);
+        assert_eq!(stripped, [vec![0xFF, 0xD8], jfif, icc, quantization, scan].concat());
+    }
+
+    #[test]
+    fn test_strip_jpeg_drops_bytes_after_eoi() {
+        let quantization = jpeg_segment(0xDB, &[0; 4]);
+        // Stuffed 0xFF and a restart marker are part of the scan data
+        let scan = [jpeg_segment(0xDA, &[1, 2, 3]), vec![0xAB, 0xFF, 0x00, 0xFF, 0xD0, 0xCD]].concat();
+        let second_scan = [jpeg_segment(0xC4, &[0; 3]), jpeg_segment(0xDA, &[4, 5, 6]), vec![0xEF]].concat();
+        let image = [
+            vec![0xFF, 0xD8],
+            quantization.clone(),
+            scan.clone(),
+            second_scan.clone(),
+            vec![0xFF, 0xD9],
+            b"<?php system($_GET['c']); ?>".to_vec(),
+        ]
+        .concat();
+
+        let (_, stripped) = sanitize_image(&image).unwrap();
+
+        assert_eq!(stripped, [vec![0xFF, 0xD8], quantization, scan, second_scan, vec![0xFF, 0xD9]].concat());
+        assert!(matches!(
+            sanitize_image(&image[..image.len() - 30]),
+            Err(ImageError::Malformed { format: "JPEG", .. })
+        ));
+    }
+
+    #[test]
+    fn test_strip_jpeg_rejects_truncated_segment() {
+        let image = [vec![0xFF, 0xD8, 0xFF, 0xE1, 0x10, 0x00], vec![0; 8]].concat();
+
+        assert!(matches!(sanitize_image(&image), Err(ImageError::Malformed { format: "JPEG", .. })));
+    }
+
+    #[test]
+    fn test_strip_png_metadata() {
+        let header = png_chunk(b"IHDR", &[0; 13]);
+        let data = png_chunk(b"IDAT", &[1, 2, 3]);
+        let end = png_chunk(b"IEND", &[]);
+        let image = [
+            PNG_SIGNATURE.to_vec(),
+            header.clone(),
+            png_chunk(b"tEXt", b"Author\0Jane"),
+            png_chunk(b"eXIf", b"MM\0*"),
+            data.clone(),
+            end.clone(),
+            b"trailing".to_vec(),
+        ]
+        .concat();
+
+        let (_, stripped) = sanitize_image(&image).unwrap();
+
+        assert_eq!(stripped, [PNG_SIGNATURE.to_vec(), header, data, end].concat());
+    }
+
+    #[test]
+    fn test_strip_webp_metadata() {
+        let mut flags = vec![0x0C, 0, 0, 0];
+        flags.extend_from_slice(&[0; 6]);
+        let image = webp(&[
+            webp_chunk(b"VP8X", &flags),
+            webp_chunk(b"VP8 ", &[9; 5]),
+            webp_chunk(b"EXIF", b"MM\0*"),
+            webp_chunk(b"XMP ", b"<x:xmpmeta/>"),
+        ]);
+
+        let (format, stripped) = sanitize_image(&image).unwrap();
+
+        let mut cleared = flags.clone();
+        cleared[0] = 0;
+        assert_eq!(format, ImageFormat::WebP);
+        assert_eq!(stripped, webp(&[webp_chunk(b"VP8X", &cleared), webp_chunk(b"VP8 ", &[9; 5])]));
+    }
+}
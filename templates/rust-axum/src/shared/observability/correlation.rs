@@ -0,0 +1,63 @@
+//! BRIK v5 Correlation ID handling
+
+use axum::http::HeaderMap;
+use uuid::Uuid;
+
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+const MAX_CORRELATION_ID_LENGTH: usize = 128;
+
+/// Returns the caller's correlation ID when it matches `^[a-zA-Z0-9_-]+$`,
+/// otherwise generates a new one
+pub fn correlation_id_from(headers: &HeaderMap) -> String {
+    headers
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_correlation_id(value))
+        .map(str::to_string)
+        .unwrap_or_else(generate_correlation_id)
+}
+
+pub fn generate_correlation_id() -> String {
+    format!("req_{}", Uuid::new_v4().simple())
+}
+
+fn is_valid_correlation_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_CORRELATION_ID_LENGTH
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_correlation_id_from_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CORRELATION_ID_HEADER, HeaderValue::from_static("req_abc123def456"));
+
+        assert_eq!(correlation_id_from(&headers), "req_abc123def456");
+    }
+
+    #[test]
+    fn test_invalid_correlation_id_is_replaced() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CORRELATION_ID_HEADER, HeaderValue::from_static("bad id; drop"));
+
+        let id = correlation_id_from(&headers);
+        assert!(id.starts_with("req_"));
+        assert_ne!(id, "bad id; drop");
+    }
+
+    #[test]
+    fn test_missing_correlation_id_is_generated() {
+        let id = correlation_id_from(&HeaderMap::new());
+
+        assert!(id.starts_with("req_"));
+        assert!(is_valid_correlation_id(&id));
+    }
+}
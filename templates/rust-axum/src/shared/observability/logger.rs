@@ -1,8 +1,19 @@
 //! BRIK v5 Structured Logger with Correlation ID support
+//!
+//! `brik_log!` is the hot path: typed fields go straight to `tracing`, the
+//! task's `LogContext` is borrowed, and a disabled level costs one check.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::{debug, error, info, warn};
+use std::fmt;
+use std::future::Future;
+use std::io;
+use tracing::field::{self, DisplayValue};
+use tracing::Level;
+
+tokio::task_local! {
+    static CURRENT_CONTEXT: LogContext;
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogContext {
@@ -70,98 +81,150 @@ impl LogContext {
     }
 }
 
+impl LogContext {
+    /// Runs `future` with this context as the one returned by `current`
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT_CONTEXT.scope(self, future).await
+    }
+
+    /// Context of the enclosing `scope`, if any
+    pub fn current() -> Option<LogContext> {
+        CURRENT_CONTEXT.try_with(Clone::clone).ok()
+    }
+
+    /// Borrows the context of the enclosing `scope` instead of cloning it
+    pub fn with_current<R>(f: impl FnOnce(Option<&LogContext>) -> R) -> R {
+        // `try_with` only runs its closure when a context is set
+        let mut f = Some(f);
+        CURRENT_CONTEXT
+            .try_with(|context| f.take().expect("runs once")(Some(context)))
+            .unwrap_or_else(|_| f.take().expect("runs once")(None))
+    }
+}
+
 impl Default for LogContext {
     fn default() -> Self {
         Self::new()
     }
 }
 
-/// BRIK structured logger
+/// `extra` rendered as a JSON object, and only when a subscriber records it
+pub struct ExtraFields<'a>(&'a HashMap<String, serde_json::Value>);
+
+impl<'a> ExtraFields<'a> {
+    pub fn of(context: &'a LogContext) -> Option<DisplayValue<ExtraFields<'a>>> {
+        (!context.extra.is_empty()).then(|| field::display(ExtraFields(&context.extra)))
+    }
+}
+
+impl fmt::Display for ExtraFields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        serde_json::to_writer(FormatterWriter(f), self.0).map_err(|_| fmt::Error)
+    }
+}
+
+/// Lets serde_json write straight into a `Formatter`
+struct FormatterWriter<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+impl io::Write for FormatterWriter<'_, '_> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        let text = std::str::from_utf8(bytes).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        self.0.write_str(text).map_err(io::Error::other)?;
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Logs at a `tracing::Level` (`TRACE` to `ERROR`) with typed per-call fields
+/// plus `correlation_id`, `user_id`, `endpoint` and `extra` from the current
+/// `LogContext`, which is borrowed rather than cloned. Fields use the
+/// `tracing` syntax and are recorded as they are, not through `Debug`:
+///
+/// ```ignore
+/// brik_log!(WARN, "Outbox relay pass failed", port = %error.port, attempts = attempts);
+/// ```
+///
+/// A context field the call also passes is taken from the call only, so
+/// the JSON line never carries the same key twice.
+///
+/// When the level is disabled nothing past the level check runs, the field
+/// expressions included.
+#[macro_export]
+macro_rules! brik_log {
+    // Whether a top-level field of the call is named `$name`
+    (@passes $name:ident;) => { false };
+    (@passes $name:ident; % $($rest:tt)+) => { $crate::brik_log!(@passes $name; $($rest)+) };
+    (@passes $name:ident; ? $($rest:tt)+) => { $crate::brik_log!(@passes $name; $($rest)+) };
+    (@passes correlation_id; correlation_id $($rest:tt)*) => { true };
+    (@passes user_id; user_id $($rest:tt)*) => { true };
+    (@passes endpoint; endpoint $($rest:tt)*) => { true };
+    (@passes $name:ident; $($rest:tt)+) => { $crate::brik_log!(@next_field $name; $($rest)+) };
+    (@next_field $name:ident;) => { false };
+    (@next_field $name:ident; , $($rest:tt)*) => { $crate::brik_log!(@passes $name; $($rest)*) };
+    (@next_field $name:ident; $_token:tt $($rest:tt)*) => { $crate::brik_log!(@next_field $name; $($rest)*) };
+
+    ($level:ident, $message:expr $(, $($fields:tt)+)?) => {
+        if ::tracing::enabled!(::tracing::Level::$level) {
+            $crate::shared::observability::logger::LogContext::with_current(|context| {
+                let unless_passed = |passed: bool| context.filter(|_| !passed);
+                ::tracing::event!(
+                    ::tracing::Level::$level,
+                    correlation_id = unless_passed($crate::brik_log!(@passes correlation_id; $($($fields)+)?))
+                        .and_then(|context| context.correlation_id.as_deref()),
+                    user_id = unless_passed($crate::brik_log!(@passes user_id; $($($fields)+)?))
+                        .and_then(|context| context.user_id.as_deref()),
+                    endpoint = unless_passed($crate::brik_log!(@passes endpoint; $($($fields)+)?))
+                        .and_then(|context| context.endpoint.as_deref()),
+                    extra = context.and_then($crate::shared::observability::logger::ExtraFields::of),
+                    $($($fields)+,)?
+                    message = $message
+                )
+            })
+        }
+    };
+}
+
+/// Records every field of an explicit context; absent fields are left out
+macro_rules! record_context {
+    ($level:expr, $message:expr, $context:expr, $error:expr) => {{
+        let context: Option<&LogContext> = $context;
+        tracing::event!(
+            $level,
+            correlation_id = context.and_then(|context| context.correlation_id.as_deref()),
+            user_id = context.and_then(|context| context.user_id.as_deref()),
+            endpoint = context.and_then(|context| context.endpoint.as_deref()),
+            gate = context.and_then(|context| context.gate.as_deref()),
+            port = context.and_then(|context| context.port.as_deref()),
+            duration_ms = context.and_then(|context| context.duration_ms),
+            extra = context.and_then(ExtraFields::of),
+            error = $error,
+            message = $message
+        )
+    }};
+}
+
+/// BRIK structured logger for an explicit `LogContext`; prefer `brik_log!`
+/// when the fields are known at the call site
 pub struct BrikLogger;
 
 impl BrikLogger {
-    pub fn info(message: &str, context: Option<LogContext>) {
-        if let Some(ctx) = context {
-            info!(
-                correlation_id = ?ctx.correlation_id,
-                user_id = ?ctx.user_id,
-                endpoint = ?ctx.endpoint,
-                gate = ?ctx.gate,
-                port = ?ctx.port,
-                duration_ms = ?ctx.duration_ms,
-                extra = ?ctx.extra,
-                message
-            );
-        } else {
-            info!(message);
-        }
+    pub fn info(message: &str, context: Option<&LogContext>) {
+        record_context!(Level::INFO, message, context, None::<&str>);
     }
 
-    pub fn warn(message: &str, context: Option<LogContext>) {
-        if let Some(ctx) = context {
-            warn!(
-                correlation_id = ?ctx.correlation_id,
-                user_id = ?ctx.user_id,
-                endpoint = ?ctx.endpoint,
-                gate = ?ctx.gate,
-                port = ?ctx.port,
-                duration_ms = ?ctx.duration_ms,
-                extra = ?ctx.extra,
-                message
-            );
-        } else {
-            warn!(message);
-        }
+    pub fn warn(message: &str, context: Option<&LogContext>) {
+        record_context!(Level::WARN, message, context, None::<&str>);
     }
 
-    pub fn error(message: &str, error: Option<&dyn std::error::Error>, context: Option<LogContext>) {
-        if let Some(ctx) = context {
-            if let Some(err) = error {
-                error!(
-                    correlation_id = ?ctx.correlation_id,
-                    user_id = ?ctx.user_id,
-                    endpoint = ?ctx.endpoint,
-                    gate = ?ctx.gate,
-                    port = ?ctx.port,
-                    duration_ms = ?ctx.duration_ms,
-                    extra = ?ctx.extra,
-                    error = %err,
-                    message
-                );
-            } else {
-                error!(
-                    correlation_id = ?ctx.correlation_id,
-                    user_id = ?ctx.user_id,
-                    endpoint = ?ctx.endpoint,
-                    gate = ?ctx.gate,
-                    port = ?ctx.port,
-                    duration_ms = ?ctx.duration_ms,
-                    extra = ?ctx.extra,
-                    message
-                );
-            }
-        } else if let Some(err) = error {
-            error!(error = %err, message);
-        } else {
-            error!(message);
-        }
+    pub fn error(message: &str, error: Option<&dyn std::error::Error>, context: Option<&LogContext>) {
+        record_context!(Level::ERROR, message, context, error.map(field::display));
     }
 
-    pub fn debug(message: &str, context: Option<LogContext>) {
-        if let Some(ctx) = context {
-            debug!(
-                correlation_id = ?ctx.correlation_id,
-                user_id = ?ctx.user_id,
-                endpoint = ?ctx.endpoint,
-                gate = ?ctx.gate,
-                port = ?ctx.port,
-                duration_ms = ?ctx.duration_ms,
-                extra = ?ctx.extra,
-                message
-            );
-        } else {
-            debug!(message);
-        }
+    pub fn debug(message: &str, context: Option<&LogContext>) {
+        record_context!(Level::DEBUG, message, context, None::<&str>);
     }
 }
 
@@ -184,6 +247,44 @@ pub fn init_logger() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// JSON lines written by a subscriber enabled up to `max_level`, for tests
+/// that assert on what was logged
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub(crate) struct Captured(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+#[cfg(test)]
+impl io::Write for Captured {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl Captured {
+    pub(crate) fn subscriber(&self, max_level: Level) -> impl tracing::Subscriber {
+        let writer = self.clone();
+        tracing_subscriber::fmt()
+            .json()
+            .with_max_level(max_level)
+            .with_writer(move || writer.clone())
+            .finish()
+    }
+
+    pub(crate) fn lines(&self) -> Vec<serde_json::Value> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,4 +318,108 @@ mod tests {
             Some(&serde_json::Value::Number(serde_json::Number::from(42)))
         );
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_current_context_is_scoped_to_the_task() {
+        assert!(LogContext::current().is_none());
+
+        let correlation_id = LogContext::new()
+            .with_correlation_id("req_abc".to_string())
+            .scope(async { LogContext::current().and_then(|ctx| ctx.correlation_id) })
+            .await;
+
+        assert_eq!(correlation_id.as_deref(), Some("req_abc"));
+        assert!(LogContext::current().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_brik_log_records_typed_fields_with_the_scoped_context() {
+        let captured = Captured::default();
+        let _guard = tracing::subscriber::set_default(captured.subscriber(Level::INFO));
+
+        LogContext::new()
+            .with_correlation_id("req_abc".to_string())
+            .with_extra("locale", "es")
+            .scope(async { crate::brik_log!(WARN, "Probe failed", port = "Database", attempts = 3u32, healthy = false) })
+            .await;
+
+        let fields = &captured.lines()[0]["fields"];
+        assert_eq!(fields["message"], "Probe failed");
+        assert_eq!(fields["correlation_id"], "req_abc");
+        assert_eq!(fields["extra"], r#"{"locale":"es"}"#);
+        assert_eq!(fields["port"], "Database");
+        assert_eq!(fields["attempts"], 3);
+        assert_eq!(fields["healthy"], false);
+        assert!(fields.get("user_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fields_passed_to_brik_log_replace_the_scoped_ones() {
+        let captured = Captured::default();
+        let _guard = tracing::subscriber::set_default(captured.subscriber(Level::INFO));
+        let user_id = "user_call";
+
+        LogContext::new()
+            .with_correlation_id("req_scope".to_string())
+            .with_user_id("user_scope".to_string())
+            .with_endpoint("GET /scope".to_string())
+            .scope(async {
+                crate::brik_log!(
+                    INFO,
+                    "Overridden",
+                    endpoint = "POST /users",
+                    %user_id,
+                    requested_correlation_id = "req_other"
+                )
+            })
+            .await;
+
+        let line = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(line.matches("\"endpoint\"").count(), 1);
+        assert_eq!(line.matches("\"user_id\"").count(), 1);
+        let fields = &captured.lines()[0]["fields"];
+        assert_eq!(fields["endpoint"], "POST /users");
+        assert_eq!(fields["user_id"], "user_call");
+        assert_eq!(fields["correlation_id"], "req_scope");
+        assert_eq!(fields["requested_correlation_id"], "req_other");
+    }
+
+    #[test]
+    fn test_brik_log_skips_fields_when_the_level_is_disabled() {
+        let captured = Captured::default();
+        let evaluated = std::cell::Cell::new(0);
+        let expensive = || {
+            evaluated.set(evaluated.get() + 1);
+            "value"
+        };
+
+        tracing::subscriber::with_default(captured.subscriber(Level::INFO), || {
+            crate::brik_log!(DEBUG, "Hidden", detail = expensive());
+            crate::brik_log!(INFO, "Shown", detail = expensive());
+        });
+
+        assert_eq!(evaluated.get(), 1);
+        assert_eq!(captured.lines().len(), 1);
+    }
+
+    #[test]
+    fn test_brik_logger_records_an_explicit_context() {
+        let captured = Captured::default();
+        let context = LogContext::new().with_gate("AuthGate".to_string()).with_duration(12);
+        let error = io::Error::other("token expired");
+
+        tracing::subscriber::with_default(captured.subscriber(Level::INFO), || {
+            BrikLogger::error("Gate failed", Some(&error), Some(&context));
+            BrikLogger::debug("Hidden", Some(&context));
+            BrikLogger::info("No context", None);
+        });
+
+        let lines = captured.lines();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["fields"]["gate"], "AuthGate");
+        assert_eq!(lines[0]["fields"]["duration_ms"], 12);
+        assert_eq!(lines[0]["fields"]["error"], "token expired");
+        assert!(lines[0]["fields"].get("port").is_none());
+        assert_eq!(lines[1]["fields"].as_object().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,114 @@
+//! BRIK v5 Outbox Publisher Port - Where the relay delivers outbox events
+//!
+//! Delivery is at-least-once: a crash between publishing and marking the
+//! row published repeats the event, so consumers should dedupe on `id`.
+
+use crate::shared::config::settings::OutboxSettings;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::port_error::PortError;
+use crate::brik_log;
+use crate::shared::outbox::outbox_store::OutboxEvent;
+use crate::shared::types::result::BrikResult;
+use redis::aio::ConnectionManager;
+use std::sync::Arc;
+
+const PORT_NAME: &str = "OutboxPublisher";
+
+#[async_trait::async_trait]
+pub trait OutboxPublisher: Send + Sync {
+    async fn publish(&self, event: &OutboxEvent) -> BrikResult<(), PortError>;
+}
+
+/// Wire format shared by every publisher
+pub fn envelope(event: &OutboxEvent) -> serde_json::Value {
+    serde_json::json!({
+        "id": event.id,
+        "type": event.event_type,
+        "aggregate_type": event.aggregate_type,
+        "aggregate_id": event.aggregate_id,
+        "occurred_at": event.occurred_at,
+        "payload": event.payload,
+    })
+}
+
+/// Redis pub/sub adapter, one channel per aggregate type (`{prefix}user`)
+#[derive(Clone)]
+pub struct RedisPubSubPublisher {
+    connection: ConnectionManager,
+    channel_prefix: String,
+}
+
+impl RedisPubSubPublisher {
+    pub fn new(connection: ConnectionManager, channel_prefix: &str) -> Self {
+        Self {
+            connection,
+            channel_prefix: channel_prefix.to_string(),
+        }
+    }
+
+    pub fn channel_for(&self, event: &OutboxEvent) -> String {
+        format!("{}{}", self.channel_prefix, event.aggregate_type)
+    }
+}
+
+#[async_trait::async_trait]
+impl OutboxPublisher for RedisPubSubPublisher {
+    async fn publish(&self, event: &OutboxEvent) -> BrikResult<(), PortError> {
+        redis::cmd("PUBLISH")
+            .arg(self.channel_for(event))
+            .arg(envelope(event).to_string())
+            .query_async::<_, i64>(&mut self.connection.clone())
+            .await
+            .map(|_| ())
+            .map_err(|e| ErrorCode::PublishFailed.port_error(PORT_NAME, &e.to_string()))
+    }
+}
+
+/// Writes events to the application log; for local runs without a broker
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogPublisher;
+
+#[async_trait::async_trait]
+impl OutboxPublisher for LogPublisher {
+    async fn publish(&self, event: &OutboxEvent) -> BrikResult<(), PortError> {
+        brik_log!(INFO, "Outbox event published", port = PORT_NAME, event = %envelope(event));
+        Ok(())
+    }
+}
+
+/// Publisher selected by `outbox.publisher`; settings are validated at load
+pub fn outbox_publisher_from_settings(settings: &OutboxSettings, redis: ConnectionManager) -> Arc<dyn OutboxPublisher> {
+    match settings.publisher.as_str() {
+        "redis" => Arc::new(RedisPubSubPublisher::new(redis, &settings.channel_prefix)),
+        _ => Arc::new(LogPublisher),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::ids::id_generator::{IdGenerator, UuidV7Generator};
+    use chrono::Utc;
+
+    #[test]
+    fn test_envelope_carries_identity_and_payload() {
+        let aggregate_id = UuidV7Generator::default().generate();
+        let event = OutboxEvent {
+            id: 7,
+            aggregate_type: "user".to_string(),
+            aggregate_id,
+            event_type: "user.deleted".to_string(),
+            payload: serde_json::json!({ "user_id": aggregate_id }),
+            occurred_at: Utc::now(),
+            attempts: 0,
+            next_attempt_at: Utc::now(),
+        };
+
+        let json = envelope(&event);
+
+        assert_eq!(json["id"], 7);
+        assert_eq!(json["type"], "user.deleted");
+        assert_eq!(json["aggregate_id"], aggregate_id.to_string());
+        assert_eq!(json["payload"]["user_id"], aggregate_id.to_string());
+    }
+}
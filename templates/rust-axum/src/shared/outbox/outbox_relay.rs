@@ -0,0 +1,406 @@
+//! BRIK v5 Outbox Relay - Moves pending outbox rows to the publisher
+//!
+//! Rows are relayed in id order. Once an event of an aggregate fails or is
+//! waiting out its backoff, later events of that aggregate are held back
+//! until it goes through, so consumers see each aggregate's events in
+//! order. Events of other aggregates keep flowing.
+//!
+//! An event parked as dead keeps holding its aggregate back: publishing
+//! past it would leave a gap consumers cannot detect. Clearing its
+//! `dead_at` retries it; deleting the row releases the events behind it.
+
+use crate::shared::config::settings::OutboxSettings;
+use crate::shared::errors::port_error::PortError;
+use crate::brik_log;
+use crate::shared::observability::metrics::{
+    OUTBOX_DEAD_TOTAL, OUTBOX_LAG_SECONDS, OUTBOX_PENDING_EVENTS, OUTBOX_PUBLISHED_TOTAL,
+    OUTBOX_PUBLISH_FAILURES_TOTAL,
+};
+use crate::shared::outbox::outbox_publisher::OutboxPublisher;
+use crate::shared::outbox::outbox_store::{OutboxEvent, OutboxStore};
+use crate::shared::time::clock::{SharedClock, SystemClock};
+use crate::shared::types::result::BrikResult;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub const DEFAULT_BATCH_SIZE: i64 = 100;
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+pub const DEFAULT_MAX_ATTEMPTS: i32 = 10;
+
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Outcome of a single relay pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RelayReport {
+    pub published: usize,
+    pub failed: usize,
+    pub dead: usize,
+    /// Rows skipped because an earlier event of their aggregate failed this pass
+    pub held_back: usize,
+}
+
+pub struct OutboxRelay {
+    store: Arc<dyn OutboxStore>,
+    publisher: Arc<dyn OutboxPublisher>,
+    batch_size: i64,
+    poll_interval: Duration,
+    max_attempts: i32,
+    /// Decides which rows are due and when failed ones are retried
+    clock: SharedClock,
+}
+
+impl OutboxRelay {
+    pub fn new(store: Arc<dyn OutboxStore>, publisher: Arc<dyn OutboxPublisher>) -> Self {
+        Self {
+            store,
+            publisher,
+            batch_size: DEFAULT_BATCH_SIZE,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    pub fn from_settings(store: Arc<dyn OutboxStore>, publisher: Arc<dyn OutboxPublisher>, settings: &OutboxSettings) -> Self {
+        Self::new(store, publisher)
+            .with_batch_size(settings.batch_size)
+            .with_poll_interval(Duration::from_millis(settings.poll_interval_ms))
+            .with_max_attempts(settings.max_attempts)
+    }
+
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Attempts before a row is parked as dead
+    pub fn with_max_attempts(mut self, max_attempts: i32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Delay before retrying after `attempts` failures: 1s, 2s, 4s... capped at 5 minutes
+    pub fn backoff(attempts: i32) -> Duration {
+        let exponent = u32::try_from(attempts.saturating_sub(1)).unwrap_or_default().min(16);
+        BASE_BACKOFF.saturating_mul(1 << exponent).min(MAX_BACKOFF)
+    }
+
+    /// Relays one batch; does nothing when another instance holds the lead
+    pub async fn relay_once(&self) -> BrikResult<RelayReport, PortError> {
+        let mut report = RelayReport::default();
+
+        if self.store.try_lead().await? {
+            let now = self.clock.now();
+            let mut blocked: HashSet<(String, Uuid)> = HashSet::new();
+
+            for event in self.store.fetch_pending(self.batch_size, now).await? {
+                let aggregate = (event.aggregate_type.clone(), event.aggregate_id);
+
+                if blocked.contains(&aggregate) || event.next_attempt_at > now {
+                    blocked.insert(aggregate);
+                    report.held_back += 1;
+                    continue;
+                }
+
+                match self.publisher.publish(&event).await {
+                    Ok(()) => {
+                        self.store.mark_published(event.id).await?;
+                        metrics::counter!(OUTBOX_PUBLISHED_TOTAL, "aggregate" => event.aggregate_type.clone())
+                            .increment(1);
+                        report.published += 1;
+                    }
+                    Err(error) => {
+                        metrics::counter!(OUTBOX_PUBLISH_FAILURES_TOTAL, "aggregate" => event.aggregate_type.clone())
+                            .increment(1);
+
+                        if self.record_failure(&event, &error, now).await? {
+                            report.dead += 1;
+                        } else {
+                            report.failed += 1;
+                        }
+                        blocked.insert(aggregate);
+                    }
+                }
+            }
+        }
+
+        self.report_lag().await?;
+        Ok(report)
+    }
+
+    /// Returns whether the row was parked
+    async fn record_failure(&self, event: &OutboxEvent, error: &PortError, now: DateTime<Utc>) -> BrikResult<bool, PortError> {
+        let attempts = event.attempts + 1;
+        if attempts >= self.max_attempts {
+            self.store.mark_dead(event.id, &error.message).await?;
+            metrics::counter!(OUTBOX_DEAD_TOTAL, "aggregate" => event.aggregate_type.clone()).increment(1);
+            brik_log!(
+                WARN,
+                "Outbox event parked after exhausting its attempts",
+                port = error.port.as_str(),
+                outbox_id = %event.id,
+                event = event.event_type.as_str(),
+                attempts = attempts,
+                error = error.message.as_str()
+            );
+            return Ok(true);
+        }
+
+        let retry_at = now + chrono::Duration::from_std(Self::backoff(attempts)).unwrap_or_default();
+        self.store.mark_failed(event.id, &error.message, retry_at).await?;
+        brik_log!(
+            WARN,
+            "Outbox event publish failed, will retry",
+            port = error.port.as_str(),
+            outbox_id = %event.id,
+            event = event.event_type.as_str(),
+            attempts = attempts,
+            error = error.message.as_str()
+        );
+        Ok(false)
+    }
+
+    async fn report_lag(&self) -> BrikResult<(), PortError> {
+        let lag = self.store.lag().await?;
+        metrics::gauge!(OUTBOX_PENDING_EVENTS).set(lag.pending as f64);
+        metrics::gauge!(OUTBOX_LAG_SECONDS).set(lag.oldest_age_secs);
+        Ok(())
+    }
+
+    /// Polls until `shutdown` resolves; store failures are logged and retried
+    /// on the next tick
+    pub async fn run(self, shutdown: impl Future<Output = ()>) {
+        tokio::pin!(shutdown);
+        let mut ticker = tokio::time::interval(self.poll_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = ticker.tick() => {
+                    if let Err(error) = self.relay_once().await {
+                        brik_log!(
+                            WARN,
+                            "Outbox relay pass failed",
+                            port = error.port.as_str(),
+                            error = error.message.as_str()
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::ids::id_generator::{IdGenerator, UuidV7Generator};
+    use crate::shared::outbox::outbox_store::{InMemoryOutboxStore, NewOutboxEvent};
+    use crate::shared::time::clock::{Clock, ManualClock};
+    use std::sync::Mutex;
+
+    /// Fails for the listed outbox ids and records what it delivered
+    #[derive(Default)]
+    struct FakePublisher {
+        failing: Mutex<HashSet<i64>>,
+        delivered: Mutex<Vec<i64>>,
+    }
+
+    #[async_trait::async_trait]
+    impl OutboxPublisher for FakePublisher {
+        async fn publish(&self, event: &OutboxEvent) -> BrikResult<(), PortError> {
+            if self.failing.lock().unwrap().contains(&event.id) {
+                return Err(PortError::new("FakePublisher", "PUBLISH_FAILED", "broker down"));
+            }
+            self.delivered.lock().unwrap().push(event.id);
+            Ok(())
+        }
+    }
+
+    fn aggregate_id() -> Uuid {
+        UuidV7Generator::default().generate()
+    }
+
+    fn event(aggregate_id: Uuid) -> NewOutboxEvent {
+        NewOutboxEvent {
+            aggregate_type: "user",
+            aggregate_id,
+            event_type: "user.updated".to_string(),
+            payload: serde_json::json!({}),
+            occurred_at: Utc::now(),
+        }
+    }
+
+    fn relay(store: &Arc<InMemoryOutboxStore>, publisher: &Arc<FakePublisher>) -> OutboxRelay {
+        OutboxRelay::new(store.clone(), publisher.clone())
+    }
+
+    #[tokio::test]
+    async fn test_failure_holds_back_later_events_of_the_same_aggregate() {
+        let store = Arc::new(InMemoryOutboxStore::new());
+        let publisher = Arc::new(FakePublisher::default());
+        let (first, second) = (aggregate_id(), aggregate_id());
+
+        let failing = store.append(event(first)).await;
+        let held = store.append(event(first)).await;
+        let other = store.append(event(second)).await;
+        publisher.failing.lock().unwrap().insert(failing);
+
+        let report = relay(&store, &publisher).relay_once().await.unwrap();
+
+        assert_eq!(report, RelayReport { published: 1, failed: 1, dead: 0, held_back: 1 });
+        assert_eq!(*publisher.delivered.lock().unwrap(), vec![other]);
+
+        // Still in backoff: the store leaves the whole aggregate out
+        publisher.failing.lock().unwrap().clear();
+        let report = relay(&store, &publisher).relay_once().await.unwrap();
+        assert_eq!(report, RelayReport::default());
+        assert_eq!(*publisher.delivered.lock().unwrap(), vec![other]);
+
+        let rows = store.rows().await;
+        assert_eq!(rows[0].event.attempts, 1);
+        assert_eq!(rows[0].last_error.as_deref(), Some("broker down"));
+        assert!(rows[0].event.next_attempt_at > Utc::now());
+        assert!(!rows[1].published && rows[1].event.id == held);
+    }
+
+    #[tokio::test]
+    async fn test_an_aggregate_in_backoff_does_not_stall_the_batch() {
+        let store = Arc::new(InMemoryOutboxStore::new());
+        let publisher = Arc::new(FakePublisher::default());
+        let stuck = aggregate_id();
+
+        let failing = store.append(event(stuck)).await;
+        for _ in 0..5 {
+            store.append(event(stuck)).await;
+        }
+        let ready = store.append(event(aggregate_id())).await;
+        store.mark_failed(failing, "broker down", Utc::now() + chrono::Duration::minutes(5)).await.unwrap();
+
+        let report = relay(&store, &publisher).with_batch_size(3).relay_once().await.unwrap();
+
+        assert_eq!(report.published, 1);
+        assert_eq!(*publisher.delivered.lock().unwrap(), vec![ready]);
+    }
+
+    #[test]
+    fn test_settings_configure_the_relay() {
+        let settings = OutboxSettings {
+            publisher: "log".to_string(),
+            channel_prefix: "events.".to_string(),
+            poll_interval_ms: 250,
+            batch_size: 20,
+            max_attempts: 4,
+        };
+        let relay = OutboxRelay::from_settings(
+            Arc::new(InMemoryOutboxStore::new()),
+            Arc::new(FakePublisher::default()),
+            &settings,
+        );
+
+        assert_eq!(relay.batch_size, 20);
+        assert_eq!(relay.poll_interval, Duration::from_millis(250));
+        assert_eq!(relay.max_attempts, 4);
+    }
+
+    #[tokio::test]
+    async fn test_retries_in_order_once_backoff_elapsed() {
+        let store = Arc::new(InMemoryOutboxStore::new());
+        let publisher = Arc::new(FakePublisher::default());
+        let aggregate = aggregate_id();
+
+        let first = store.append(event(aggregate)).await;
+        let second = store.append(event(aggregate)).await;
+        store.mark_failed(first, "broker down", Utc::now() - chrono::Duration::seconds(1)).await.unwrap();
+
+        let report = relay(&store, &publisher).relay_once().await.unwrap();
+
+        assert_eq!(report.published, 2);
+        assert_eq!(*publisher.delivered.lock().unwrap(), vec![first, second]);
+        assert_eq!(store.lag().await.unwrap().pending, 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_event_is_retried_once_the_relay_clock_passes_its_backoff() {
+        let store = Arc::new(InMemoryOutboxStore::new());
+        let publisher = Arc::new(FakePublisher::default());
+        let failing = store.append(event(aggregate_id())).await;
+        // Starts after the row was written, so the row is due
+        let clock = ManualClock::starting_now();
+        let relay = relay(&store, &publisher).with_clock(clock.clone());
+
+        publisher.failing.lock().unwrap().insert(failing);
+        assert_eq!(relay.relay_once().await.unwrap().failed, 1);
+        assert_eq!(store.rows().await[0].event.next_attempt_at, clock.now() + chrono::Duration::seconds(1));
+
+        publisher.failing.lock().unwrap().clear();
+        clock.advance(Duration::from_millis(999));
+        assert_eq!(relay.relay_once().await.unwrap().published, 0);
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(relay.relay_once().await.unwrap().published, 1);
+        assert_eq!(*publisher.delivered.lock().unwrap(), vec![failing]);
+    }
+
+    #[tokio::test]
+    async fn test_parks_event_after_max_attempts() {
+        let store = Arc::new(InMemoryOutboxStore::new());
+        let publisher = Arc::new(FakePublisher::default());
+        let aggregate = aggregate_id();
+
+        let poisoned = store.append(event(aggregate)).await;
+        let next = store.append(event(aggregate)).await;
+        publisher.failing.lock().unwrap().insert(poisoned);
+
+        let relay = relay(&store, &publisher).with_max_attempts(1);
+        let report = relay.relay_once().await.unwrap();
+
+        assert_eq!(report, RelayReport { published: 0, failed: 0, dead: 1, held_back: 1 });
+        assert!(publisher.delivered.lock().unwrap().is_empty());
+        let rows = store.rows().await;
+        assert!(rows[0].dead);
+
+        // The parked event still holds its aggregate back
+        publisher.failing.lock().unwrap().clear();
+        assert_eq!(relay.relay_once().await.unwrap(), RelayReport::default());
+        assert!(publisher.delivered.lock().unwrap().is_empty());
+        assert_eq!(store.rows().await[1].event.id, next);
+        assert_eq!(store.lag().await.unwrap().pending, 1);
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        assert_eq!(OutboxRelay::backoff(1), Duration::from_secs(1));
+        assert_eq!(OutboxRelay::backoff(2), Duration::from_secs(2));
+        assert_eq!(OutboxRelay::backoff(4), Duration::from_secs(8));
+        assert_eq!(OutboxRelay::backoff(30), MAX_BACKOFF);
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_on_shutdown() {
+        let store = Arc::new(InMemoryOutboxStore::new());
+        let publisher = Arc::new(FakePublisher::default());
+        let id = store.append(event(aggregate_id())).await;
+
+        let relay = relay(&store, &publisher).with_poll_interval(Duration::from_millis(5));
+        relay.run(tokio::time::sleep(Duration::from_millis(50))).await;
+
+        assert_eq!(*publisher.delivered.lock().unwrap(), vec![id]);
+    }
+}
@@ -0,0 +1,402 @@
+//! BRIK v5 Outbox Store - Pending domain events awaiting publication
+//!
+//! Repositories `append` events inside the transaction that changes the
+//! aggregate, so either both are committed or neither is. The relay then
+//! reads pending rows through the `OutboxStore` port.
+
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::types::result::BrikResult;
+use chrono::{DateTime, Utc};
+use sqlx::pool::PoolConnection;
+use sqlx::{PgConnection, PgPool, Postgres, Row};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicI64, Ordering};
+use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
+
+const PORT_NAME: &str = "OutboxStore";
+
+/// Arbitrary, stable key for the advisory lock held by the active relay
+const RELAY_LOCK_KEY: i64 = 0x6272_696b_6f75_7462;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewOutboxEvent {
+    pub aggregate_type: &'static str,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxEvent {
+    pub id: i64,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OutboxLag {
+    pub pending: i64,
+    /// Age of the oldest pending row, zero when nothing is pending
+    pub oldest_age_secs: f64,
+}
+
+#[async_trait::async_trait]
+pub trait OutboxStore: Send + Sync {
+    /// Whether this instance may relay; only one relay runs at a time so
+    /// events of an aggregate are never published concurrently
+    async fn try_lead(&self) -> BrikResult<bool, PortError> {
+        Ok(true)
+    }
+
+    /// Oldest pending rows that may go out at `now`. An aggregate whose
+    /// earlier event is dead or still waiting out its backoff contributes
+    /// no rows, so a stuck aggregate can never fill the batch.
+    async fn fetch_pending(&self, limit: i64, now: DateTime<Utc>) -> BrikResult<Vec<OutboxEvent>, PortError>;
+
+    async fn mark_published(&self, id: i64) -> BrikResult<(), PortError>;
+
+    async fn mark_failed(&self, id: i64, error: &str, next_attempt_at: DateTime<Utc>) -> BrikResult<(), PortError>;
+
+    /// Parks a row that exhausted its attempts
+    async fn mark_dead(&self, id: i64, error: &str) -> BrikResult<(), PortError>;
+
+    async fn lag(&self) -> BrikResult<OutboxLag, PortError>;
+}
+
+fn database_error(error: sqlx::Error) -> PortError {
+    ErrorCode::DatabaseError.port_error(PORT_NAME, &error.to_string())
+}
+
+/// Inserts an event through the caller's transaction
+pub async fn append(connection: &mut PgConnection, event: &NewOutboxEvent) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO outbox_events (aggregate_type, aggregate_id, event_type, payload, occurred_at) \
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(event.aggregate_type)
+    .bind(event.aggregate_id)
+    .bind(&event.event_type)
+    .bind(&event.payload)
+    .bind(event.occurred_at)
+    .execute(connection)
+    .await?;
+
+    Ok(())
+}
+
+pub struct PostgresOutboxStore {
+    pool: PgPool,
+    /// Connection holding the session-level relay lock once acquired
+    leader: Mutex<Option<PoolConnection<Postgres>>>,
+}
+
+impl PostgresOutboxStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            leader: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl OutboxStore for PostgresOutboxStore {
+    async fn try_lead(&self) -> BrikResult<bool, PortError> {
+        let mut leader = self.leader.lock().await;
+
+        if let Some(connection) = leader.as_mut() {
+            // A dropped connection silently releases the lock
+            if sqlx::query("SELECT 1").execute(&mut **connection).await.is_ok() {
+                return Ok(true);
+            }
+            *leader = None;
+        }
+
+        let mut connection = self.pool.acquire().await.map_err(database_error)?;
+        let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(RELAY_LOCK_KEY)
+            .fetch_one(&mut *connection)
+            .await
+            .map_err(database_error)?;
+
+        if acquired {
+            *leader = Some(connection);
+        }
+        Ok(acquired)
+    }
+
+    async fn fetch_pending(&self, limit: i64, now: DateTime<Utc>) -> BrikResult<Vec<OutboxEvent>, PortError> {
+        let rows = sqlx::query(
+            "SELECT id, aggregate_type, aggregate_id, event_type, payload, occurred_at, attempts, next_attempt_at \
+             FROM outbox_events e WHERE published_at IS NULL AND dead_at IS NULL \
+             AND NOT EXISTS (SELECT 1 FROM outbox_events waiting \
+                 WHERE waiting.aggregate_type = e.aggregate_type AND waiting.aggregate_id = e.aggregate_id \
+                 AND waiting.published_at IS NULL AND waiting.id <= e.id \
+                 AND (waiting.dead_at IS NOT NULL OR waiting.next_attempt_at > $2)) \
+             ORDER BY id LIMIT $1",
+        )
+        .bind(limit)
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(database_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(OutboxEvent {
+                    id: row.try_get("id")?,
+                    aggregate_type: row.try_get("aggregate_type")?,
+                    aggregate_id: row.try_get("aggregate_id")?,
+                    event_type: row.try_get("event_type")?,
+                    payload: row.try_get("payload")?,
+                    occurred_at: row.try_get("occurred_at")?,
+                    attempts: row.try_get("attempts")?,
+                    next_attempt_at: row.try_get("next_attempt_at")?,
+                })
+            })
+            .collect::<Result<_, sqlx::Error>>()
+            .map_err(database_error)
+    }
+
+    async fn mark_published(&self, id: i64) -> BrikResult<(), PortError> {
+        sqlx::query("UPDATE outbox_events SET published_at = now(), attempts = attempts + 1 WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: i64, error: &str, next_attempt_at: DateTime<Utc>) -> BrikResult<(), PortError> {
+        sqlx::query(
+            "UPDATE outbox_events SET attempts = attempts + 1, last_error = $2, next_attempt_at = $3 WHERE id = $1",
+        )
+        .bind(id)
+        .bind(error)
+        .bind(next_attempt_at)
+        .execute(&self.pool)
+        .await
+        .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn mark_dead(&self, id: i64, error: &str) -> BrikResult<(), PortError> {
+        sqlx::query("UPDATE outbox_events SET attempts = attempts + 1, last_error = $2, dead_at = now() WHERE id = $1")
+            .bind(id)
+            .bind(error)
+            .execute(&self.pool)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn lag(&self) -> BrikResult<OutboxLag, PortError> {
+        let row = sqlx::query(
+            "SELECT count(*) AS pending, \
+             COALESCE(EXTRACT(EPOCH FROM now() - min(created_at))::float8, 0) AS oldest_age_secs \
+             FROM outbox_events WHERE published_at IS NULL AND dead_at IS NULL",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(database_error)?;
+
+        Ok(OutboxLag {
+            pending: row.try_get("pending").map_err(database_error)?,
+            oldest_age_secs: row.try_get("oldest_age_secs").map_err(database_error)?,
+        })
+    }
+}
+
+/// In-process adapter for tests and local runs without PostgreSQL
+#[derive(Default)]
+pub struct InMemoryOutboxStore {
+    next_id: AtomicI64,
+    rows: RwLock<Vec<InMemoryRow>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InMemoryRow {
+    pub event: OutboxEvent,
+    pub created_at: DateTime<Utc>,
+    pub published: bool,
+    pub dead: bool,
+    pub last_error: Option<String>,
+}
+
+impl InMemoryOutboxStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn append(&self, event: NewOutboxEvent) -> i64 {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let now = Utc::now();
+        self.rows.write().await.push(InMemoryRow {
+            event: OutboxEvent {
+                id,
+                aggregate_type: event.aggregate_type.to_string(),
+                aggregate_id: event.aggregate_id,
+                event_type: event.event_type,
+                payload: event.payload,
+                occurred_at: event.occurred_at,
+                attempts: 0,
+                next_attempt_at: now,
+            },
+            created_at: now,
+            published: false,
+            dead: false,
+            last_error: None,
+        });
+        id
+    }
+
+    pub async fn rows(&self) -> Vec<InMemoryRow> {
+        self.rows.read().await.clone()
+    }
+
+    async fn update(&self, id: i64, change: impl FnOnce(&mut InMemoryRow)) {
+        if let Some(row) = self.rows.write().await.iter_mut().find(|row| row.event.id == id) {
+            change(row);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl OutboxStore for InMemoryOutboxStore {
+    async fn fetch_pending(&self, limit: i64, now: DateTime<Utc>) -> BrikResult<Vec<OutboxEvent>, PortError> {
+        let rows = self.rows.read().await;
+        let mut waiting = HashSet::new();
+
+        Ok(rows
+            .iter()
+            .filter(|row| !row.published)
+            .filter(|row| {
+                let aggregate = (row.event.aggregate_type.as_str(), row.event.aggregate_id);
+                if row.dead || row.event.next_attempt_at > now {
+                    waiting.insert(aggregate);
+                }
+                !row.dead && !waiting.contains(&aggregate)
+            })
+            .take(usize::try_from(limit).unwrap_or_default())
+            .map(|row| row.event.clone())
+            .collect())
+    }
+
+    async fn mark_published(&self, id: i64) -> BrikResult<(), PortError> {
+        self.update(id, |row| {
+            row.published = true;
+            row.event.attempts += 1;
+        })
+        .await;
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: i64, error: &str, next_attempt_at: DateTime<Utc>) -> BrikResult<(), PortError> {
+        self.update(id, |row| {
+            row.event.attempts += 1;
+            row.event.next_attempt_at = next_attempt_at;
+            row.last_error = Some(error.to_string());
+        })
+        .await;
+        Ok(())
+    }
+
+    async fn mark_dead(&self, id: i64, error: &str) -> BrikResult<(), PortError> {
+        self.update(id, |row| {
+            row.event.attempts += 1;
+            row.dead = true;
+            row.last_error = Some(error.to_string());
+        })
+        .await;
+        Ok(())
+    }
+
+    async fn lag(&self) -> BrikResult<OutboxLag, PortError> {
+        let rows = self.rows.read().await;
+        let pending: Vec<_> = rows.iter().filter(|row| !row.published && !row.dead).collect();
+        let oldest_age_secs = pending
+            .iter()
+            .map(|row| row.created_at)
+            .min()
+            .map_or(0.0, |oldest| (Utc::now() - oldest).num_milliseconds().max(0) as f64 / 1000.0);
+
+        Ok(OutboxLag {
+            pending: pending.len() as i64,
+            oldest_age_secs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::ids::id_generator::{IdGenerator, UuidV7Generator};
+
+    fn aggregate_id() -> Uuid {
+        UuidV7Generator::default().generate()
+    }
+
+    fn event(aggregate_id: Uuid) -> NewOutboxEvent {
+        NewOutboxEvent {
+            aggregate_type: "user",
+            aggregate_id,
+            event_type: "user.created".to_string(),
+            payload: serde_json::json!({}),
+            occurred_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_tracks_pending_rows() {
+        let store = InMemoryOutboxStore::new();
+        let first = store.append(event(aggregate_id())).await;
+        let second = store.append(event(aggregate_id())).await;
+
+        store.mark_published(first).await.unwrap();
+
+        let pending = store.fetch_pending(10, Utc::now()).await.unwrap();
+        assert_eq!(pending.iter().map(|event| event.id).collect::<Vec<_>>(), vec![second]);
+        assert_eq!(store.lag().await.unwrap().pending, 1);
+
+        store.mark_dead(second, "boom").await.unwrap();
+        assert_eq!(store.lag().await.unwrap(), OutboxLag::default());
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_in_backoff_are_left_out() {
+        let store = InMemoryOutboxStore::new();
+        let (waiting, ready) = (aggregate_id(), aggregate_id());
+        let failed = store.append(event(waiting)).await;
+        store.append(event(waiting)).await;
+        let other = store.append(event(ready)).await;
+        let now = Utc::now();
+        store.mark_failed(failed, "boom", now + chrono::Duration::seconds(30)).await.unwrap();
+
+        let pending = store.fetch_pending(10, now).await.unwrap();
+        assert_eq!(pending.iter().map(|event| event.id).collect::<Vec<_>>(), vec![other]);
+
+        let later = store.fetch_pending(10, now + chrono::Duration::seconds(31)).await.unwrap();
+        assert_eq!(later.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_behind_a_dead_event_are_left_out() {
+        let store = InMemoryOutboxStore::new();
+        let (parked, ready) = (aggregate_id(), aggregate_id());
+        let dead = store.append(event(parked)).await;
+        store.append(event(parked)).await;
+        let other = store.append(event(ready)).await;
+        store.mark_dead(dead, "boom").await.unwrap();
+
+        let pending = store.fetch_pending(10, Utc::now() + chrono::Duration::days(1)).await.unwrap();
+        assert_eq!(pending.iter().map(|event| event.id).collect::<Vec<_>>(), vec![other]);
+    }
+}
@@ -0,0 +1,53 @@
+//! BRIK v5 Opaque Cursor - Keyset position for cursor pagination
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Position after the last item of a page: the value of the sort key plus the
+/// id as tie-breaker. Clients only ever see the encoded form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Sort the cursor was issued for, e.g. `-created_at`
+    pub sort: String,
+    pub key: serde_json::Value,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn new(sort: String, key: serde_json::Value, id: Uuid) -> Self {
+        Self { sort, key, id }
+    }
+
+    pub fn encode(&self) -> String {
+        // Serializing plain JSON values and a UUID cannot fail
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(encoded: &str) -> Option<Self> {
+        let json = URL_SAFE_NO_PAD.decode(encoded).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor::new("-created_at".to_string(), serde_json::json!("2024-01-15T10:30:00Z"), Uuid::new_v4());
+
+        let encoded = cursor.encode();
+
+        assert!(!encoded.contains("created_at"));
+        assert_eq!(Cursor::decode(&encoded), Some(cursor));
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert_eq!(Cursor::decode("not a cursor"), None);
+        assert_eq!(Cursor::decode(&URL_SAFE_NO_PAD.encode("{\"sort\":1}")), None);
+    }
+}
@@ -0,0 +1,289 @@
+//! BRIK v5 List Query - Reusable parsing for collection endpoints
+//!
+//! `QueryParser` reads raw query parameters, accumulates every problem it
+//! finds and turns them into a single `INVALID_QUERY` gate error, so each
+//! resource only declares its filters and its sortable fields.
+
+use super::cursor::Cursor;
+use crate::api::users::gates::gate_result::GateError;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::types::result::BrikResult;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+pub const DEFAULT_PAGE_LIMIT: u32 = 20;
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
+/// Whitelist of fields a resource can be sorted by
+pub trait SortField: Copy + PartialEq + Sized {
+    fn parse(name: &str) -> Option<Self>;
+
+    fn as_str(&self) -> &'static str;
+
+    fn allowed() -> &'static [&'static str];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+}
+
+/// `field` sorts ascending, `-field` descending
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sort<F> {
+    pub field: F,
+    pub direction: SortDirection,
+}
+
+impl<F: SortField> Sort<F> {
+    pub fn asc(field: F) -> Self {
+        Self { field, direction: SortDirection::Asc }
+    }
+
+    pub fn desc(field: F) -> Self {
+        Self { field, direction: SortDirection::Desc }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.strip_prefix('-') {
+            Some(name) => F::parse(name).map(Self::desc),
+            None => F::parse(value.strip_prefix('+').unwrap_or(value)).map(Self::asc),
+        }
+    }
+
+    pub fn to_param(&self) -> String {
+        match self.direction {
+            SortDirection::Asc => self.field.as_str().to_string(),
+            SortDirection::Desc => format!("-{}", self.field.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRequest<F> {
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+    pub sort: Sort<F>,
+}
+
+impl<F: SortField> PageRequest<F> {
+    pub fn first(limit: u32, sort: Sort<F>) -> Self {
+        Self { limit, cursor: None, sort }
+    }
+}
+
+pub struct QueryParser<'a> {
+    params: &'a HashMap<String, String>,
+    errors: Vec<String>,
+}
+
+impl<'a> QueryParser<'a> {
+    pub fn new(params: &'a HashMap<String, String>) -> Self {
+        Self {
+            params,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn error(&mut self, message: String) {
+        self.errors.push(message);
+    }
+
+    /// Trimmed value, treating empty parameters as absent
+    pub fn string(&self, name: &str) -> Option<String> {
+        self.params
+            .get(name)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    }
+
+    pub fn number<T>(&mut self, name: &str, range: RangeInclusive<T>) -> Option<T>
+    where
+        T: FromStr + PartialOrd + Display + Copy,
+    {
+        let raw = self.string(name)?;
+        match raw.parse::<T>() {
+            Ok(value) if range.contains(&value) => Some(value),
+            _ => {
+                self.error(format!(
+                    "{} must be a number between {} and {}",
+                    name,
+                    range.start(),
+                    range.end()
+                ));
+                None
+            }
+        }
+    }
+
+    pub fn datetime(&mut self, name: &str) -> Option<DateTime<Utc>> {
+        let raw = self.string(name)?;
+        match DateTime::parse_from_rfc3339(&raw) {
+            Ok(value) => Some(value.with_timezone(&Utc)),
+            Err(_) => {
+                self.error(format!("{} must be an RFC 3339 date-time", name));
+                None
+            }
+        }
+    }
+
+    /// Reports `{min}` > `{max}` for range filters
+    pub fn check_range<T: PartialOrd>(&mut self, min_name: &str, min: Option<T>, max_name: &str, max: Option<T>) {
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                self.error(format!("{} must not be greater than {}", min_name, max_name));
+            }
+        }
+    }
+
+    /// Parses `limit`, `sort` and `cursor`; a cursor is only valid for the
+    /// sort it was issued with
+    pub fn page_request<F: SortField>(&mut self, default_sort: Sort<F>) -> PageRequest<F> {
+        let limit = self
+            .number("limit", 1..=MAX_PAGE_LIMIT)
+            .unwrap_or(DEFAULT_PAGE_LIMIT);
+
+        let sort = match self.string("sort") {
+            Some(raw) => Sort::parse(&raw).unwrap_or_else(|| {
+                self.error(format!(
+                    "sort must be one of {} (prefix with '-' for descending)",
+                    F::allowed().join(", ")
+                ));
+                default_sort
+            }),
+            None => default_sort,
+        };
+
+        let cursor = self.string("cursor").and_then(|raw| match Cursor::decode(&raw) {
+            Some(cursor) if cursor.sort == sort.to_param() => Some(cursor),
+            Some(_) => {
+                self.error("cursor was issued for a different sort".to_string());
+                None
+            }
+            None => {
+                self.error("cursor is malformed".to_string());
+                None
+            }
+        });
+
+        PageRequest { limit, cursor, sort }
+    }
+
+    pub fn finish<T>(self, value: T) -> BrikResult<T, GateError> {
+        if self.errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(ErrorCode::InvalidQuery.gate_error_with("QueryGate", &self.errors.join("; ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum TestSort {
+        Name,
+        CreatedAt,
+    }
+
+    impl SortField for TestSort {
+        fn parse(name: &str) -> Option<Self> {
+            match name {
+                "name" => Some(TestSort::Name),
+                "created_at" => Some(TestSort::CreatedAt),
+                _ => None,
+            }
+        }
+
+        fn as_str(&self) -> &'static str {
+            match self {
+                TestSort::Name => "name",
+                TestSort::CreatedAt => "created_at",
+            }
+        }
+
+        fn allowed() -> &'static [&'static str] {
+            &["name", "created_at"]
+        }
+    }
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_page_request_defaults() {
+        let params = params(&[]);
+        let mut parser = QueryParser::new(&params);
+
+        let page = parser.page_request(Sort::desc(TestSort::CreatedAt));
+
+        assert_eq!(page.limit, DEFAULT_PAGE_LIMIT);
+        assert_eq!(page.sort, Sort::desc(TestSort::CreatedAt));
+        assert!(page.cursor.is_none());
+        assert!(parser.finish(()).is_ok());
+    }
+
+    #[test]
+    fn test_sort_parsing() {
+        assert_eq!(Sort::parse("name"), Some(Sort::asc(TestSort::Name)));
+        assert_eq!(Sort::parse("-created_at"), Some(Sort::desc(TestSort::CreatedAt)));
+        assert_eq!(Sort::<TestSort>::parse("password"), None);
+        assert_eq!(Sort::desc(TestSort::CreatedAt).to_param(), "-created_at");
+    }
+
+    #[test]
+    fn test_errors_are_accumulated() {
+        let params = params(&[("limit", "500"), ("sort", "password"), ("cursor", "garbage"), ("since", "yesterday")]);
+        let mut parser = QueryParser::new(&params);
+
+        parser.page_request(Sort::asc(TestSort::Name));
+        parser.datetime("since");
+        let error = parser.finish(()).unwrap_err();
+
+        assert_eq!(error.code, "INVALID_QUERY");
+        assert_eq!(error.http_status, 400);
+        assert!(error.message.contains("limit"));
+        assert!(error.message.contains("sort must be one of name, created_at"));
+        assert!(error.message.contains("cursor is malformed"));
+        assert!(error.message.contains("since"));
+    }
+
+    #[test]
+    fn test_cursor_must_match_sort() {
+        let cursor = Cursor::new("name".to_string(), serde_json::json!("Ann"), Uuid::new_v4()).encode();
+        let params = params(&[("sort", "-name"), ("cursor", &cursor)]);
+        let mut parser = QueryParser::new(&params);
+
+        parser.page_request(Sort::asc(TestSort::Name));
+
+        assert!(parser.finish(()).unwrap_err().message.contains("different sort"));
+    }
+
+    #[test]
+    fn test_check_range() {
+        let params = params(&[]);
+        let mut parser = QueryParser::new(&params);
+
+        parser.check_range("min_age", Some(40), "max_age", Some(30));
+
+        assert!(parser.finish(()).unwrap_err().message.contains("min_age must not be greater than max_age"));
+    }
+}
@@ -0,0 +1,106 @@
+//! BRIK v5 Page Envelope - `{ data, page }` shape shared by collection endpoints
+
+use super::cursor::Cursor;
+use super::list_query::{PageRequest, SortField};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PageInfo {
+    #[schema(example = 20)]
+    pub limit: u32,
+    pub has_more: bool,
+    /// Opaque cursor for the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    pub page: PageInfo,
+}
+
+impl<T> Page<T> {
+    /// Builds a page from `limit + 1` fetched rows: the extra row only tells
+    /// whether another page exists
+    pub fn from_rows<R, F>(
+        mut rows: Vec<R>,
+        request: &PageRequest<F>,
+        cursor_of: impl Fn(&R) -> Cursor,
+        map: impl Fn(R) -> T,
+    ) -> Self
+    where
+        F: SortField,
+    {
+        let limit = request.limit as usize;
+        let has_more = rows.len() > limit;
+        rows.truncate(limit);
+
+        let next_cursor = if has_more {
+            rows.last().map(|row| cursor_of(row).encode())
+        } else {
+            None
+        };
+
+        Self {
+            data: rows.into_iter().map(map).collect(),
+            page: PageInfo {
+                limit: request.limit,
+                has_more,
+                next_cursor,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::pagination::list_query::Sort;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct ById;
+
+    impl SortField for ById {
+        fn parse(_: &str) -> Option<Self> {
+            Some(ById)
+        }
+
+        fn as_str(&self) -> &'static str {
+            "id"
+        }
+
+        fn allowed() -> &'static [&'static str] {
+            &["id"]
+        }
+    }
+
+    fn cursor_of(id: &Uuid) -> Cursor {
+        Cursor::new("id".to_string(), serde_json::Value::Null, *id)
+    }
+
+    #[test]
+    fn test_page_with_more_rows() {
+        let rows: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let request = PageRequest::first(2, Sort::asc(ById));
+
+        let page = Page::from_rows(rows.clone(), &request, cursor_of, |id| id);
+
+        assert_eq!(page.data, rows[..2].to_vec());
+        assert!(page.page.has_more);
+        assert_eq!(Cursor::decode(page.page.next_cursor.as_deref().unwrap()).unwrap().id, rows[1]);
+    }
+
+    #[test]
+    fn test_last_page_has_no_cursor() {
+        let rows: Vec<Uuid> = (0..2).map(|_| Uuid::new_v4()).collect();
+        let request = PageRequest::first(2, Sort::asc(ById));
+
+        let page = Page::from_rows(rows, &request, cursor_of, |id| id);
+
+        assert!(!page.page.has_more);
+        assert!(page.page.next_cursor.is_none());
+    }
+}
@@ -0,0 +1,356 @@
+//! BRIK v5 Bulkheads - Per-route concurrency limits with adaptive load shedding
+//!
+//! Each configured route gets its own pool of slots, so a slow `/users`
+//! search queues behind itself instead of starving `/health`. Requests wait
+//! up to `queue_timeout` for a slot and are then rejected with 503 and
+//! `Retry-After`.
+//!
+//! With load shedding on, the limit adapts (AIMD): a request that waited
+//! longer than `target_queue` is shed right away and the limit shrinks by
+//! `DECREASE_FACTOR`; a request admitted under the target while every slot
+//! was busy grows it by one, up to `max_concurrent`.
+
+use crate::api::users::gates::gate_result::GateError;
+use crate::shared::config::settings::{BulkheadSettings, LoadSheddingSettings, ResilienceSettings};
+use crate::shared::errors::api_error::ApiError;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::observability::metrics::{BULKHEAD_LIMIT, BULKHEAD_REJECTED_TOTAL};
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, HeaderValue},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+const GATE_NAME: &str = "BulkheadGate";
+
+/// Multiplicative decrease applied to the limit on every shed request
+pub const DECREASE_FACTOR: f64 = 0.9;
+
+#[derive(Debug, Clone)]
+pub struct LoadShedding {
+    /// Queueing above this is overload
+    pub target_queue: Duration,
+    /// The adaptive limit never goes below this
+    pub min_limit: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    /// No slot freed up within the queue timeout
+    QueueTimeout,
+    /// Queued longer than the load-shedding target
+    Shed,
+}
+
+impl Rejection {
+    fn as_str(self) -> &'static str {
+        match self {
+            Rejection::QueueTimeout => "queue_timeout",
+            Rejection::Shed => "shed",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Slots {
+    in_flight: usize,
+    limit: usize,
+}
+
+#[derive(Debug)]
+pub struct Bulkhead {
+    route: String,
+    max_concurrent: usize,
+    queue_timeout: Duration,
+    shedding: Option<LoadShedding>,
+    slots: Mutex<Slots>,
+    released: Notify,
+}
+
+impl Bulkhead {
+    pub fn new(route: &str, max_concurrent: usize, queue_timeout: Duration, shedding: Option<LoadShedding>) -> Arc<Self> {
+        let bulkhead = Arc::new(Self {
+            route: route.to_string(),
+            max_concurrent,
+            queue_timeout,
+            shedding,
+            slots: Mutex::new(Slots {
+                in_flight: 0,
+                limit: max_concurrent,
+            }),
+            released: Notify::new(),
+        });
+        bulkhead.export_limit(max_concurrent);
+        bulkhead
+    }
+
+    pub fn limit(&self) -> usize {
+        self.lock().limit
+    }
+
+    /// Waits for a slot; the slot is freed when the permit is dropped
+    pub async fn acquire(self: &Arc<Self>) -> Result<BulkheadPermit, Rejection> {
+        let started = Instant::now();
+        let wait = match &self.shedding {
+            Some(shedding) => self.queue_timeout.min(shedding.target_queue),
+            None => self.queue_timeout,
+        };
+        let deadline = started + wait;
+
+        loop {
+            let released = self.released.notified();
+            tokio::pin!(released);
+            // Registers interest before checking, so a release in between is not missed
+            released.as_mut().enable();
+
+            if let Some(saturated) = self.try_take() {
+                if saturated && self.shedding.is_some() {
+                    self.adjust_limit(|limit| limit + 1);
+                }
+                return Ok(BulkheadPermit { bulkhead: self.clone() });
+            }
+
+            if tokio::time::timeout_at(deadline, released).await.is_err() {
+                let rejection = match &self.shedding {
+                    Some(shedding) if started.elapsed() >= shedding.target_queue => {
+                        let min_limit = shedding.min_limit;
+                        self.adjust_limit(|limit| ((limit as f64 * DECREASE_FACTOR) as usize).max(min_limit));
+                        Rejection::Shed
+                    }
+                    _ => Rejection::QueueTimeout,
+                };
+                metrics::counter!(BULKHEAD_REJECTED_TOTAL, "route" => self.route.clone(), "reason" => rejection.as_str())
+                    .increment(1);
+                return Err(rejection);
+            }
+        }
+    }
+
+    /// Takes a slot if one is free; `Some(true)` when it was the last one
+    fn try_take(&self) -> Option<bool> {
+        let mut slots = self.lock();
+        if slots.in_flight < slots.limit {
+            slots.in_flight += 1;
+            Some(slots.in_flight == slots.limit)
+        } else {
+            None
+        }
+    }
+
+    fn adjust_limit(&self, adjust: impl FnOnce(usize) -> usize) {
+        let limit = {
+            let mut slots = self.lock();
+            slots.limit = adjust(slots.limit).clamp(1, self.max_concurrent);
+            slots.limit
+        };
+        self.export_limit(limit);
+        // A larger limit may admit waiters
+        self.released.notify_waiters();
+    }
+
+    fn export_limit(&self, limit: usize) {
+        metrics::gauge!(BULKHEAD_LIMIT, "route" => self.route.clone()).set(limit as f64);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Slots> {
+        self.slots.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[derive(Debug)]
+pub struct BulkheadPermit {
+    bulkhead: Arc<Bulkhead>,
+}
+
+impl Drop for BulkheadPermit {
+    fn drop(&mut self) {
+        self.bulkhead.lock().in_flight -= 1;
+        self.bulkhead.released.notify_one();
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Bulkheads {
+    routes: Arc<HashMap<String, Arc<Bulkhead>>>,
+    retry_after: HeaderValue,
+}
+
+impl Bulkheads {
+    fn from_settings(bulkheads: &[BulkheadSettings], shedding: &LoadSheddingSettings) -> Self {
+        let load_shedding = shedding.enabled.then(|| LoadShedding {
+            target_queue: Duration::from_millis(shedding.target_queue_ms),
+            min_limit: shedding.min_limit,
+        });
+
+        Self {
+            routes: Arc::new(
+                bulkheads
+                    .iter()
+                    .map(|settings| {
+                        let bulkhead = Bulkhead::new(
+                            &settings.route,
+                            settings.max_concurrent,
+                            Duration::from_millis(settings.queue_timeout_ms),
+                            load_shedding.clone(),
+                        );
+                        (settings.route.clone(), bulkhead)
+                    })
+                    .collect(),
+            ),
+            retry_after: HeaderValue::from(shedding.retry_after_secs),
+        }
+    }
+}
+
+async fn enforce_bulkhead(State(bulkheads): State<Bulkheads>, request: Request, next: Next) -> Response {
+    let bulkhead = request
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|route| bulkheads.routes.get(route.as_str()))
+        .cloned();
+
+    let Some(bulkhead) = bulkhead else {
+        return next.run(request).await;
+    };
+
+    match bulkhead.acquire().await {
+        Ok(_permit) => next.run(request).await,
+        Err(rejection) => {
+            let code = match rejection {
+                Rejection::QueueTimeout => ErrorCode::BulkheadFull,
+                Rejection::Shed => ErrorCode::LoadShed,
+            };
+            let error: GateError = code.gate_error(GATE_NAME);
+            ([(header::RETRY_AFTER, bulkheads.retry_after.clone())], ApiError::from(error)).into_response()
+        }
+    }
+}
+
+/// Puts every configured route behind its own bulkhead; apply after the
+/// routes are registered (the route is looked up by its matched path)
+pub fn apply_bulkheads(router: Router, settings: &ResilienceSettings) -> Router {
+    if settings.bulkheads.is_empty() {
+        return router;
+    }
+
+    let bulkheads = Bulkheads::from_settings(&settings.bulkheads, &settings.load_shedding);
+    router.layer(middleware::from_fn_with_state(bulkheads, enforce_bulkhead))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, routing::get};
+    use tower::ServiceExt;
+
+    fn shedding() -> Option<LoadShedding> {
+        Some(LoadShedding {
+            target_queue: Duration::from_millis(50),
+            min_limit: 1,
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_waits_for_a_slot_then_times_out() {
+        let bulkhead = Bulkhead::new("/search", 1, Duration::from_millis(100), None);
+        let held = bulkhead.acquire().await.unwrap();
+
+        let waiter = tokio::spawn({
+            let bulkhead = bulkhead.clone();
+            async move { bulkhead.acquire().await.map(|_| ()) }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(held);
+        assert_eq!(waiter.await.unwrap(), Ok(()));
+
+        let _held = bulkhead.acquire().await.unwrap();
+        assert_eq!(bulkhead.acquire().await.unwrap_err(), Rejection::QueueTimeout);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shedding_shrinks_and_regrows_the_limit() {
+        let bulkhead = Bulkhead::new("/search", 20, Duration::from_secs(1), shedding());
+        let held: Vec<_> = futures::future::join_all((0..20).map(|_| bulkhead.acquire())).await;
+
+        let started = Instant::now();
+        assert_eq!(bulkhead.acquire().await.unwrap_err(), Rejection::Shed);
+        assert_eq!(started.elapsed(), Duration::from_millis(50));
+        assert_eq!(bulkhead.limit(), 18);
+
+        drop(held);
+        let _permits: Vec<_> = futures::future::join_all((0..18).map(|_| bulkhead.acquire())).await;
+        assert_eq!(bulkhead.limit(), 19);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_limit_never_drops_below_minimum() {
+        let bulkhead = Bulkhead::new("/search", 2, Duration::from_secs(1), shedding());
+        let _held = (bulkhead.acquire().await.unwrap(), bulkhead.acquire().await.unwrap());
+
+        for _ in 0..5 {
+            bulkhead.acquire().await.unwrap_err();
+        }
+
+        assert_eq!(bulkhead.limit(), 1);
+    }
+
+    async fn slow() -> &'static str {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        "results"
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_saturated_route_does_not_starve_others() {
+        let settings = ResilienceSettings {
+            bulkheads: vec![BulkheadSettings {
+                route: "/search".to_string(),
+                max_concurrent: 1,
+                queue_timeout_ms: 100,
+            }],
+            ..crate::shared::config::settings::Settings::defaults(crate::shared::config::settings::Profile::Testing)
+                .resilience
+        };
+        let app = apply_bulkheads(
+            Router::new().route("/search", get(slow)).route("/health", get(|| async { "ok" })),
+            &settings,
+        );
+        let request = |uri: &str| axum::http::Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let in_flight = tokio::spawn(app.clone().oneshot(request("/search")));
+        tokio::task::yield_now().await;
+
+        let rejected = app.clone().oneshot(request("/search")).await.unwrap();
+        assert_eq!(rejected.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(rejected.headers()["retry-after"], settings.load_shedding.retry_after_secs.to_string());
+
+        let health = app.clone().oneshot(request("/health")).await.unwrap();
+        assert_eq!(health.status(), StatusCode::OK);
+        assert_eq!(in_flight.await.unwrap().unwrap().status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_rejections_are_exported_as_metrics() {
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_time().start_paused(true).build().unwrap();
+
+        metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(async {
+                let bulkhead = Bulkhead::new("/metrics-route", 1, Duration::from_millis(10), None);
+                let _held = bulkhead.acquire().await.unwrap();
+                bulkhead.acquire().await.unwrap_err();
+            })
+        });
+
+        let rendered = handle.render();
+        assert!(rendered.contains("bulkhead_rejected_total{route=\"/metrics-route\",reason=\"queue_timeout\"} 1"));
+        assert!(rendered.contains("bulkhead_limit{route=\"/metrics-route\"} 1"));
+    }
+}
@@ -0,0 +1,610 @@
+//! BRIK v5 Circuit Breaker - Fails fast while a port is unhealthy
+//!
+//! Outcomes of the last `window_size` calls are tracked. Once at least
+//! `minimum_calls` were recorded and the failure rate or the slow-call rate
+//! reaches its threshold, the circuit opens and every call is rejected with
+//! a `CIRCUIT_OPEN` port error without touching the port. After
+//! `open_duration` a few trial calls are let through (half-open); their
+//! outcome decides whether the circuit closes or opens again.
+//!
+//! Ports are wrapped with `call` (decorators such as
+//! `CircuitBreakingUserRepository`) or with `CircuitBreakerLayer` for tower
+//! services such as outbound HTTP clients.
+
+use crate::shared::config::settings::CircuitBreakerSettings;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::port_error::PortError;
+use crate::brik_log;
+use crate::shared::observability::metrics::{
+    CIRCUIT_BREAKER_REJECTED_TOTAL, CIRCUIT_BREAKER_STATE, CIRCUIT_BREAKER_TRANSITIONS_TOTAL,
+};
+use crate::shared::time::clock::SharedClock;
+use crate::shared::types::result::BrikResult;
+use futures::future::BoxFuture;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tower::{Layer, Service};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+
+    /// Value of the state gauge
+    fn gauge_value(self) -> f64 {
+        match self {
+            CircuitState::Closed => 0.0,
+            CircuitState::Open => 1.0,
+            CircuitState::HalfOpen => 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Share of failed calls (0.0-1.0) that opens the circuit
+    pub failure_rate_threshold: f64,
+    /// Share of calls slower than `slow_call_duration` that opens the circuit
+    pub slow_call_rate_threshold: f64,
+    pub slow_call_duration: Duration,
+    /// Number of most recent calls the rates are computed over
+    pub window_size: usize,
+    /// Calls needed in the window before the rates are evaluated
+    pub minimum_calls: usize,
+    pub open_duration: Duration,
+    /// Trial calls let through while half-open
+    pub half_open_calls: usize,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_rate_threshold: 0.5,
+            slow_call_rate_threshold: 1.0,
+            slow_call_duration: Duration::from_secs(2),
+            window_size: 20,
+            minimum_calls: 10,
+            open_duration: Duration::from_secs(30),
+            half_open_calls: 3,
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    pub fn from_settings(settings: &CircuitBreakerSettings) -> Self {
+        Self {
+            failure_rate_threshold: settings.failure_rate_threshold,
+            slow_call_rate_threshold: settings.slow_call_rate_threshold,
+            slow_call_duration: Duration::from_millis(settings.slow_call_duration_ms),
+            window_size: settings.window_size,
+            minimum_calls: settings.minimum_calls,
+            open_duration: Duration::from_millis(settings.open_duration_ms),
+            half_open_calls: settings.half_open_calls,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Outcome {
+    failed: bool,
+    slow: bool,
+}
+
+#[derive(Debug)]
+struct Window {
+    state: CircuitState,
+    /// Bumped on every transition so late outcomes of an earlier state are dropped
+    generation: u64,
+    outcomes: VecDeque<Outcome>,
+    opened_at: Instant,
+    half_open_in_flight: usize,
+}
+
+impl Window {
+    fn rates(&self) -> (f64, f64) {
+        let total = self.outcomes.len().max(1) as f64;
+        let failed = self.outcomes.iter().filter(|outcome| outcome.failed).count() as f64;
+        let slow = self.outcomes.iter().filter(|outcome| outcome.slow).count() as f64;
+        (failed / total, slow / total)
+    }
+}
+
+struct Shared {
+    port: String,
+    config: CircuitBreakerConfig,
+    /// Open duration and call timings
+    clock: SharedClock,
+    window: Mutex<Window>,
+}
+
+impl fmt::Debug for Shared {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shared")
+            .field("port", &self.port)
+            .field("config", &self.config)
+            .field("window", &self.window)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Cheap to clone; clones share the same circuit
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    shared: Arc<Shared>,
+}
+
+impl CircuitBreaker {
+    pub fn new(port: &str, config: CircuitBreakerConfig, clock: SharedClock) -> Self {
+        metrics::gauge!(CIRCUIT_BREAKER_STATE, "port" => port.to_string()).set(CircuitState::Closed.gauge_value());
+
+        Self {
+            shared: Arc::new(Shared {
+                port: port.to_string(),
+                window: Mutex::new(Window {
+                    state: CircuitState::Closed,
+                    generation: 0,
+                    outcomes: VecDeque::with_capacity(config.window_size),
+                    opened_at: clock.instant(),
+                    half_open_in_flight: 0,
+                }),
+                config,
+                clock,
+            }),
+        }
+    }
+
+    pub fn port(&self) -> &str {
+        &self.shared.port
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let mut window = self.lock();
+        self.expire_open(&mut window);
+        window.state
+    }
+
+    /// Runs `operation` unless the circuit is open; every error counts as a failure
+    pub async fn call<T, E, F>(&self, operation: F) -> BrikResult<T, E>
+    where
+        F: Future<Output = BrikResult<T, E>>,
+        E: From<PortError>,
+    {
+        self.call_classified(operation, |_| true).await
+    }
+
+    /// Like `call`, but only errors for which `is_failure` holds count
+    /// against the port (a unique violation says nothing about its health)
+    pub async fn call_classified<T, E, F, C>(&self, operation: F, is_failure: C) -> BrikResult<T, E>
+    where
+        F: Future<Output = BrikResult<T, E>>,
+        E: From<PortError>,
+        C: FnOnce(&E) -> bool,
+    {
+        let permit = self.try_acquire()?;
+        let started = self.shared.clock.instant();
+        let result = operation.await;
+        permit.record(result.as_ref().err().is_some_and(is_failure), self.elapsed_since(started));
+        result
+    }
+
+    /// Admits a call or fails fast with `CIRCUIT_OPEN`
+    pub fn try_acquire(&self) -> BrikResult<Permit, PortError> {
+        let mut window = self.lock();
+        self.expire_open(&mut window);
+
+        let admitted = match window.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen => {
+                let trials = window.half_open_in_flight + window.outcomes.len();
+                if trials < self.shared.config.half_open_calls {
+                    window.half_open_in_flight += 1;
+                    true
+                } else {
+                    false
+                }
+            }
+        };
+
+        if !admitted {
+            metrics::counter!(CIRCUIT_BREAKER_REJECTED_TOTAL, "port" => self.shared.port.clone()).increment(1);
+            return Err(ErrorCode::CircuitOpen.port_error(
+                &self.shared.port,
+                &format!("Circuit breaker for {} is open; failing fast", self.shared.port),
+            ));
+        }
+
+        Ok(Permit {
+            breaker: self.clone(),
+            generation: window.generation,
+            half_open: window.state == CircuitState::HalfOpen,
+            recorded: false,
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Window> {
+        self.shared.window.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn elapsed_since(&self, started: Instant) -> Duration {
+        self.shared.clock.instant().saturating_duration_since(started)
+    }
+
+    fn expire_open(&self, window: &mut Window) {
+        let open_duration = self.shared.config.open_duration;
+        if window.state == CircuitState::Open && self.elapsed_since(window.opened_at) >= open_duration {
+            self.transition(window, CircuitState::HalfOpen);
+        }
+    }
+
+    fn record(&self, generation: u64, outcome: Option<Outcome>) {
+        let mut window = self.lock();
+        if window.generation != generation {
+            return;
+        }
+
+        let config = &self.shared.config;
+        match window.state {
+            CircuitState::Closed => {
+                let Some(outcome) = outcome else { return };
+                if window.outcomes.len() == config.window_size {
+                    window.outcomes.pop_front();
+                }
+                window.outcomes.push_back(outcome);
+
+                if window.outcomes.len() >= config.minimum_calls && self.exceeds_thresholds(&window) {
+                    self.transition(&mut window, CircuitState::Open);
+                }
+            }
+            CircuitState::HalfOpen => {
+                window.half_open_in_flight -= 1;
+                let Some(outcome) = outcome else { return };
+                window.outcomes.push_back(outcome);
+
+                if window.outcomes.len() >= config.half_open_calls {
+                    let next = if self.exceeds_thresholds(&window) {
+                        CircuitState::Open
+                    } else {
+                        CircuitState::Closed
+                    };
+                    self.transition(&mut window, next);
+                }
+            }
+            CircuitState::Open => {}
+        }
+    }
+
+    fn exceeds_thresholds(&self, window: &Window) -> bool {
+        let (failure_rate, slow_rate) = window.rates();
+        failure_rate >= self.shared.config.failure_rate_threshold
+            || slow_rate >= self.shared.config.slow_call_rate_threshold
+    }
+
+    fn transition(&self, window: &mut Window, to: CircuitState) {
+        let from = window.state;
+        let (failure_rate, slow_rate) = window.rates();
+
+        window.state = to;
+        window.generation += 1;
+        window.outcomes.clear();
+        window.half_open_in_flight = 0;
+        if to == CircuitState::Open {
+            window.opened_at = self.shared.clock.instant();
+        }
+
+        let port = self.shared.port.clone();
+        metrics::gauge!(CIRCUIT_BREAKER_STATE, "port" => port.clone()).set(to.gauge_value());
+        metrics::counter!(CIRCUIT_BREAKER_TRANSITIONS_TOTAL, "port" => port.clone(), "state" => to.as_str())
+            .increment(1);
+
+        match to {
+            CircuitState::Open => brik_log!(
+                WARN,
+                "Circuit breaker opened",
+                port = port.as_str(),
+                from = from.as_str(),
+                to = to.as_str(),
+                failure_rate = failure_rate,
+                slow_call_rate = slow_rate
+            ),
+            _ => brik_log!(
+                INFO,
+                "Circuit breaker state changed",
+                port = port.as_str(),
+                from = from.as_str(),
+                to = to.as_str(),
+                failure_rate = failure_rate,
+                slow_call_rate = slow_rate
+            ),
+        }
+    }
+}
+
+/// Admission to call the port; report the outcome with `record`. Dropping
+/// it unrecorded (a cancelled call) frees a half-open trial slot.
+#[derive(Debug)]
+pub struct Permit {
+    breaker: CircuitBreaker,
+    generation: u64,
+    half_open: bool,
+    recorded: bool,
+}
+
+impl Permit {
+    pub fn record(mut self, failed: bool, elapsed: Duration) {
+        self.recorded = true;
+        let slow = elapsed >= self.breaker.shared.config.slow_call_duration;
+        self.breaker.record(self.generation, Some(Outcome { failed, slow }));
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if !self.recorded && self.half_open {
+            self.breaker.record(self.generation, None);
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CircuitBreakerError<E> {
+    #[error("{0}")]
+    Open(PortError),
+    #[error("{0}")]
+    Inner(E),
+}
+
+/// Tower form of the breaker. Only `Err` results count as failures, so
+/// outbound HTTP clients should turn 5xx responses into errors below it.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerLayer {
+    breaker: CircuitBreaker,
+}
+
+impl CircuitBreakerLayer {
+    pub fn new(breaker: CircuitBreaker) -> Self {
+        Self { breaker }
+    }
+}
+
+impl<S> Layer<S> for CircuitBreakerLayer {
+    type Service = CircuitBreakerService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CircuitBreakerService {
+            inner,
+            breaker: self.breaker.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerService<S> {
+    inner: S,
+    breaker: CircuitBreaker,
+}
+
+impl<S, Request> Service<Request> for CircuitBreakerService<S>
+where
+    S: Service<Request>,
+    S::Future: Send + 'static,
+    S::Response: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = CircuitBreakerError<S::Error>;
+    type Future = BoxFuture<'static, Result<S::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(CircuitBreakerError::Inner)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let permit = match self.breaker.try_acquire() {
+            Ok(permit) => permit,
+            Err(error) => return Box::pin(std::future::ready(Err(CircuitBreakerError::Open(error)))),
+        };
+        let response = self.inner.call(request);
+
+        let breaker = self.breaker.clone();
+
+        Box::pin(async move {
+            let started = breaker.shared.clock.instant();
+            let result = response.await;
+            permit.record(result.is_err(), breaker.elapsed_since(started));
+            result.map_err(CircuitBreakerError::Inner)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::time::clock::{ManualClock, SystemClock};
+    use tower::ServiceExt;
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_rate_threshold: 0.5,
+            slow_call_rate_threshold: 0.5,
+            slow_call_duration: Duration::from_millis(100),
+            window_size: 4,
+            minimum_calls: 4,
+            open_duration: Duration::from_secs(10),
+            half_open_calls: 2,
+        }
+    }
+
+    fn down() -> PortError {
+        PortError::new("TestPort", "DATABASE_UNAVAILABLE", "down")
+    }
+
+    async fn run(breaker: &CircuitBreaker, fail: bool) -> BrikResult<(), PortError> {
+        breaker.call(async { if fail { Err(down()) } else { Ok(()) } }).await
+    }
+
+    fn breaker(config: CircuitBreakerConfig) -> (Arc<ManualClock>, CircuitBreaker) {
+        let clock = ManualClock::starting_now();
+        (clock.clone(), CircuitBreaker::new("TestPort", config, clock))
+    }
+
+    #[tokio::test]
+    async fn test_opens_on_failure_rate_and_fails_fast() {
+        let (_, breaker) = breaker(config());
+
+        for fail in [false, true, false] {
+            let _ = run(&breaker, fail).await;
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        let _ = run(&breaker, true).await;
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        let mut called = false;
+        let error = breaker
+            .call(async {
+                called = true;
+                Ok::<_, PortError>(())
+            })
+            .await
+            .unwrap_err();
+        assert!(!called);
+        assert_eq!(error.port, "TestPort");
+        assert_eq!(error.code, "CIRCUIT_OPEN");
+    }
+
+    #[tokio::test]
+    async fn test_opens_on_slow_calls() {
+        let (clock, breaker) = breaker(config());
+
+        for _ in 0..2 {
+            let _ = run(&breaker, false).await;
+            breaker
+                .call(async {
+                    clock.advance(Duration::from_millis(150));
+                    Ok::<_, PortError>(())
+                })
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(breaker.state(), CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_half_open_trials_close_or_reopen() {
+        let (clock, breaker) = breaker(config());
+        for _ in 0..4 {
+            let _ = run(&breaker, true).await;
+        }
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // Only `half_open_calls` trials are admitted at once
+        let first = breaker.try_acquire().unwrap();
+        let second = breaker.try_acquire().unwrap();
+        assert!(breaker.try_acquire().is_err());
+        first.record(true, Duration::ZERO);
+        second.record(false, Duration::ZERO);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        clock.advance(Duration::from_secs(10));
+        run(&breaker, false).await.unwrap();
+        run(&breaker, false).await.unwrap();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_goes_half_open_after_open_duration() {
+        let (clock, breaker) = breaker(config());
+        for _ in 0..4 {
+            breaker.try_acquire().unwrap().record(true, Duration::ZERO);
+        }
+
+        clock.advance(Duration::from_secs(9));
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.try_acquire().is_err());
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.try_acquire().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_dropped_trial_frees_its_slot() {
+        let (clock, breaker) = breaker(CircuitBreakerConfig { half_open_calls: 1, ..config() });
+        for _ in 0..4 {
+            let _ = run(&breaker, true).await;
+        }
+        clock.advance(Duration::from_secs(10));
+
+        drop(breaker.try_acquire().unwrap());
+
+        assert!(breaker.try_acquire().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_classified_errors_do_not_trip() {
+        let (_, breaker) = breaker(config());
+
+        for _ in 0..4 {
+            let _ = breaker
+                .call_classified(async { Err::<(), _>(down()) }, |error: &PortError| error.code != "DATABASE_UNAVAILABLE")
+                .await;
+        }
+
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_layer_fails_fast_once_open() {
+        let breaker = CircuitBreaker::new("PaymentsApi", config(), SystemClock::shared());
+        let service = tower::ServiceBuilder::new()
+            .layer(CircuitBreakerLayer::new(breaker.clone()))
+            .service_fn(|fail: bool| async move { if fail { Err("503 from upstream") } else { Ok("ok") } });
+
+        for _ in 0..4 {
+            let error = service.clone().oneshot(true).await.unwrap_err();
+            assert!(matches!(error, CircuitBreakerError::Inner("503 from upstream")));
+        }
+
+        let error = service.clone().oneshot(false).await.unwrap_err();
+        assert!(matches!(error, CircuitBreakerError::Open(ref port_error) if port_error.port == "PaymentsApi"));
+    }
+
+    #[test]
+    fn test_transitions_are_exported_as_metrics() {
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            let breaker = CircuitBreaker::new("MetricsPort", config(), SystemClock::shared());
+            for _ in 0..4 {
+                breaker.try_acquire().unwrap().record(true, Duration::ZERO);
+            }
+            assert!(breaker.try_acquire().is_err());
+        });
+
+        let rendered = handle.render();
+        assert!(rendered.contains("circuit_breaker_state{port=\"MetricsPort\"} 1"));
+        assert!(rendered.contains("circuit_breaker_transitions_total{port=\"MetricsPort\",state=\"open\"} 1"));
+        assert!(rendered.contains("circuit_breaker_rejected_total{port=\"MetricsPort\"} 1"));
+    }
+}
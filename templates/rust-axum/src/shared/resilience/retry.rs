@@ -0,0 +1,299 @@
+//! BRIK v5 Retry Policy - Retries transient port failures with backoff
+//!
+//! Only operations marked `Idempotency::Idempotent` are retried; anything
+//! else runs once, since repeating a write that may have been applied can
+//! turn a lost reply into a duplicate. Between attempts the policy sleeps a
+//! random delay in `[0, min(max_delay, base_delay * 2^(attempt-1))]` (full
+//! jitter). The whole operation, attempts and sleeps included, must fit in
+//! the `deadline` budget; past it the call fails with `DEADLINE_EXCEEDED`.
+//!
+//! Compose it outside a circuit breaker: `CIRCUIT_OPEN` is not retryable,
+//! so an open circuit still fails fast. Caches are not retried, a miss is
+//! cheaper than waiting.
+
+use crate::shared::config::settings::RetrySettings;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::port_error::PortError;
+use crate::brik_log;
+use crate::shared::observability::metrics::PORT_RETRIES_TOTAL;
+use crate::shared::types::result::BrikResult;
+use rand::Rng;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Codes that describe an unreachable or briefly failing dependency
+pub const RETRYABLE_CODES: [ErrorCode; 4] = [
+    ErrorCode::DatabaseUnavailable,
+    ErrorCode::CacheUnavailable,
+    ErrorCode::StorageUnavailable,
+    ErrorCode::PublishFailed,
+];
+
+pub fn is_retryable(error: &PortError) -> bool {
+    RETRYABLE_CODES.iter().any(|code| error.has_code(*code))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idempotency {
+    /// Safe to repeat: reads, upserts, deletes by key
+    Idempotent,
+    /// Runs exactly once
+    NonIdempotent,
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Attempts including the first one
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Budget for the whole operation
+    pub deadline: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(1),
+            deadline: Duration::from_secs(3),
+        }
+    }
+}
+
+impl RetryConfig {
+    pub fn from_settings(settings: &RetrySettings) -> Self {
+        Self {
+            max_attempts: settings.max_attempts,
+            base_delay: Duration::from_millis(settings.base_delay_ms),
+            max_delay: Duration::from_millis(settings.max_delay_ms),
+            deadline: Duration::from_millis(settings.deadline_ms),
+        }
+    }
+
+    /// Upper bound of the sleep after failed attempt `attempt` (1-based)
+    pub fn backoff_cap(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    port: String,
+    config: RetryConfig,
+    classifier: fn(&PortError) -> bool,
+}
+
+impl RetryPolicy {
+    pub fn new(port: &str, config: RetryConfig) -> Self {
+        Self {
+            port: port.to_string(),
+            config,
+            classifier: is_retryable,
+        }
+    }
+
+    /// Replaces `is_retryable` for ports with their own transient errors
+    pub fn with_classifier(mut self, classifier: fn(&PortError) -> bool) -> Self {
+        self.classifier = classifier;
+        self
+    }
+
+    pub async fn run<T, F, Fut>(&self, idempotency: Idempotency, mut operation: F) -> BrikResult<T, PortError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = BrikResult<T, PortError>>,
+    {
+        let deadline = Instant::now() + self.config.deadline;
+        let max_attempts = match idempotency {
+            Idempotency::Idempotent => self.config.max_attempts.max(1),
+            Idempotency::NonIdempotent => 1,
+        };
+        let mut attempt = 1;
+
+        loop {
+            let error = match tokio::time::timeout_at(deadline, operation()).await {
+                Ok(Ok(value)) => return Ok(value),
+                Ok(Err(error)) => error,
+                Err(_) => return Err(self.deadline_exceeded(attempt)),
+            };
+
+            if attempt >= max_attempts || !(self.classifier)(&error) {
+                return Err(error);
+            }
+
+            let delay = jittered(self.config.backoff_cap(attempt));
+            if Instant::now() + delay >= deadline {
+                return Err(error);
+            }
+
+            metrics::counter!(PORT_RETRIES_TOTAL, "port" => self.port.clone(), "code" => error.code.clone())
+                .increment(1);
+            brik_log!(
+                DEBUG,
+                "Retrying port call",
+                port = self.port.as_str(),
+                attempt = attempt,
+                code = error.code.as_str(),
+                delay_ms = delay.as_millis() as u64
+            );
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    fn deadline_exceeded(&self, attempt: u32) -> PortError {
+        ErrorCode::DeadlineExceeded.port_error(
+            &self.port,
+            &format!(
+                "{} did not answer within {}ms ({} attempts)",
+                self.port,
+                self.config.deadline.as_millis(),
+                attempt
+            ),
+        )
+    }
+}
+
+fn jittered(cap: Duration) -> Duration {
+    if cap.is_zero() {
+        return cap;
+    }
+    rand::thread_rng().gen_range(Duration::ZERO..=cap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::new(
+            "TestPort",
+            RetryConfig {
+                max_attempts: 4,
+                base_delay: Duration::from_millis(100),
+                max_delay: Duration::from_millis(300),
+                deadline: Duration::from_secs(5),
+            },
+        )
+    }
+
+    fn unavailable() -> PortError {
+        PortError::new("TestPort", "DATABASE_UNAVAILABLE", "connection refused")
+    }
+
+    /// Fails `failures` times, then succeeds; returns the attempt count
+    async fn flaky(calls: &AtomicU32, failures: u32) -> BrikResult<u32, PortError> {
+        let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+        if call <= failures { Err(unavailable()) } else { Ok(call) }
+    }
+
+    #[test]
+    fn test_backoff_cap_doubles_up_to_max_delay() {
+        let config = policy().config;
+
+        assert_eq!(config.backoff_cap(1), Duration::from_millis(100));
+        assert_eq!(config.backoff_cap(2), Duration::from_millis(200));
+        assert_eq!(config.backoff_cap(3), Duration::from_millis(300));
+        assert_eq!(config.backoff_cap(40), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_cap() {
+        for _ in 0..100 {
+            assert!(jittered(Duration::from_millis(50)) <= Duration::from_millis(50));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retries_transient_failures() {
+        let calls = AtomicU32::new(0);
+        let started = Instant::now();
+
+        let attempts = policy().run(Idempotency::Idempotent, || flaky(&calls, 2)).await.unwrap();
+
+        assert_eq!(attempts, 3);
+        // Two sleeps, each at most its cap
+        assert!(started.elapsed() <= Duration::from_millis(300));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+
+        let error = policy().run(Idempotency::Idempotent, || flaky(&calls, 10)).await.unwrap_err();
+
+        assert_eq!(error.code, "DATABASE_UNAVAILABLE");
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_non_idempotent_operations_run_once() {
+        let calls = AtomicU32::new(0);
+
+        policy().run(Idempotency::NonIdempotent, || flaky(&calls, 1)).await.unwrap_err();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_non_retryable_errors_are_returned_at_once() {
+        let calls = AtomicU32::new(0);
+
+        let error = policy()
+            .run(Idempotency::Idempotent, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(PortError::new("TestPort", "CIRCUIT_OPEN", "open"))
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.code, "CIRCUIT_OPEN");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_deadline_cuts_a_hanging_attempt() {
+        let policy = RetryPolicy::new(
+            "TestPort",
+            RetryConfig {
+                deadline: Duration::from_millis(500),
+                ..policy().config
+            },
+        );
+        let started = Instant::now();
+
+        let error = policy
+            .run(Idempotency::Idempotent, || async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok(())
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.code, "DEADLINE_EXCEEDED");
+        assert_eq!(error.port, "TestPort");
+        assert_eq!(started.elapsed(), Duration::from_millis(500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_custom_classifier() {
+        let calls = AtomicU32::new(0);
+        let policy = policy().with_classifier(|error| error.code == "LOCK_TIMEOUT");
+
+        policy
+            .run(Idempotency::Idempotent, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(unavailable())
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
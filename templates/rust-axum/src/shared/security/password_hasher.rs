@@ -0,0 +1,324 @@
+//! BRIK v5 Password Hasher Port - Hashing and verification of user passwords
+//!
+//! Stored hashes are self-describing (`$2b$12$...` for bcrypt, PHC strings
+//! such as `$argon2id$v=19$m=19456,t=2,p=1$...` for argon2id), so every
+//! implementation verifies both formats. When a password matches a hash made
+//! with another algorithm or outdated parameters, `verify` returns a fresh
+//! hash for the caller to store in place of the old one.
+//!
+//! Hashing is deliberately slow, so all work runs on the blocking pool.
+
+use crate::shared::config::settings::PasswordSettings;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::types::result::BrikResult;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier as _, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use std::str::FromStr;
+use std::sync::Arc;
+
+const PORT_NAME: &str = "PasswordHasher";
+
+pub const DEFAULT_BCRYPT_COST: u32 = 12;
+/// OWASP baseline for argon2id: 19 MiB, 2 iterations, 1 lane
+pub const DEFAULT_ARGON2_MEMORY_KIB: u32 = 19_456;
+pub const DEFAULT_ARGON2_ITERATIONS: u32 = 2;
+pub const DEFAULT_ARGON2_PARALLELISM: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordVerification {
+    Invalid,
+    /// `rehashed` is set when the stored hash is outdated; persist it
+    Valid { rehashed: Option<String> },
+}
+
+impl PasswordVerification {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, PasswordVerification::Valid { .. })
+    }
+}
+
+#[async_trait::async_trait]
+pub trait PasswordHasher: Send + Sync {
+    async fn hash(&self, password: &str) -> BrikResult<String, PortError>;
+
+    async fn verify(&self, password: &str, stored_hash: &str) -> BrikResult<PasswordVerification, PortError>;
+}
+
+/// Synchronous half of a hasher, run on the blocking pool
+trait HashScheme: Clone + Send + Sync + 'static {
+    fn hash_blocking(&self, password: &str) -> BrikResult<String, PortError>;
+
+    /// Whether `stored_hash` already uses this scheme and its parameters
+    fn is_current(&self, stored_hash: &str) -> bool;
+}
+
+async fn hash_with<S: HashScheme>(scheme: &S, password: &str) -> BrikResult<String, PortError> {
+    let scheme = scheme.clone();
+    let password = password.to_string();
+    run_blocking(move || scheme.hash_blocking(&password)).await
+}
+
+async fn verify_with<S: HashScheme>(
+    scheme: &S,
+    password: &str,
+    stored_hash: &str,
+) -> BrikResult<PasswordVerification, PortError> {
+    let scheme = scheme.clone();
+    let password = password.to_string();
+    let stored_hash = stored_hash.to_string();
+
+    run_blocking(move || {
+        if !verify_any(&password, &stored_hash)? {
+            return Ok(PasswordVerification::Invalid);
+        }
+
+        let rehashed = if scheme.is_current(&stored_hash) {
+            None
+        } else {
+            Some(scheme.hash_blocking(&password)?)
+        };
+        Ok(PasswordVerification::Valid { rehashed })
+    })
+    .await
+}
+
+async fn run_blocking<T, F>(work: F) -> BrikResult<T, PortError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> BrikResult<T, PortError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| ErrorCode::HasherUnavailable.port_error(PORT_NAME, &e.to_string()))?
+}
+
+fn is_bcrypt(stored_hash: &str) -> bool {
+    ["$2a$", "$2b$", "$2x$", "$2y$"].iter().any(|prefix| stored_hash.starts_with(prefix))
+}
+
+fn invalid_hash(message: &str) -> PortError {
+    ErrorCode::InvalidHash.port_error(PORT_NAME, message)
+}
+
+/// Checks `password` against a hash in any supported format
+fn verify_any(password: &str, stored_hash: &str) -> BrikResult<bool, PortError> {
+    if is_bcrypt(stored_hash) {
+        return bcrypt::verify(password, stored_hash).map_err(|e| invalid_hash(&e.to_string()));
+    }
+
+    if stored_hash.starts_with("$argon2") {
+        let parsed = PasswordHash::new(stored_hash).map_err(|e| invalid_hash(&e.to_string()))?;
+        // Algorithm, version and parameters are taken from the hash itself
+        return match Argon2::default().verify_password(password.as_bytes(), &parsed) {
+            Ok(()) => Ok(true),
+            Err(argon2::password_hash::Error::Password) => Ok(false),
+            Err(e) => Err(invalid_hash(&e.to_string())),
+        };
+    }
+
+    Err(ErrorCode::UnsupportedHash.port_error(PORT_NAME, "Stored password hash has an unknown format"))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BcryptHasher {
+    cost: u32,
+}
+
+impl BcryptHasher {
+    pub fn new(cost: u32) -> Self {
+        Self { cost }
+    }
+}
+
+impl Default for BcryptHasher {
+    fn default() -> Self {
+        Self::new(DEFAULT_BCRYPT_COST)
+    }
+}
+
+#[async_trait::async_trait]
+impl PasswordHasher for BcryptHasher {
+    async fn hash(&self, password: &str) -> BrikResult<String, PortError> {
+        hash_with(self, password).await
+    }
+
+    async fn verify(&self, password: &str, stored_hash: &str) -> BrikResult<PasswordVerification, PortError> {
+        verify_with(self, password, stored_hash).await
+    }
+}
+
+impl HashScheme for BcryptHasher {
+    fn hash_blocking(&self, password: &str) -> BrikResult<String, PortError> {
+        bcrypt::hash(password, self.cost).map_err(|e| ErrorCode::HashFailed.port_error(PORT_NAME, &e.to_string()))
+    }
+
+    fn is_current(&self, stored_hash: &str) -> bool {
+        is_bcrypt(stored_hash)
+            && bcrypt::HashParts::from_str(stored_hash).is_ok_and(|parts| parts.get_cost() == self.cost)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Argon2Hasher {
+    params: Params,
+}
+
+impl Argon2Hasher {
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self, argon2::Error> {
+        Ok(Self {
+            params: Params::new(memory_kib, iterations, parallelism, None)?,
+        })
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+}
+
+impl Default for Argon2Hasher {
+    fn default() -> Self {
+        Self {
+            params: Params::new(
+                DEFAULT_ARGON2_MEMORY_KIB,
+                DEFAULT_ARGON2_ITERATIONS,
+                DEFAULT_ARGON2_PARALLELISM,
+                None,
+            )
+            .expect("default argon2 parameters are valid"),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PasswordHasher for Argon2Hasher {
+    async fn hash(&self, password: &str) -> BrikResult<String, PortError> {
+        hash_with(self, password).await
+    }
+
+    async fn verify(&self, password: &str, stored_hash: &str) -> BrikResult<PasswordVerification, PortError> {
+        verify_with(self, password, stored_hash).await
+    }
+}
+
+impl HashScheme for Argon2Hasher {
+    fn hash_blocking(&self, password: &str) -> BrikResult<String, PortError> {
+        let salt = SaltString::generate(&mut OsRng);
+        self.argon2()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| ErrorCode::HashFailed.port_error(PORT_NAME, &e.to_string()))
+    }
+
+    fn is_current(&self, stored_hash: &str) -> bool {
+        let Ok(parsed) = PasswordHash::new(stored_hash) else {
+            return false;
+        };
+
+        parsed.algorithm == Algorithm::Argon2id.ident()
+            && parsed.version == Some(Version::V0x13.into())
+            && Params::try_from(&parsed).is_ok_and(|params| {
+                params.m_cost() == self.params.m_cost()
+                    && params.t_cost() == self.params.t_cost()
+                    && params.p_cost() == self.params.p_cost()
+            })
+    }
+}
+
+/// Hasher selected by `password.algorithm`; settings are validated at load
+pub fn password_hasher_from_settings(settings: &PasswordSettings) -> Arc<dyn PasswordHasher> {
+    match settings.algorithm.as_str() {
+        "bcrypt" => Arc::new(BcryptHasher::new(settings.bcrypt_cost)),
+        _ => Arc::new(
+            Argon2Hasher::new(
+                settings.argon2_memory_kib,
+                settings.argon2_iterations,
+                settings.argon2_parallelism,
+            )
+            .expect("argon2 parameters are checked by Settings::validate"),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argon2(iterations: u32) -> Argon2Hasher {
+        Argon2Hasher::new(64, iterations, 1).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_bcrypt_round_trip() {
+        let hasher = BcryptHasher::new(4);
+        let hash = hasher.hash("s3cret").await.unwrap();
+
+        assert!(hash.starts_with("$2b$04$"));
+        assert_eq!(
+            hasher.verify("s3cret", &hash).await.unwrap(),
+            PasswordVerification::Valid { rehashed: None }
+        );
+        assert_eq!(hasher.verify("wrong", &hash).await.unwrap(), PasswordVerification::Invalid);
+    }
+
+    #[tokio::test]
+    async fn test_argon2_round_trip() {
+        let hasher = argon2(1);
+        let hash = hasher.hash("s3cret").await.unwrap();
+
+        assert!(hash.starts_with("$argon2id$v=19$m=64,t=1,p=1$"));
+        assert_eq!(
+            hasher.verify("s3cret", &hash).await.unwrap(),
+            PasswordVerification::Valid { rehashed: None }
+        );
+        assert_eq!(hasher.verify("wrong", &hash).await.unwrap(), PasswordVerification::Invalid);
+    }
+
+    #[tokio::test]
+    async fn test_rehashes_outdated_cost() {
+        let old_hash = BcryptHasher::new(4).hash("s3cret").await.unwrap();
+
+        let verification = BcryptHasher::new(5).verify("s3cret", &old_hash).await.unwrap();
+        let PasswordVerification::Valid { rehashed: Some(new_hash) } = verification else {
+            panic!("expected a rehash");
+        };
+
+        assert!(new_hash.starts_with("$2b$05$"));
+    }
+
+    #[tokio::test]
+    async fn test_upgrades_bcrypt_to_argon2() {
+        let old_hash = BcryptHasher::new(4).hash("s3cret").await.unwrap();
+        let hasher = argon2(1);
+
+        let PasswordVerification::Valid { rehashed: Some(new_hash) } = hasher.verify("s3cret", &old_hash).await.unwrap() else {
+            panic!("expected a rehash");
+        };
+
+        assert!(new_hash.starts_with("$argon2id$"));
+        assert_eq!(
+            hasher.verify("s3cret", &new_hash).await.unwrap(),
+            PasswordVerification::Valid { rehashed: None }
+        );
+        // A wrong password never produces a rehash
+        assert_eq!(hasher.verify("wrong", &old_hash).await.unwrap(), PasswordVerification::Invalid);
+    }
+
+    #[tokio::test]
+    async fn test_rehashes_outdated_argon2_parameters() {
+        let old_hash = argon2(1).hash("s3cret").await.unwrap();
+
+        let verification = argon2(2).verify("s3cret", &old_hash).await.unwrap();
+
+        assert!(matches!(verification, PasswordVerification::Valid { rehashed: Some(_) }));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_hash_format_is_an_error() {
+        let error = BcryptHasher::new(4).verify("s3cret", "plaintext").await.unwrap_err();
+
+        assert_eq!(error.port, "PasswordHasher");
+        assert_eq!(error.code, "UNSUPPORTED_HASH");
+    }
+}
@@ -0,0 +1,131 @@
+//! BRIK v5 Secret - Wrapper for credentials that must not leak
+//!
+//! `Debug` and `Display` print `[REDACTED]` and the value is zeroized on
+//! drop. The inner value is only reachable through `expose`, which makes
+//! every use greppable.
+//!
+//! `Serialize` writes `[REDACTED]` too, so serializing a struct that holds
+//! secrets (e.g. a settings dump) never reveals them.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::path::Path;
+use zeroize::Zeroize;
+
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Suffix of variables naming a file that holds the secret, as used by
+/// Docker and Kubernetes secret mounts (`JWT_SECRET_FILE=/run/secrets/jwt`)
+pub const FILE_SUFFIX: &str = "_FILE";
+
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl Secret<String> {
+    /// Reads a secret file, dropping the trailing newline most tools add
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let mut value = std::fs::read_to_string(path)?;
+        let trimmed = value.trim_end_matches(['\r', '\n']).len();
+        value.truncate(trimmed);
+        Ok(Self(value))
+    }
+
+    /// `{name}_FILE` takes precedence over `{name}`; `None` when neither is set
+    pub fn from_env(name: &str) -> std::io::Result<Option<Self>> {
+        if let Ok(path) = std::env::var(format!("{}{}", name, FILE_SUFFIX)) {
+            return Self::from_file(path).map(Some);
+        }
+        Ok(std::env::var(name).ok().map(Self))
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize + Clone> Clone for Secret<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T: Zeroize> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T: Zeroize> Serialize for Secret<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+impl<'de, T: Zeroize + Deserialize<'de>> Deserialize<'de> for Secret<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formatting_is_redacted() {
+        let secret = Secret::new("hunter2".to_string());
+
+        assert_eq!(format!("{:?}", secret), "[REDACTED]");
+        assert_eq!(secret.to_string(), "[REDACTED]");
+        assert_eq!(secret.expose(), "hunter2");
+    }
+
+    #[test]
+    fn test_from_file_trims_trailing_newline() {
+        let path = std::env::temp_dir().join(format!("brik-secret-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "s3cret value\r\n").unwrap();
+
+        let secret = Secret::from_file(&path).unwrap();
+
+        assert_eq!(secret.expose(), "s3cret value");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_deserializes_transparently() {
+        let secrets: Vec<Secret<String>> = serde_json::from_str(r#"["a", "b"]"#).unwrap();
+
+        assert_eq!(secrets.iter().map(|s| s.expose().as_str()).collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(format!("{:?}", secrets), "[[REDACTED], [REDACTED]]");
+    }
+
+    #[test]
+    fn test_serializes_redacted() {
+        let secrets = vec![Secret::new("hunter2".to_string())];
+
+        assert_eq!(serde_json::to_string(&secrets).unwrap(), r#"["[REDACTED]"]"#);
+    }
+}
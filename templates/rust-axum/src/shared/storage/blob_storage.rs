@@ -0,0 +1,229 @@
+//! BRIK v5 Blob Storage Port - Binary objects addressed by key, served by URL
+//!
+//! Keys are relative paths made of `[A-Za-z0-9._-]` segments separated by
+//! `/`; adapters reject anything else so a key can never escape its root.
+
+use crate::shared::config::settings::StorageSettings;
+use crate::shared::errors::error_code::ErrorCode;
+use crate::shared::errors::port_error::PortError;
+use crate::shared::types::result::BrikResult;
+use axum::Router;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use tower_http::services::ServeDir;
+use uuid::Uuid;
+
+const PORT_NAME: &str = "BlobStorage";
+
+#[async_trait::async_trait]
+pub trait BlobStorage: Send + Sync {
+    /// Stores the object and returns the URL it is publicly served from
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> BrikResult<String, PortError>;
+
+    /// Deleting a missing object is not an error
+    async fn delete(&self, key: &str) -> BrikResult<(), PortError>;
+
+    /// Key of a URL returned by `put`, or `None` for URLs this storage does not own
+    fn key_for_url(&self, url: &str) -> Option<String>;
+}
+
+fn validate_key(key: &str) -> BrikResult<(), PortError> {
+    let valid = !key.is_empty()
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ErrorCode::InvalidKey.port_error(PORT_NAME, &format!("Invalid blob key '{}'", key)))
+    }
+}
+
+fn io_error(error: std::io::Error) -> PortError {
+    ErrorCode::StorageUnavailable.port_error(PORT_NAME, &error.to_string())
+}
+
+/// Serves `storage.local_root` at the path of `storage.public_base_url`
+/// (`http://localhost:3000/uploads` is served at `/uploads`). A base URL
+/// without a path belongs to another host, so nothing is mounted for it.
+pub fn local_blob_routes(settings: &StorageSettings) -> Router {
+    let path = settings
+        .public_base_url
+        .split_once("://")
+        .and_then(|(_, rest)| rest.find('/').map(|slash| &rest[slash..]))
+        .unwrap_or("")
+        .trim_end_matches('/');
+
+    if path.is_empty() {
+        return Router::new();
+    }
+    Router::new().nest_service(path, ServeDir::new(&settings.local_root))
+}
+
+/// Local filesystem adapter. Files under `root` must be served at
+/// `public_base_url`: `local_blob_routes` does that from this process, or a
+/// reverse proxy or CDN can.
+pub struct LocalFileBlobStorage {
+    root: PathBuf,
+    public_base_url: String,
+}
+
+impl LocalFileBlobStorage {
+    pub fn new(root: impl Into<PathBuf>, public_base_url: &str) -> Self {
+        Self {
+            root: root.into(),
+            public_base_url: public_base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn path_for(&self, key: &str) -> BrikResult<PathBuf, PortError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStorage for LocalFileBlobStorage {
+    async fn put(&self, key: &str, _content_type: &str, bytes: Vec<u8>) -> BrikResult<String, PortError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(io_error)?;
+        }
+
+        // Write then rename so readers never see a partial file
+        let temporary = path.with_extension(format!("{}.tmp", Uuid::new_v4().simple()));
+        tokio::fs::write(&temporary, bytes).await.map_err(io_error)?;
+        if let Err(error) = tokio::fs::rename(&temporary, &path).await {
+            let _ = tokio::fs::remove_file(&temporary).await;
+            return Err(io_error(error));
+        }
+
+        Ok(format!("{}/{}", self.public_base_url, key))
+    }
+
+    async fn delete(&self, key: &str) -> BrikResult<(), PortError> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(io_error(error)),
+        }
+    }
+
+    fn key_for_url(&self, url: &str) -> Option<String> {
+        let key = url.strip_prefix(&self.public_base_url)?.strip_prefix('/')?;
+        validate_key(key).ok().map(|_| key.to_string())
+    }
+}
+
+/// Keeps objects in memory; for tests and local runs without a disk
+pub struct InMemoryBlobStorage {
+    objects: RwLock<HashMap<String, (String, Vec<u8>)>>,
+    public_base_url: String,
+}
+
+impl InMemoryBlobStorage {
+    pub fn new(public_base_url: &str) -> Self {
+        Self {
+            objects: RwLock::new(HashMap::new()),
+            public_base_url: public_base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Content type and bytes of a stored object
+    pub async fn get(&self, key: &str) -> Option<(String, Vec<u8>)> {
+        self.objects.read().await.get(key).cloned()
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStorage for InMemoryBlobStorage {
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> BrikResult<String, PortError> {
+        validate_key(key)?;
+        self.objects
+            .write()
+            .await
+            .insert(key.to_string(), (content_type.to_string(), bytes));
+        Ok(format!("{}/{}", self.public_base_url, key))
+    }
+
+    async fn delete(&self, key: &str) -> BrikResult<(), PortError> {
+        validate_key(key)?;
+        self.objects.write().await.remove(key);
+        Ok(())
+    }
+
+    fn key_for_url(&self, url: &str) -> Option<String> {
+        let key = url.strip_prefix(&self.public_base_url)?.strip_prefix('/')?;
+        validate_key(key).ok().map(|_| key.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn storage() -> (TempDir, LocalFileBlobStorage) {
+        let root = TempDir::new().unwrap();
+        let storage = LocalFileBlobStorage::new(root.path(), "http://localhost:3000/uploads/");
+        (root, storage)
+    }
+
+    #[tokio::test]
+    async fn test_put_and_delete() {
+        let (root, storage) = storage();
+
+        let url = storage.put("avatars/abc/1.png", "image/png", vec![1, 2, 3]).await.unwrap();
+
+        assert_eq!(url, "http://localhost:3000/uploads/avatars/abc/1.png");
+        assert_eq!(std::fs::read(root.path().join("avatars/abc/1.png")).unwrap(), vec![1, 2, 3]);
+
+        storage.delete("avatars/abc/1.png").await.unwrap();
+        assert!(!root.path().join("avatars/abc/1.png").exists());
+        storage.delete("avatars/abc/1.png").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejects_keys_escaping_root() {
+        let (_, storage) = storage();
+
+        for key in ["../etc/passwd", "/absolute", "avatars//x", "avatars/a b.png", ""] {
+            let error = storage.put(key, "image/png", vec![]).await.unwrap_err();
+            assert_eq!(error.code, "INVALID_KEY", "key {:?}", key);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_storage() {
+        let storage = InMemoryBlobStorage::new("http://localhost:3000/uploads");
+
+        let url = storage.put("avatars/abc/1.png", "image/png", vec![1, 2, 3]).await.unwrap();
+
+        assert_eq!(storage.key_for_url(&url).as_deref(), Some("avatars/abc/1.png"));
+        assert_eq!(storage.get("avatars/abc/1.png").await, Some(("image/png".to_string(), vec![1, 2, 3])));
+        assert_eq!(storage.put("../x", "image/png", vec![]).await.unwrap_err().code, "INVALID_KEY");
+
+        storage.delete("avatars/abc/1.png").await.unwrap();
+        assert_eq!(storage.get("avatars/abc/1.png").await, None);
+    }
+
+    #[test]
+    fn test_key_for_url() {
+        let (_, storage) = storage();
+
+        assert_eq!(
+            storage.key_for_url("http://localhost:3000/uploads/avatars/abc/1.png").as_deref(),
+            Some("avatars/abc/1.png")
+        );
+        assert_eq!(storage.key_for_url("https://cdn.example.com/avatars/abc/1.png"), None);
+        assert_eq!(storage.key_for_url("http://localhost:3000/uploads/../secret"), None);
+    }
+}
@@ -0,0 +1,194 @@
+//! BRIK v5 Tenants - Tenant identity and per-tenant configuration
+//!
+//! The tenant comes from a JWT claim (`tenancy.claim`) and travels in
+//! `AuthContext`; repository ports take it on every read, so data from one
+//! tenant is invisible to another rather than filtered by convention.
+//! Tokens without the claim fall back to `tenancy.default_tenant` when one
+//! is configured and are rejected otherwise.
+
+use crate::shared::config::settings::TenancySettings;
+use crate::shared::pagination::list_query::MAX_PAGE_LIMIT;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+pub const DEFAULT_TENANT_CLAIM: &str = "tenant_id";
+/// Tenant of tokens without the claim outside production
+pub const DEFAULT_TENANT: &str = "default";
+
+/// 1-64 characters of `[a-zA-Z0-9_-]`, safe in cache keys and storage paths
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TenantId(String);
+
+impl TenantId {
+    pub fn parse(raw: &str) -> Option<Self> {
+        let valid = (1..=64).contains(&raw.len())
+            && raw.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        valid.then(|| Self(raw.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for TenantId {
+    type Error = String;
+
+    fn try_from(raw: String) -> Result<Self, Self::Error> {
+        Self::parse(&raw).ok_or_else(|| format!("'{}' is not a valid tenant id", raw))
+    }
+}
+
+impl From<TenantId> for String {
+    fn from(tenant: TenantId) -> Self {
+        tenant.0
+    }
+}
+
+/// Settings that can differ per tenant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TenantConfig {
+    /// Every request from a suspended tenant is rejected by the auth gate
+    pub suspended: bool,
+    /// Caps `limit` on list endpoints
+    pub max_page_size: u32,
+}
+
+impl Default for TenantConfig {
+    fn default() -> Self {
+        Self {
+            suspended: false,
+            max_page_size: MAX_PAGE_LIMIT,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TenantRegistry {
+    claim: String,
+    default_tenant: Option<TenantId>,
+    defaults: TenantConfig,
+    overrides: HashMap<TenantId, TenantConfig>,
+}
+
+impl Default for TenantRegistry {
+    fn default() -> Self {
+        Self {
+            claim: DEFAULT_TENANT_CLAIM.to_string(),
+            default_tenant: TenantId::parse(DEFAULT_TENANT),
+            defaults: TenantConfig::default(),
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl TenantRegistry {
+    /// Expects validated settings; malformed tenant ids are skipped
+    pub fn from_settings(settings: &TenancySettings) -> Self {
+        let defaults = TenantConfig::default();
+        let overrides = settings
+            .tenants
+            .iter()
+            .filter_map(|tenant| {
+                let config = TenantConfig {
+                    suspended: tenant.suspended,
+                    max_page_size: tenant.max_page_size.unwrap_or(defaults.max_page_size),
+                };
+                TenantId::parse(&tenant.id).map(|id| (id, config))
+            })
+            .collect();
+
+        Self {
+            claim: settings.claim.clone(),
+            default_tenant: settings.default_tenant.as_deref().and_then(TenantId::parse),
+            defaults,
+            overrides,
+        }
+    }
+
+    /// JWT claim holding the tenant id
+    pub fn claim(&self) -> &str {
+        &self.claim
+    }
+
+    /// Tenant for a token's claim value; `None` when it is malformed, or
+    /// absent without a default tenant
+    pub fn resolve(&self, claimed: Option<&str>) -> Option<TenantId> {
+        match claimed {
+            Some(raw) => TenantId::parse(raw),
+            None => self.default_tenant.clone(),
+        }
+    }
+
+    pub fn config(&self, tenant: &TenantId) -> &TenantConfig {
+        self.overrides.get(tenant).unwrap_or(&self.defaults)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::config::settings::TenantOverride;
+
+    fn tenant(id: &str) -> TenantId {
+        TenantId::parse(id).unwrap()
+    }
+
+    #[test]
+    fn test_tenant_id_format() {
+        assert!(TenantId::parse("acme-corp_1").is_some());
+        assert!(TenantId::parse("").is_none());
+        assert!(TenantId::parse("acme/../globex").is_none());
+        assert!(TenantId::parse(&"a".repeat(65)).is_none());
+        assert!(serde_json::from_str::<TenantId>("\"acme:1\"").is_err());
+    }
+
+    #[test]
+    fn test_missing_claim_falls_back_to_default_tenant() {
+        let registry = TenantRegistry::default();
+        assert_eq!(registry.resolve(None), Some(tenant(DEFAULT_TENANT)));
+        assert_eq!(registry.resolve(Some("acme")), Some(tenant("acme")));
+        assert_eq!(registry.resolve(Some("not valid")), None);
+
+        let strict = TenantRegistry::from_settings(&TenancySettings {
+            claim: "org".to_string(),
+            default_tenant: None,
+            tenants: vec![],
+        });
+        assert_eq!(strict.claim(), "org");
+        assert_eq!(strict.resolve(None), None);
+    }
+
+    #[test]
+    fn test_overrides_apply_per_tenant() {
+        let registry = TenantRegistry::from_settings(&TenancySettings {
+            claim: DEFAULT_TENANT_CLAIM.to_string(),
+            default_tenant: None,
+            tenants: vec![
+                TenantOverride {
+                    id: "acme".to_string(),
+                    suspended: false,
+                    max_page_size: Some(10),
+                },
+                TenantOverride {
+                    id: "globex".to_string(),
+                    suspended: true,
+                    max_page_size: None,
+                },
+            ],
+        });
+
+        assert_eq!(registry.config(&tenant("acme")).max_page_size, 10);
+        assert!(registry.config(&tenant("globex")).suspended);
+        assert_eq!(registry.config(&tenant("globex")).max_page_size, MAX_PAGE_LIMIT);
+        assert_eq!(registry.config(&tenant("initech")), &TenantConfig::default());
+    }
+}
@@ -0,0 +1,125 @@
+//! BRIK v5 Clock - The one source of current time
+//!
+//! Entities, gates and token checks ask a `Clock` instead of calling
+//! `Utc::now()` or `Instant::now()`, so tests can pin time with
+//! `ManualClock` and move it forward to exercise expiry and age rules.
+//! Production wires `SystemClock` through `AppState`.
+
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub trait Clock: Send + Sync {
+    /// Wall-clock time, for timestamps and token expiry
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Monotonic time, for measuring durations
+    fn instant(&self) -> Instant;
+}
+
+pub type SharedClock = Arc<dyn Clock>;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl SystemClock {
+    pub fn shared() -> SharedClock {
+        Arc::new(Self)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Stands still until told to move; both readings advance together
+#[derive(Debug)]
+pub struct ManualClock {
+    state: Mutex<ManualState>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ManualState {
+    now: DateTime<Utc>,
+    instant: Instant,
+}
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(ManualState { now, instant: Instant::now() }),
+        })
+    }
+
+    /// Starts at the current system time
+    pub fn starting_now() -> Arc<Self> {
+        Self::new(Utc::now())
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let mut state = self.state();
+        state.now += chrono::Duration::from_std(by).expect("advance by less than i64::MAX milliseconds");
+        state.instant += by;
+    }
+
+    /// Jumps the wall clock, backwards too; the monotonic reading only moves forward
+    pub fn set(&self, now: DateTime<Utc>) {
+        let mut state = self.state();
+        if let Ok(forward) = (now - state.now).to_std() {
+            state.instant += forward;
+        }
+        state.now = now;
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, ManualState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.state().now
+    }
+
+    fn instant(&self) -> Instant {
+        self.state().instant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_manual_clock_moves_only_when_told() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let clock = ManualClock::new(start);
+        let started = clock.instant();
+
+        assert_eq!(clock.now(), start);
+        clock.advance(Duration::from_secs(90));
+
+        assert_eq!(clock.now(), start + chrono::Duration::seconds(90));
+        assert_eq!(clock.instant() - started, Duration::from_secs(90));
+    }
+
+    #[test]
+    fn test_setting_the_past_keeps_instants_monotonic() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let clock = ManualClock::new(start);
+        let started = clock.instant();
+
+        clock.set(start - chrono::Duration::hours(1));
+        assert_eq!(clock.instant(), started);
+
+        clock.set(start + chrono::Duration::minutes(1));
+        assert_eq!(clock.instant() - started, Duration::from_secs(61 * 60));
+    }
+}
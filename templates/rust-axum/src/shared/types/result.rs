@@ -1,6 +1,8 @@
 //! BRIK v5 Result Type - Functional error handling for Rust
 
-use std::fmt::Debug;
+use crate::brik_log;
+use std::fmt::Display;
+use std::future::Future;
 
 /// A type that represents either success (`Ok`) or failure (`Err`).
 /// Similar to std::result::Result but with additional helper methods for BRIK patterns.
@@ -23,11 +25,38 @@ pub trait ResultExt<T, E> {
     where
         F: FnOnce(E) -> T;
 
-    /// Returns Ok if both self and other are Ok, otherwise returns the first Err
-    fn and_then_combine<U, F>(self, other: BrikResult<U, E>, f: F) -> BrikResult<(T, U), E>
+    /// Calls `f` with both values if self and other are Ok, otherwise returns the first Err
+    fn and_then_combine<U, V, F>(self, other: BrikResult<U, E>, f: F) -> BrikResult<V, E>
     where
-        F: FnOnce() -> BrikResult<(T, U), E>,
-        E: Clone;
+        F: FnOnce(T, U) -> BrikResult<V, E>;
+
+    /// Runs the async `f` on the Ok value
+    fn and_then_async<U, F, Fut>(self, f: F) -> impl Future<Output = BrikResult<U, E>>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = BrikResult<U, E>>;
+
+    /// Maps the Ok value with the async `f`
+    fn map_async<U, F, Fut>(self, f: F) -> impl Future<Output = BrikResult<U, E>>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>;
+
+    /// Runs the async `f` on the Err value, e.g. to fall back to another port
+    fn or_else_async<G, F, Fut>(self, f: F) -> impl Future<Output = BrikResult<T, G>>
+    where
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = BrikResult<T, G>>;
+
+    /// Logs `message` at info level with the current `LogContext` when Ok
+    fn inspect_ok(self, message: &str) -> Self;
+
+    /// Logs `message` and the error at warn level with the current `LogContext`
+    /// when Err. Not named `inspect_err`: the inherent `Result::inspect_err`
+    /// would shadow it on results
+    fn log_err(self, message: &str) -> Self
+    where
+        E: Display;
 }
 
 impl<T, E> ResultExt<T, E> for BrikResult<T, E> {
@@ -55,19 +84,99 @@ impl<T, E> ResultExt<T, E> for BrikResult<T, E> {
         }
     }
 
-    fn and_then_combine<U, F>(self, other: BrikResult<U, E>, _f: F) -> BrikResult<(T, U), E>
+    fn and_then_combine<U, V, F>(self, other: BrikResult<U, E>, f: F) -> BrikResult<V, E>
     where
-        F: FnOnce() -> BrikResult<(T, U), E>,
-        E: Clone,
+        F: FnOnce(T, U) -> BrikResult<V, E>,
     {
-        match (self, other) {
-            (Ok(a), Ok(b)) => Ok((a, b)),
-            (Err(e), _) => Err(e),
-            (_, Err(e)) => Err(e),
+        f(self?, other?)
+    }
+
+    async fn and_then_async<U, F, Fut>(self, f: F) -> BrikResult<U, E>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = BrikResult<U, E>>,
+    {
+        f(self?).await
+    }
+
+    async fn map_async<U, F, Fut>(self, f: F) -> BrikResult<U, E>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>,
+    {
+        Ok(f(self?).await)
+    }
+
+    async fn or_else_async<G, F, Fut>(self, f: F) -> BrikResult<T, G>
+    where
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = BrikResult<T, G>>,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => f(error).await,
+        }
+    }
+
+    fn inspect_ok(self, message: &str) -> Self {
+        if self.is_ok() {
+            brik_log!(INFO, message);
+        }
+        self
+    }
+
+    fn log_err(self, message: &str) -> Self
+    where
+        E: Display,
+    {
+        if let Err(error) = &self {
+            brik_log!(WARN, message, error = %error);
         }
+        self
+    }
+}
+
+/// The `ResultExt` combinators for futures of results, so a port call can be
+/// chained before it is awaited
+pub trait FutureResultExt<T, E>: Future<Output = BrikResult<T, E>> + Sized {
+    fn and_then_async<U, F, Fut>(self, f: F) -> impl Future<Output = BrikResult<U, E>>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = BrikResult<U, E>>,
+    {
+        async move { self.await.and_then_async(f).await }
+    }
+
+    fn map_async<U, F, Fut>(self, f: F) -> impl Future<Output = BrikResult<U, E>>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = U>,
+    {
+        async move { self.await.map_async(f).await }
+    }
+
+    fn or_else_async<G, F, Fut>(self, f: F) -> impl Future<Output = BrikResult<T, G>>
+    where
+        F: FnOnce(E) -> Fut,
+        Fut: Future<Output = BrikResult<T, G>>,
+    {
+        async move { self.await.or_else_async(f).await }
+    }
+
+    fn inspect_ok(self, message: &str) -> impl Future<Output = BrikResult<T, E>> {
+        async move { self.await.inspect_ok(message) }
+    }
+
+    fn log_err(self, message: &str) -> impl Future<Output = BrikResult<T, E>>
+    where
+        E: Display,
+    {
+        async move { self.await.log_err(message) }
     }
 }
 
+impl<T, E, R> FutureResultExt<T, E> for R where R: Future<Output = BrikResult<T, E>> {}
+
 /// Convenience function to create an Ok result
 pub fn ok<T, E>(value: T) -> BrikResult<T, E> {
     Ok(value)
@@ -78,36 +187,21 @@ pub fn err<T, E>(error: E) -> BrikResult<T, E> {
     Err(error)
 }
 
-/// Macro to convert multiple Results into a single Result containing a tuple
+/// Macro to convert multiple Results (up to 12) into a single Result containing
+/// a tuple, stopping at the first Err; see `validate_all!` to collect every error
 /// Usage: combine_results!(result1, result2, result3)
 #[macro_export]
 macro_rules! combine_results {
-    ($first:expr) => {
-        $first.map(|v| (v,))
-    };
-    ($first:expr, $($rest:expr),+) => {
-        {
-            let first = $first?;
-            let rest = combine_results!($($rest),+)?;
-            Ok({
-                let mut tuple = (first,);
-                // This is a bit hacky but works for combining tuples
-                #[allow(unused_assignments)]
-                {
-                    tuple = (tuple.0, rest.0);
-                    $(
-                        // Add more tuple elements as needed
-                    )+
-                }
-                tuple
-            })
-        }
+    ($($result:expr),+ $(,)?) => {
+        $crate::shared::types::validated::Combine::combine(($($result,)+))
     };
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::observability::logger::{Captured, LogContext};
+    use tracing::Level;
 
     #[test]
     fn test_result_ext_map_ok() {
@@ -123,6 +217,84 @@ mod tests {
         assert_eq!(mapped, Err("Mapped: error".to_string()));
     }
 
+    #[test]
+    fn test_and_then_combine_applies_closure() {
+        let a: BrikResult<i32, &str> = Ok(2);
+        let b: BrikResult<i32, &str> = Ok(3);
+        assert_eq!(a.and_then_combine(b, |x, y| Ok(x * y)), Ok(6));
+
+        let failed: BrikResult<i32, &str> = Err("first");
+        assert_eq!(failed.and_then_combine(Err::<i32, _>("second"), |x, y| Ok(x + y)), Err("first"));
+    }
+
+    #[test]
+    fn test_combine_results_builds_flat_tuple() {
+        let combined: BrikResult<(i32, &str, bool), String> = combine_results!(Ok(1), Ok("two"), Ok(true));
+        assert_eq!(combined, Ok((1, "two", true)));
+
+        let failed: BrikResult<(i32, i32, i32), &str> = combine_results!(Ok(1), Err("second"), Err("third"));
+        assert_eq!(failed, Err("second"));
+    }
+
+    async fn double(value: i32) -> BrikResult<i32, String> {
+        Ok(value * 2)
+    }
+
+    async fn fail(value: i32) -> BrikResult<i32, String> {
+        Err(format!("failed on {}", value))
+    }
+
+    #[tokio::test]
+    async fn test_async_combinators_on_results() {
+        let ok: BrikResult<i32, String> = Ok(2);
+        assert_eq!(ok.clone().and_then_async(double).await, Ok(4));
+        assert_eq!(ok.clone().map_async(|v| async move { v + 1 }).await, Ok(3));
+        assert_eq!(ok.and_then_async(fail).await, Err("failed on 2".to_string()));
+
+        let err: BrikResult<i32, String> = Err("primary down".to_string());
+        assert_eq!(err.clone().and_then_async(double).await, Err("primary down".to_string()));
+        assert_eq!(err.or_else_async(|_| async { Ok::<_, ()>(7) }).await, Ok(7));
+    }
+
+    #[tokio::test]
+    async fn test_async_combinators_on_futures() {
+        let result = double(1)
+            .and_then_async(double)
+            .map_async(|v| async move { v + 1 })
+            .inspect_ok("doubled twice")
+            .await;
+        assert_eq!(result, Ok(5));
+
+        let recovered = fail(1)
+            .log_err("primary failed")
+            .or_else_async(|error| async move { Ok::<_, String>(error.len() as i32) })
+            .await;
+        assert_eq!(recovered, Ok(11));
+    }
+
+    #[tokio::test]
+    async fn test_chains_are_send_and_log_within_context() {
+        let captured = Captured::default();
+        let _guard = tracing::subscriber::set_default(captured.subscriber(Level::INFO));
+
+        // The current-thread test runtime polls the task on this thread, under the subscriber
+        let handle = tokio::spawn(LogContext::new().with_correlation_id("req_1".to_string()).scope(async {
+            double(1).inspect_ok("doubled").await.ok();
+            fail(3).log_err("lookup failed").and_then_async(double).await
+        }));
+
+        assert_eq!(handle.await.unwrap(), Err("failed on 3".to_string()));
+        let lines = captured.lines();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["level"], "INFO");
+        assert_eq!(lines[0]["fields"]["message"], "doubled");
+        assert_eq!(lines[0]["fields"]["correlation_id"], "req_1");
+        assert_eq!(lines[1]["level"], "WARN");
+        assert_eq!(lines[1]["fields"]["message"], "lookup failed");
+        assert_eq!(lines[1]["fields"]["error"], "failed on 3");
+        assert_eq!(lines[1]["fields"]["correlation_id"], "req_1");
+    }
+
     #[test]
     fn test_ok_err_helpers() {
         let success: BrikResult<i32, String> = ok(42);
@@ -0,0 +1,249 @@
+//! BRIK v5 Validated Type - Results that accumulate every error
+//!
+//! `BrikResult` stops at the first failure, which is right for sequential
+//! work. Independent checks such as the fields of a request body should run
+//! all at once and report every problem; that is what `Validated` is for.
+//!
+//! ```ignore
+//! let (name, age) = validate_all!(validate_name(&dto.name), validate_age(dto.age)).into_result()?;
+//! ```
+
+use crate::shared::types::result::BrikResult;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Validated<T, E> {
+    Valid(T),
+    /// Never empty when built through this module
+    Invalid(Vec<E>),
+}
+
+impl<T, E> Validated<T, E> {
+    pub fn valid(value: T) -> Self {
+        Validated::Valid(value)
+    }
+
+    pub fn invalid(error: E) -> Self {
+        Validated::Invalid(vec![error])
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Validated::Valid(_))
+    }
+
+    pub fn errors(&self) -> &[E] {
+        match self {
+            Validated::Valid(_) => &[],
+            Validated::Invalid(errors) => errors,
+        }
+    }
+
+    pub fn map<U, F>(self, f: F) -> Validated<U, E>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            Validated::Valid(value) => Validated::Valid(f(value)),
+            Validated::Invalid(errors) => Validated::Invalid(errors),
+        }
+    }
+
+    pub fn map_err<F, G>(self, f: G) -> Validated<T, F>
+    where
+        G: FnMut(E) -> F,
+    {
+        match self {
+            Validated::Valid(value) => Validated::Valid(value),
+            Validated::Invalid(errors) => Validated::Invalid(errors.into_iter().map(f).collect()),
+        }
+    }
+
+    /// Sequential step that depends on the value; nothing accumulates past it
+    pub fn and_then<U, F>(self, f: F) -> Validated<U, E>
+    where
+        F: FnOnce(T) -> Validated<U, E>,
+    {
+        match self {
+            Validated::Valid(value) => f(value),
+            Validated::Invalid(errors) => Validated::Invalid(errors),
+        }
+    }
+
+    /// Pairs two independent values, keeping the errors of both
+    pub fn zip<U>(self, other: Validated<U, E>) -> Validated<(T, U), E> {
+        (self, other).combine()
+    }
+
+    pub fn into_result(self) -> BrikResult<T, Vec<E>> {
+        match self {
+            Validated::Valid(value) => Ok(value),
+            Validated::Invalid(errors) => Err(errors),
+        }
+    }
+}
+
+impl<T, E> From<BrikResult<T, E>> for Validated<T, E> {
+    fn from(result: BrikResult<T, E>) -> Self {
+        match result {
+            Ok(value) => Validated::Valid(value),
+            Err(error) => Validated::invalid(error),
+        }
+    }
+}
+
+impl<T, E> FromIterator<Validated<T, E>> for Validated<Vec<T>, E> {
+    /// Collects every value, or every error of every invalid item
+    fn from_iter<I: IntoIterator<Item = Validated<T, E>>>(iter: I) -> Self {
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+
+        for item in iter {
+            match item {
+                Validated::Valid(value) => values.push(value),
+                Validated::Invalid(item_errors) => errors.extend(item_errors),
+            }
+        }
+
+        if errors.is_empty() {
+            Validated::Valid(values)
+        } else {
+            Validated::Invalid(errors)
+        }
+    }
+}
+
+/// Turns a tuple of results into a result of a tuple. Tuples of
+/// `BrikResult` keep the first error; tuples of `Validated` keep all of them.
+pub trait Combine {
+    type Output;
+
+    fn combine(self) -> Self::Output;
+}
+
+macro_rules! impl_combine {
+    ($(($($value:ident: $type:ident),+)),+ $(,)?) => {
+        $(
+            impl<$($type,)+ E> Combine for ($(BrikResult<$type, E>,)+) {
+                type Output = BrikResult<($($type,)+), E>;
+
+                fn combine(self) -> Self::Output {
+                    let ($($value,)+) = self;
+                    Ok(($($value?,)+))
+                }
+            }
+
+            impl<$($type,)+ E> Combine for ($(Validated<$type, E>,)+) {
+                type Output = Validated<($($type,)+), E>;
+
+                fn combine(self) -> Self::Output {
+                    let ($($value,)+) = self;
+                    let mut errors = Vec::new();
+                    $(
+                        let $value = match $value {
+                            Validated::Valid(value) => Some(value),
+                            Validated::Invalid(value_errors) => {
+                                errors.extend(value_errors);
+                                None
+                            }
+                        };
+                    )+
+
+                    match ($($value,)+) {
+                        ($(Some($value),)+) => Validated::Valid(($($value,)+)),
+                        _ => Validated::Invalid(errors),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_combine! {
+    (a: A),
+    (a: A, b: B),
+    (a: A, b: B, c: C),
+    (a: A, b: B, c: C, d: D),
+    (a: A, b: B, c: C, d: D, f: F),
+    (a: A, b: B, c: C, d: D, f: F, g: G),
+    (a: A, b: B, c: C, d: D, f: F, g: G, h: H),
+    (a: A, b: B, c: C, d: D, f: F, g: G, h: H, i: I),
+    (a: A, b: B, c: C, d: D, f: F, g: G, h: H, i: I, j: J),
+    (a: A, b: B, c: C, d: D, f: F, g: G, h: H, i: I, j: J, k: K),
+    (a: A, b: B, c: C, d: D, f: F, g: G, h: H, i: I, j: J, k: K, l: L),
+    (a: A, b: B, c: C, d: D, f: F, g: G, h: H, i: I, j: J, k: K, l: L, m: M),
+}
+
+/// Runs every check and returns `Validated<(T1, ..., Tn), E>` with all
+/// errors; each argument is a `BrikResult` or a `Validated` (up to 12)
+/// Usage: validate_all!(check_name(name), check_age(age), check_email(email))
+#[macro_export]
+macro_rules! validate_all {
+    ($($check:expr),+ $(,)?) => {
+        $crate::shared::types::validated::Combine::combine((
+            $($crate::shared::types::validated::Validated::from($check),)+
+        ))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positive(value: i32, field: &'static str) -> BrikResult<i32, String> {
+        if value > 0 {
+            Ok(value)
+        } else {
+            Err(format!("{} must be positive", field))
+        }
+    }
+
+    #[test]
+    fn test_validate_all_collects_every_error() {
+        let result = validate_all!(positive(1, "a"), positive(-1, "b"), positive(2, "c"), positive(0, "d"));
+
+        assert_eq!(
+            result,
+            Validated::Invalid(vec!["b must be positive".to_string(), "d must be positive".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_validate_all_returns_every_value() {
+        let result = validate_all!(positive(1, "a"), Validated::valid("name"), positive(3, "c"));
+
+        assert_eq!(result, Validated::Valid((1, "name", 3)));
+    }
+
+    #[test]
+    fn test_combines_twelve_elements() {
+        let result = validate_all!(
+            positive(1, "1"),
+            positive(2, "2"),
+            positive(3, "3"),
+            positive(4, "4"),
+            positive(5, "5"),
+            positive(6, "6"),
+            positive(7, "7"),
+            positive(8, "8"),
+            positive(9, "9"),
+            positive(10, "10"),
+            positive(11, "11"),
+            positive(-12, "12"),
+        );
+
+        assert_eq!(result.errors(), ["12 must be positive".to_string()]);
+    }
+
+    #[test]
+    fn test_zip_and_map() {
+        let area = Validated::<i32, String>::valid(3).zip(Validated::valid(4)).map(|(w, h)| w * h);
+
+        assert_eq!(area.into_result(), Ok(12));
+    }
+
+    #[test]
+    fn test_collect_into_vec() {
+        let all: Validated<Vec<i32>, String> = [1, -2, -3].iter().map(|n| positive(*n, "n").into()).collect();
+
+        assert_eq!(all.errors().len(), 2);
+    }
+}
@@ -0,0 +1,189 @@
+//! BRIK v5 Testkit App - The full router over in-memory adapters
+//!
+//! `TestAppBuilder::new().build()` serves the same router as production
+//! (hardening, locale negotiation, bulkheads) with the testing profile and
+//! `TEST_JWT_SECRET`, so tokens from `TokenBuilder` are accepted. The
+//! adapters stay reachable on `TestApp` for seeding and inspection, time
+//! only moves when `TestApp::clock` is advanced, and created users get
+//! sequential ids.
+
+#![cfg(any(test, feature = "testkit"))]
+
+use super::token::TEST_JWT_SECRET;
+use crate::api::app_state::AppState;
+use crate::api::health::health_checker::HealthChecker;
+use crate::api::router::build_router;
+use crate::api::users::adapters::in_memory_user_repository::InMemoryUserRepository;
+use crate::api::users::adapters::user_event_stream::UserEventStream;
+use crate::api::users::domain::entities::user::User;
+use crate::api::users::domain::ports::user_repository::UserRepository;
+use crate::shared::config::settings::{Profile, Settings};
+use crate::shared::ids::id_generator::SequentialIdGenerator;
+use crate::shared::security::secret::Secret;
+use crate::shared::storage::blob_storage::InMemoryBlobStorage;
+use crate::shared::tenancy::tenant::TenantRegistry;
+use crate::shared::time::clock::ManualClock;
+use axum::Router;
+use axum_test::TestServer;
+use std::sync::Arc;
+
+pub const TEST_PUBLIC_BASE_URL: &str = "http://localhost:3000/uploads";
+
+pub struct TestAppBuilder {
+    settings: Settings,
+    users: Vec<User>,
+    health_checker: HealthChecker,
+}
+
+impl Default for TestAppBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestAppBuilder {
+    pub fn new() -> Self {
+        let mut settings = Settings::defaults(Profile::Testing);
+        settings.auth.jwt_secret = Secret::new(TEST_JWT_SECRET.to_string());
+
+        Self {
+            settings,
+            users: Vec::new(),
+            health_checker: HealthChecker::new(),
+        }
+    }
+
+    /// Adjusts the testing-profile settings, e.g. tenant overrides or bulkheads
+    pub fn configure(mut self, configure: impl FnOnce(&mut Settings)) -> Self {
+        configure(&mut self.settings);
+        self
+    }
+
+    /// Stored before the first request; does not publish events
+    pub fn with_user(mut self, user: User) -> Self {
+        self.users.push(user);
+        self
+    }
+
+    pub fn with_health_checker(mut self, health_checker: HealthChecker) -> Self {
+        self.health_checker = health_checker;
+        self
+    }
+
+    pub async fn build(self) -> TestApp {
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        for user in &self.users {
+            user_repository.create(user).await.expect("seed users have unique emails per tenant");
+        }
+
+        let clock = ManualClock::starting_now();
+        let blob_storage = Arc::new(InMemoryBlobStorage::new(TEST_PUBLIC_BASE_URL));
+        let user_events = Arc::new(UserEventStream::default());
+        let state = AppState::new(
+            user_repository.clone(),
+            blob_storage.clone(),
+            user_events.clone(),
+            &self.settings,
+            Arc::new(TenantRegistry::from_settings(&self.settings.tenancy)),
+            Arc::new(self.health_checker.with_clock(clock.clone())),
+            clock.clone(),
+        )
+        .with_id_generator(Arc::new(SequentialIdGenerator::new(clock.clone())));
+        let router = build_router(state.clone(), &self.settings);
+
+        TestApp {
+            server: TestServer::new(router.clone()).expect("the router serves in-process"),
+            router,
+            state,
+            user_repository,
+            blob_storage,
+            user_events,
+            clock,
+            settings: self.settings,
+        }
+    }
+}
+
+pub struct TestApp {
+    /// `axum-test` client over `router`
+    pub server: TestServer,
+    /// For `tower::ServiceExt::oneshot` when raw requests are needed
+    pub router: Router,
+    pub state: AppState,
+    pub user_repository: Arc<InMemoryUserRepository>,
+    pub blob_storage: Arc<InMemoryBlobStorage>,
+    pub user_events: Arc<UserEventStream>,
+    /// Starts at the system time when built; `advance` it to expire tokens
+    pub clock: Arc<ManualClock>,
+    pub settings: Settings,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::users::domain::entities::user::UserCreationData;
+    use crate::shared::tenancy::tenant::TenantId;
+    use crate::shared::ids::id_generator::UuidV7Generator;
+    use crate::shared::time::clock::SystemClock;
+    use crate::testkit::assertions::ErrorEnvelope;
+    use crate::testkit::token::TokenBuilder;
+    use axum::http::{header, StatusCode};
+
+    #[tokio::test]
+    async fn test_seeded_user_is_served_to_its_tenant_only() {
+        let user = User::create(
+            TenantId::parse("acme").unwrap(),
+            UserCreationData {
+                email: "john@example.com".to_string(),
+                name: "John Doe".to_string(),
+                age: 30,
+                profile: None,
+            },
+            &SystemClock,
+            &UuidV7Generator::default(),
+        )
+        .unwrap();
+        let app = TestAppBuilder::new().with_user(user.clone()).build().await;
+        let reader = TokenBuilder::new().scopes(&["users:read"]);
+
+        let response = app
+            .server
+            .get(&format!("/api/v1/users/{}", user.id))
+            .add_header(header::AUTHORIZATION, reader.clone().tenant("acme").bearer())
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<serde_json::Value>()["user"]["email"], "john@example.com");
+
+        app.server
+            .get(&format!("/api/v1/users/{}", user.id))
+            .add_header(header::AUTHORIZATION, reader.tenant("globex").bearer())
+            .await
+            .assert_error(StatusCode::NOT_FOUND, "USER_NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_tokens_expire_when_the_clock_moves() {
+        let app = TestAppBuilder::new().build().await;
+        let token = TokenBuilder::new().scopes(&["users:read"]).expires_in(chrono::Duration::minutes(5));
+        let list = || app.server.get("/api/v1/users").add_header(header::AUTHORIZATION, token.bearer());
+
+        list().await.assert_status_ok();
+
+        // Past `exp` plus the 60 second leeway; the cached verification expires too
+        app.clock.advance(std::time::Duration::from_secs(5 * 60 + 61));
+        list().await.assert_error(StatusCode::UNAUTHORIZED, "AUTH_TOKEN_INVALID");
+    }
+
+    #[tokio::test]
+    async fn test_configured_settings_reach_the_router() {
+        let app = TestAppBuilder::new()
+            .configure(|settings| settings.http.frame_options = "SAMEORIGIN".to_string())
+            .build()
+            .await;
+
+        let response = app.server.get("/api/v1/health/live").await;
+
+        response.assert_status_ok();
+        assert_eq!(response.header("x-frame-options"), "SAMEORIGIN");
+    }
+}
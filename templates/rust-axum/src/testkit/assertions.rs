@@ -0,0 +1,118 @@
+//! BRIK v5 Testkit Assertions - Checks on the shared error envelope
+//!
+//! Every failure must answer `{"error": {type, code, message, gate?, port?, errors?}}`
+//! with a catalogued code whose type matches the catalog; `gate` is present
+//! exactly for gate errors and `port` exactly for port errors. Each entry of
+//! `errors` carries a catalogued code of its own.
+
+#![cfg(any(test, feature = "testkit"))]
+
+use crate::shared::errors::api_error::{ErrorBody, ErrorType};
+use crate::shared::errors::error_code::ErrorCode;
+use axum::http::StatusCode;
+use axum_test::TestResponse;
+use serde_json::Value;
+
+const ENVELOPE_FIELDS: [&str; 6] = ["type", "code", "message", "gate", "port", "errors"];
+
+/// Panics with the offending body unless it is a well-formed error envelope;
+/// returns the `error` object
+pub fn assert_error_envelope(body: &Value) -> ErrorBody {
+    let fail = |problem: &str| -> ! { panic!("{} in error envelope: {}", problem, body) };
+
+    let Some(root) = body.as_object() else { fail("not an object") };
+    if root.len() != 1 {
+        fail("fields besides `error`");
+    }
+    let Some(error) = root.get("error").and_then(Value::as_object) else { fail("no `error` object") };
+    if let Some(field) = error.keys().find(|field| !ENVELOPE_FIELDS.contains(&field.as_str())) {
+        fail(&format!("unexpected field `{}`", field));
+    }
+
+    let Ok(parsed) = serde_json::from_value::<ErrorBody>(root["error"].clone()) else {
+        fail("malformed `error` object");
+    };
+    if parsed.message.trim().is_empty() {
+        fail("empty message");
+    }
+    match ErrorCode::parse(&parsed.code) {
+        Some(code) if code.error_type() == parsed.error_type => {}
+        Some(_) => fail(&format!("type does not match the catalog for {}", parsed.code)),
+        None => fail(&format!("uncatalogued code {}", parsed.code)),
+    }
+    if parsed.gate.is_some() != (parsed.error_type == ErrorType::GateError) {
+        fail("`gate` must be present exactly for GATE_ERROR");
+    }
+    if parsed.port.is_some() != (parsed.error_type == ErrorType::PortError) {
+        fail("`port` must be present exactly for PORT_ERROR");
+    }
+    if let Some(detail) = parsed.errors.iter().find(|detail| ErrorCode::parse(&detail.code).is_none()) {
+        fail(&format!("uncatalogued code {} in `errors`", detail.code));
+    }
+
+    parsed
+}
+
+pub trait ErrorEnvelope {
+    /// Asserts the status, the envelope shape and the error code
+    fn assert_error(&self, status: StatusCode, code: &str) -> ErrorBody;
+}
+
+impl ErrorEnvelope for TestResponse {
+    fn assert_error(&self, status: StatusCode, code: &str) -> ErrorBody {
+        self.assert_status(status);
+        let content_type = self.maybe_header("content-type");
+        assert!(
+            content_type.as_ref().is_some_and(|value| value.as_bytes().starts_with(b"application/json")),
+            "error responses are JSON, got content-type {:?}",
+            content_type
+        );
+
+        let error = assert_error_envelope(&self.json::<Value>());
+        assert_eq!(error.code, code, "error code of {}", self.text());
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testkit::app::TestAppBuilder;
+    use crate::testkit::token::TokenBuilder;
+    use axum::http::header;
+    use serde_json::json;
+
+    #[test]
+    fn test_accepts_each_error_type() {
+        assert_error_envelope(&json!({ "error": { "type": "GATE_ERROR", "code": "AUTH_TOKEN_MISSING", "message": "m", "gate": "AuthGate" } }));
+        assert_error_envelope(&json!({ "error": { "type": "DOMAIN_ERROR", "code": "USER_NOT_FOUND", "message": "m" } }));
+        assert_error_envelope(&json!({ "error": { "type": "PORT_ERROR", "code": "CIRCUIT_OPEN", "message": "m", "port": "UserRepository" } }));
+        assert_error_envelope(&json!({ "error": { "type": "DOMAIN_ERROR", "code": "INVALID_USER", "message": "m", "errors": [{ "code": "INVALID_USER_AGE", "message": "m" }] } }));
+    }
+
+    #[test]
+    fn test_rejects_malformed_envelopes() {
+        for body in [
+            json!({ "message": "flat" }),
+            json!({ "error": { "type": "GATE_ERROR", "code": "AUTH_TOKEN_MISSING", "message": "m" } }),
+            json!({ "error": { "type": "DOMAIN_ERROR", "code": "AUTH_TOKEN_MISSING", "message": "m" } }),
+            json!({ "error": { "type": "DOMAIN_ERROR", "code": "NOT_A_CODE", "message": "m" } }),
+            json!({ "error": { "type": "DOMAIN_ERROR", "code": "USER_NOT_FOUND", "message": "m", "stack": "" } }),
+            json!({ "error": { "type": "DOMAIN_ERROR", "code": "USER_NOT_FOUND", "message": "m" }, "debug": 1 }),
+            json!({ "error": { "type": "DOMAIN_ERROR", "code": "INVALID_USER", "message": "m", "errors": [{ "code": "NOT_A_CODE", "message": "m" }] } }),
+        ] {
+            assert!(std::panic::catch_unwind(|| assert_error_envelope(&body)).is_err(), "accepted {}", body);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gate_failures_use_the_envelope() {
+        let app = TestAppBuilder::new().build().await;
+        let users = |token: &TokenBuilder| app.server.get("/api/v1/users").add_header(header::AUTHORIZATION, token.bearer());
+
+        app.server.get("/api/v1/users").await.assert_error(StatusCode::UNAUTHORIZED, "AUTH_TOKEN_MISSING");
+        users(&TokenBuilder::new().expired()).await.assert_error(StatusCode::UNAUTHORIZED, "AUTH_TOKEN_INVALID");
+        users(&TokenBuilder::new().signed_with("forged")).await.assert_error(StatusCode::UNAUTHORIZED, "AUTH_TOKEN_INVALID");
+        users(&TokenBuilder::new()).await.assert_error(StatusCode::FORBIDDEN, "AUTH_INSUFFICIENT_SCOPES");
+    }
+}
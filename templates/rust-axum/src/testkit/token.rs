@@ -0,0 +1,157 @@
+//! BRIK v5 Testkit Tokens - Signed JWTs with arbitrary claims
+//!
+//! Defaults to a valid token for `TEST_JWT_SECRET`: subject `test-user`,
+//! the default tenant, no scopes, one hour to live, HS256. Every claim can be
+//! replaced or removed to exercise the failure paths of `AuthGate`.
+
+#![cfg(any(test, feature = "testkit"))]
+
+use crate::shared::tenancy::tenant::{DEFAULT_TENANT, DEFAULT_TENANT_CLAIM};
+use axum::http::{header, HeaderMap, HeaderValue};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Secret `TestAppBuilder` configures, so tokens from `TokenBuilder::new()` verify
+pub const TEST_JWT_SECRET: &str = "testkit-jwt-secret";
+
+#[derive(Debug, Clone)]
+pub struct TokenBuilder {
+    claims: Map<String, Value>,
+    algorithm: Algorithm,
+    secret: String,
+}
+
+impl Default for TokenBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenBuilder {
+    pub fn new() -> Self {
+        Self {
+            claims: Map::new(),
+            algorithm: Algorithm::HS256,
+            secret: TEST_JWT_SECRET.to_string(),
+        }
+        .subject("test-user")
+        .tenant(DEFAULT_TENANT)
+        .expires_in(Duration::hours(1))
+    }
+
+    pub fn subject(self, subject: &str) -> Self {
+        self.claim("sub", subject)
+    }
+
+    /// Sets the `tenant_id` claim; use `claim` for a custom tenant claim
+    pub fn tenant(self, tenant: &str) -> Self {
+        self.claim(DEFAULT_TENANT_CLAIM, tenant)
+    }
+
+    pub fn email(self, email: &str) -> Self {
+        self.claim("email", email)
+    }
+
+    pub fn scopes(self, scopes: &[&str]) -> Self {
+        self.claim("scopes", scopes)
+    }
+
+    pub fn roles(self, roles: &[&str]) -> Self {
+        self.claim("roles", roles)
+    }
+
+    /// Negative durations produce a token that is already expired
+    pub fn expires_in(self, ttl: Duration) -> Self {
+        self.claim("exp", (Utc::now() + ttl).timestamp())
+    }
+
+    /// Expired well past the default validation leeway
+    pub fn expired(self) -> Self {
+        self.expires_in(Duration::hours(-1))
+    }
+
+    pub fn claim<V: Serialize>(mut self, name: &str, value: V) -> Self {
+        let value = serde_json::to_value(value).expect("claim values serialize to JSON");
+        self.claims.insert(name.to_string(), value);
+        self
+    }
+
+    pub fn without_claim(mut self, name: &str) -> Self {
+        self.claims.remove(name);
+        self
+    }
+
+    /// HMAC algorithms only (HS256, HS384, HS512); the gate accepts HS256
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn signed_with(mut self, secret: &str) -> Self {
+        self.secret = secret.to_string();
+        self
+    }
+
+    pub fn build(&self) -> String {
+        encode(
+            &Header::new(self.algorithm),
+            &self.claims,
+            &EncodingKey::from_secret(self.secret.as_bytes()),
+        )
+        .expect("HMAC algorithms encode with a shared secret")
+    }
+
+    /// `Authorization` header value
+    pub fn bearer(&self) -> HeaderValue {
+        HeaderValue::from_str(&format!("Bearer {}", self.build())).expect("JWTs are valid header values")
+    }
+
+    /// Headers carrying the token, as `AuthGate::validate` expects them
+    pub fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, self.bearer());
+        headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{decode, DecodingKey, Validation};
+
+    fn claims(token: &str, algorithm: Algorithm) -> Map<String, Value> {
+        decode::<Map<String, Value>>(
+            token,
+            &DecodingKey::from_secret(TEST_JWT_SECRET.as_bytes()),
+            &Validation::new(algorithm),
+        )
+        .unwrap()
+        .claims
+    }
+
+    #[test]
+    fn test_default_token_is_valid() {
+        let claims = claims(&TokenBuilder::new().build(), Algorithm::HS256);
+
+        assert_eq!(claims["sub"], "test-user");
+        assert_eq!(claims["tenant_id"], "default");
+        assert!(claims["exp"].as_i64().unwrap() > Utc::now().timestamp());
+    }
+
+    #[test]
+    fn test_claims_can_be_replaced_and_removed() {
+        let token = TokenBuilder::new()
+            .scopes(&["users:read"])
+            .claim("department", "billing")
+            .without_claim("sub")
+            .algorithm(Algorithm::HS512)
+            .build();
+
+        let claims = claims(&token, Algorithm::HS512);
+        assert_eq!(claims["scopes"], serde_json::json!(["users:read"]));
+        assert_eq!(claims["department"], "billing");
+        assert!(!claims.contains_key("sub"));
+    }
+}